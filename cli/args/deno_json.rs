@@ -1,11 +1,26 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
 
+use deno_config::fs::DenoConfigFs;
+use deno_config::ConfigFile;
+use deno_config::FmtOptionsConfig;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use deno_core::url::Url;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
+use jsonc_parser::common::Range;
+use jsonc_parser::common::Ranged;
+use serde::Deserialize;
 
 pub fn deno_json_deps(
   config: &deno_config::ConfigFile,
@@ -49,3 +64,674 @@ fn values_to_set<'a>(
   }
   entries
 }
+
+/// Reads the config files for deno_config while keeping the sections of each
+/// file that are handled by the CLI rather than by deno_config.
+pub struct CliConfigFs<'a> {
+  fs: &'a (dyn DenoConfigFs + Sync),
+  sections: Mutex<HashMap<Url, Arc<CliConfigSections>>>,
+}
+
+impl<'a> CliConfigFs<'a> {
+  pub fn new(fs: &'a (dyn DenoConfigFs + Sync)) -> Self {
+    Self {
+      fs,
+      sections: Default::default(),
+    }
+  }
+
+  /// Takes the sections of the config files that were read so far.
+  pub fn take_sections(&self) -> CliConfigSectionsMap {
+    CliConfigSectionsMap(std::mem::take(&mut *self.sections.lock()))
+  }
+}
+
+impl<'a> DenoConfigFs for CliConfigFs<'a> {
+  fn read_to_string(&self, path: &Path) -> Result<String, std::io::Error> {
+    let text = self.fs.read_to_string(path)?;
+    // package.json files are read through here as well
+    if path.file_name().is_some_and(|name| name == "package.json") {
+      return Ok(text);
+    }
+    let Ok(specifier) = Url::from_file_path(path) else {
+      return Ok(text);
+    };
+    let (text, sections) = CliConfigSections::take_from_text(text);
+    self.sections.lock().insert(specifier, Arc::new(sections));
+    Ok(text)
+  }
+}
+
+/// The sections of a config file that are handled by the CLI.
+#[derive(Debug, Default)]
+pub struct CliConfigSections {
+  coverage: Option<Value>,
+  fmt_languages: Option<Value>,
+  lint_plugins: Option<Value>,
+  permissions: Option<Value>,
+  /// The tasks in the object form, which deno_config gets the command of.
+  tasks: HashMap<String, Value>,
+}
+
+impl CliConfigSections {
+  /// Takes the sections out of the text of a config file, returning the text
+  /// that is left for deno_config. The text is returned as is when it isn't
+  /// valid, so that deno_config reports the error.
+  pub fn take_from_text(text: String) -> (String, Self) {
+    let root = match jsonc_parser::parse_to_ast(
+      &text,
+      &Default::default(),
+      &Default::default(),
+    ) {
+      Ok(jsonc_parser::ParseResult {
+        value: Some(jsonc_parser::ast::Value::Object(root)),
+        ..
+      }) => root,
+      _ => return (text, Default::default()),
+    };
+    let mut sections = CliConfigSections {
+      coverage: root
+        .get("coverage")
+        .map(|prop| Value::from(prop.value.clone())),
+      permissions: root
+        .get("permissions")
+        .map(|prop| Value::from(prop.value.clone())),
+      ..Default::default()
+    };
+    let mut replacements = Vec::new();
+    if let Some(fmt) = root.get_object("fmt") {
+      let taken = take_properties(
+        fmt,
+        &["css", "html", "yaml", "sql", "embeddedTemplates"],
+        &mut replacements,
+      );
+      if !taken.is_empty() {
+        sections.fmt_languages = Some(Value::Object(taken));
+      }
+    }
+    if let Some(lint) = root.get_object("lint") {
+      let mut taken = take_properties(lint, &["plugins"], &mut replacements);
+      sections.lint_plugins = taken.remove("plugins");
+    }
+    if let Some(tasks) = root.get_object("tasks") {
+      for prop in &tasks.properties {
+        let jsonc_parser::ast::Value::Object(task) = &prop.value else {
+          continue;
+        };
+        // an invalid task is reported when it's parsed
+        let command = task
+          .get_string("command")
+          .map(|command| command.value.to_string())
+          .unwrap_or_default();
+        sections.tasks.insert(
+          prop.name.as_str().to_string(),
+          Value::from(prop.value.clone()),
+        );
+        replacements
+          .push((*prop.value.range(), Value::from(command).to_string()));
+      }
+    }
+    (replace_ranges(&text, replacements), sections)
+  }
+
+  pub fn to_coverage_config(&self) -> Result<CoverageConfig, AnyError> {
+    match self.coverage.clone() {
+      Some(config) => serde_json::from_value(config)
+        .context("Failed to parse \"coverage\" configuration"),
+      None => Ok(CoverageConfig::default()),
+    }
+  }
+
+  /// Gets the options of the formatters of other languages, which are in the
+  /// "fmt" configuration.
+  pub fn to_fmt_languages_config(
+    &self,
+  ) -> Result<FmtLanguagesConfig, AnyError> {
+    match self.fmt_languages.clone() {
+      Some(config) => serde_json::from_value(config)
+        .context("Failed to parse \"fmt\" configuration"),
+      None => Ok(FmtLanguagesConfig::default()),
+    }
+  }
+
+  /// Gets the modules of the "lint.plugins" configuration, resolved relative
+  /// to the config file.
+  pub fn to_lint_plugins(
+    &self,
+    config_file_specifier: &Url,
+  ) -> Result<Vec<Url>, AnyError> {
+    let Some(plugins) = self.lint_plugins.clone() else {
+      return Ok(Vec::new());
+    };
+    let plugins: Vec<String> = serde_json::from_value(plugins)
+      .context("Failed to parse \"lint.plugins\" configuration")?;
+    plugins
+      .iter()
+      .map(|plugin| {
+        config_file_specifier
+          .join(plugin)
+          .with_context(|| format!("Invalid lint plugin \"{}\"", plugin))
+      })
+      .collect()
+  }
+
+  /// Gets the named permission sets of the "permissions" configuration.
+  pub fn to_permissions_config(
+    &self,
+  ) -> Result<Option<BTreeMap<String, PermissionSetConfig>>, AnyError> {
+    match self.permissions.clone() {
+      Some(config) => serde_json::from_value(config)
+        .context("Failed to parse \"permissions\" configuration")
+        .map(Some),
+      None => Ok(None),
+    }
+  }
+
+  /// Gets the definition of a task in the object form.
+  pub fn to_task_definition(
+    &self,
+    task_name: &str,
+  ) -> Result<Option<TaskDefinition>, AnyError> {
+    match self.tasks.get(task_name) {
+      Some(task) => serde_json::from_value(task.clone())
+        .with_context(|| format!("Failed to parse task \"{}\"", task_name))
+        .map(Some),
+      None => Ok(None),
+    }
+  }
+}
+
+/// Takes properties out of an object of a config file, which is replaced by
+/// an object with the remaining properties.
+fn take_properties(
+  object: &jsonc_parser::ast::Object,
+  names: &[&str],
+  replacements: &mut Vec<(Range, String)>,
+) -> serde_json::Map<String, Value> {
+  let mut remaining = serde_json::Map::new();
+  let mut taken = serde_json::Map::new();
+  for prop in &object.properties {
+    let name = prop.name.as_str().to_string();
+    let value = Value::from(prop.value.clone());
+    if names.contains(&name.as_str()) {
+      taken.insert(name, value);
+    } else {
+      remaining.insert(name, value);
+    }
+  }
+  if !taken.is_empty() {
+    replacements.push((object.range, Value::Object(remaining).to_string()));
+  }
+  taken
+}
+
+/// Replaces ranges of a text, which don't overlap.
+fn replace_ranges(
+  text: &str,
+  mut replacements: Vec<(Range, String)>,
+) -> String {
+  replacements.sort_by_key(|(range, _)| range.start);
+  let mut result = String::with_capacity(text.len());
+  let mut last_end = 0;
+  for (range, replacement) in replacements {
+    result.push_str(&text[last_end..range.start]);
+    result.push_str(&replacement);
+    last_end = range.end;
+  }
+  result.push_str(&text[last_end..]);
+  result
+}
+
+/// The sections handled by the CLI of each config file of a workspace.
+#[derive(Debug, Default)]
+pub struct CliConfigSectionsMap(HashMap<Url, Arc<CliConfigSections>>);
+
+impl CliConfigSectionsMap {
+  pub fn get(&self, config_file: &ConfigFile) -> Arc<CliConfigSections> {
+    self
+      .0
+      .get(&config_file.specifier)
+      .cloned()
+      .unwrap_or_default()
+  }
+}
+
+/// A task in the object form, with the command, the tasks it depends on and
+/// the files it reads and writes.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TaskDefinition {
+  pub command: String,
+  /// The tasks to run before this one.
+  #[serde(default)]
+  pub dependencies: Vec<String>,
+  /// Globs of the files that the task reads. The task is skipped when these
+  /// and its outputs didn't change since its last successful run.
+  #[serde(default)]
+  pub inputs: Vec<String>,
+  /// Globs of the files that the task writes.
+  #[serde(default)]
+  pub outputs: Vec<String>,
+}
+
+/// Options of the formatters of CSS, HTML, YAML and SQL that override the
+/// `fmt` options.
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FmtLanguagesConfig {
+  pub css: FmtLanguageOptions,
+  pub html: FmtLanguageOptions,
+  pub yaml: FmtLanguageOptions,
+  pub sql: FmtSqlOptions,
+  /// Whether to format the contents of the template literals tagged with
+  /// `css`, `html`, `sql`, `gql` or `graphql` in JS and TS files.
+  pub embedded_templates: Option<bool>,
+}
+
+impl FmtLanguagesConfig {
+  /// Combines the options of a workspace member with the ones of the root.
+  pub fn or(self, root: Self) -> Self {
+    Self {
+      css: self.css.or(root.css),
+      html: self.html.or(root.html),
+      yaml: self.yaml.or(root.yaml),
+      sql: self.sql.or(root.sql),
+      embedded_templates: self.embedded_templates.or(root.embedded_templates),
+    }
+  }
+
+  /// Gets the options without the line widths, for formatting code that's
+  /// embedded in another language, which has its own line width.
+  pub fn without_line_widths(&self) -> Self {
+    let mut languages = self.clone();
+    languages.css.line_width = None;
+    languages.html.line_width = None;
+    languages.yaml.line_width = None;
+    languages
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct FmtLanguageOptions {
+  pub use_tabs: Option<bool>,
+  pub line_width: Option<u32>,
+  pub indent_width: Option<u8>,
+  pub single_quote: Option<bool>,
+}
+
+impl FmtLanguageOptions {
+  /// Combines the options of a workspace member with the ones of the root.
+  pub fn or(self, root: Self) -> Self {
+    Self {
+      use_tabs: self.use_tabs.or(root.use_tabs),
+      line_width: self.line_width.or(root.line_width),
+      indent_width: self.indent_width.or(root.indent_width),
+      single_quote: self.single_quote.or(root.single_quote),
+    }
+  }
+
+  /// Gets the `fmt` options with the ones of the language applied.
+  pub fn apply(&self, options: &FmtOptionsConfig) -> FmtOptionsConfig {
+    let mut options = options.clone();
+    if let Some(use_tabs) = self.use_tabs {
+      options.use_tabs = Some(use_tabs);
+    }
+    if let Some(line_width) = self.line_width {
+      options.line_width = Some(line_width);
+    }
+    if let Some(indent_width) = self.indent_width {
+      options.indent_width = Some(indent_width);
+    }
+    if let Some(single_quote) = self.single_quote {
+      options.single_quote = Some(single_quote);
+    }
+    options
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Hash, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct FmtSqlOptions {
+  pub use_tabs: Option<bool>,
+  pub indent_width: Option<u8>,
+  /// Whether to uppercase the keywords, which keep their casing otherwise.
+  pub uppercase: Option<bool>,
+}
+
+impl FmtSqlOptions {
+  /// Combines the options of a workspace member with the ones of the root.
+  pub fn or(self, root: Self) -> Self {
+    Self {
+      use_tabs: self.use_tabs.or(root.use_tabs),
+      indent_width: self.indent_width.or(root.indent_width),
+      uppercase: self.uppercase.or(root.uppercase),
+    }
+  }
+}
+
+/// The permissions of a kind, either all of them with `true` or a list.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PermissionListConfig {
+  All(bool),
+  List(Vec<String>),
+}
+
+/// The permissions of each kind, which are denied in a permission set.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PermissionKindsConfig {
+  pub read: Option<PermissionListConfig>,
+  pub write: Option<PermissionListConfig>,
+  pub net: Option<PermissionListConfig>,
+  pub env: Option<PermissionListConfig>,
+  pub run: Option<PermissionListConfig>,
+  pub ffi: Option<PermissionListConfig>,
+  pub sys: Option<PermissionListConfig>,
+  pub hrtime: bool,
+}
+
+/// A named set of permissions in the `permissions` section, with the allowed
+/// permissions at the top level.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PermissionSetConfig {
+  pub all: bool,
+  pub read: Option<PermissionListConfig>,
+  pub write: Option<PermissionListConfig>,
+  pub net: Option<PermissionListConfig>,
+  pub env: Option<PermissionListConfig>,
+  pub run: Option<PermissionListConfig>,
+  pub ffi: Option<PermissionListConfig>,
+  pub sys: Option<PermissionListConfig>,
+  pub hrtime: bool,
+  pub deny: PermissionKindsConfig,
+}
+
+impl PermissionSetConfig {
+  /// The permissions of each kind that are allowed.
+  pub fn allow(&self) -> PermissionKindsConfig {
+    PermissionKindsConfig {
+      read: self.read.clone(),
+      write: self.write.clone(),
+      net: self.net.clone(),
+      env: self.env.clone(),
+      run: self.run.clone(),
+      ffi: self.ffi.clone(),
+      sys: self.sys.clone(),
+      hrtime: self.hrtime,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoverageConfig {
+  pub thresholds: CoverageThresholdsConfig,
+}
+
+/// The minimum coverage percentages of `deno coverage`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct CoverageThresholdsConfig {
+  pub lines: Option<u8>,
+  pub branches: Option<u8>,
+  pub functions: Option<u8>,
+  pub per_file: bool,
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_config::workspace::Workspace;
+  use deno_config::workspace::WorkspaceDiscoverOptions;
+  use deno_config::workspace::WorkspaceDiscoverStart;
+  use deno_core::serde_json::json;
+  use std::path::PathBuf;
+
+  #[derive(Default)]
+  struct TestFs(HashMap<PathBuf, String>);
+
+  impl DenoConfigFs for TestFs {
+    fn read_to_string(&self, path: &Path) -> Result<String, std::io::Error> {
+      self.0.get(path).cloned().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "file not found")
+      })
+    }
+  }
+
+  fn root_dir() -> PathBuf {
+    if cfg!(windows) {
+      PathBuf::from("C:\\root")
+    } else {
+      PathBuf::from("/root")
+    }
+  }
+
+  fn discover(fs: TestFs) -> (Workspace, CliConfigSectionsMap) {
+    let config_fs = CliConfigFs::new(&fs);
+    let workspace = Workspace::discover(
+      WorkspaceDiscoverStart::Dirs(&[root_dir()]),
+      &WorkspaceDiscoverOptions {
+        fs: &config_fs,
+        pkg_json_cache: None,
+        config_parse_options: Default::default(),
+        additional_config_file_names: &[],
+        discover_pkg_json: false,
+      },
+    )
+    .unwrap();
+    (workspace, config_fs.take_sections())
+  }
+
+  #[test]
+  fn coverage_config() {
+    let mut fs = TestFs::default();
+    fs.0.insert(
+      root_dir().join("deno.json"),
+      json!({
+        "coverage": { "thresholds": { "lines": 80, "perFile": true } }
+      })
+      .to_string(),
+    );
+    let (workspace, sections) = discover(fs);
+    let config_file = workspace.root_folder().1.deno_json.as_ref().unwrap();
+    assert_eq!(
+      sections.get(config_file).to_coverage_config().unwrap(),
+      CoverageConfig {
+        thresholds: CoverageThresholdsConfig {
+          lines: Some(80),
+          per_file: true,
+          ..Default::default()
+        },
+      }
+    );
+
+    let (_, sections) = CliConfigSections::take_from_text(
+      r#"{ "coverage": { "thresholds": { "line": 80 } } }"#.to_string(),
+    );
+    assert!(sections.to_coverage_config().is_err());
+  }
+
+  #[test]
+  fn fmt_languages_config() {
+    let mut fs = TestFs::default();
+    fs.0.insert(
+      root_dir().join("deno.json"),
+      json!({
+        "workspace": ["./member"],
+        "fmt": {
+          "indentWidth": 4,
+          "css": { "indentWidth": 2, "lineWidth": 100 },
+          "embeddedTemplates": true
+        }
+      })
+      .to_string(),
+    );
+    fs.0.insert(
+      root_dir().join("member/deno.json"),
+      json!({
+        "fmt": {
+          "css": { "indentWidth": 4 },
+          "sql": { "uppercase": true }
+        }
+      })
+      .to_string(),
+    );
+    let (workspace, sections) = discover(fs);
+    let languages = |dir: &str| {
+      let dir_url = Url::from_directory_path(root_dir().join(dir)).unwrap();
+      let folder = workspace.config_folders().get(&dir_url).unwrap();
+      sections
+        .get(folder.deno_json.as_ref().unwrap())
+        .to_fmt_languages_config()
+        .unwrap()
+    };
+    let root_languages = languages("");
+    assert_eq!(
+      root_languages,
+      FmtLanguagesConfig {
+        css: FmtLanguageOptions {
+          indent_width: Some(2),
+          line_width: Some(100),
+          ..Default::default()
+        },
+        embedded_templates: Some(true),
+        ..Default::default()
+      }
+    );
+    assert_eq!(
+      languages("member").or(root_languages),
+      FmtLanguagesConfig {
+        css: FmtLanguageOptions {
+          indent_width: Some(4),
+          line_width: Some(100),
+          ..Default::default()
+        },
+        sql: FmtSqlOptions {
+          uppercase: Some(true),
+          ..Default::default()
+        },
+        embedded_templates: Some(true),
+        ..Default::default()
+      }
+    );
+    // the other options are left for deno_config
+    let ctx = Arc::new(workspace).resolve_start_ctx();
+    assert_eq!(ctx.to_fmt_config().unwrap().options.indent_width, Some(4));
+
+    let (_, sections) = CliConfigSections::take_from_text(
+      r#"{ "fmt": { "css": { "indent": 2 } } }"#.to_string(),
+    );
+    assert!(sections.to_fmt_languages_config().is_err());
+  }
+
+  #[test]
+  fn lint_plugins() {
+    let mut fs = TestFs::default();
+    fs.0.insert(
+      root_dir().join("deno.json"),
+      json!({
+        "workspace": ["./member"],
+        "lint": {
+          "plugins": ["./plugins/a.ts"],
+          "rules": { "include": ["no-console"] }
+        }
+      })
+      .to_string(),
+    );
+    fs.0.insert(
+      root_dir().join("member/deno.json"),
+      json!({
+        "lint": { "plugins": ["../plugins/a.ts", "./plugins/b.ts"] }
+      })
+      .to_string(),
+    );
+    let (workspace, sections) = discover(fs);
+    let lint_plugins = |dir: &str| {
+      let dir_url = Url::from_directory_path(root_dir().join(dir)).unwrap();
+      let folder = workspace.config_folders().get(&dir_url).unwrap();
+      let config_file = folder.deno_json.as_ref().unwrap();
+      sections
+        .get(config_file)
+        .to_lint_plugins(&config_file.specifier)
+        .unwrap()
+    };
+    assert_eq!(
+      lint_plugins(""),
+      vec![Url::from_file_path(root_dir().join("plugins/a.ts")).unwrap()]
+    );
+    assert_eq!(
+      lint_plugins("member"),
+      vec![
+        Url::from_file_path(root_dir().join("plugins/a.ts")).unwrap(),
+        Url::from_file_path(root_dir().join("member/plugins/b.ts")).unwrap(),
+      ]
+    );
+    // the other options are left for deno_config
+    let ctx = Arc::new(workspace).resolve_start_ctx();
+    assert_eq!(
+      ctx.to_lint_config().unwrap().rules.include,
+      Some(vec!["no-console".to_string()])
+    );
+
+    let (_, sections) = CliConfigSections::take_from_text(
+      r#"{ "lint": { "plugins": "./plugin.ts" } }"#.to_string(),
+    );
+    assert!(sections
+      .to_lint_plugins(&Url::parse("file:///deno.json").unwrap())
+      .is_err());
+  }
+
+  #[test]
+  fn task_definitions() {
+    let (text, sections) = CliConfigSections::take_from_text(
+      r#"{
+        "tasks": {
+          // builds the app
+          "build": {
+            "command": "deno run -A build.ts",
+            "dependencies": ["clean"],
+            "inputs": ["src/**"],
+            "outputs": ["dist/**"]
+          },
+          "clean": "rm -rf dist",
+          "invalid": { "command": "echo", "dependecies": [] }
+        }
+      }"#
+        .to_string(),
+    );
+    assert_eq!(
+      sections.to_task_definition("build").unwrap(),
+      Some(TaskDefinition {
+        command: "deno run -A build.ts".to_string(),
+        dependencies: vec!["clean".to_string()],
+        inputs: vec!["src/**".to_string()],
+        outputs: vec!["dist/**".to_string()],
+      })
+    );
+    assert_eq!(sections.to_task_definition("clean").unwrap(), None);
+    let err = sections.to_task_definition("invalid").unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown field `dependecies`"));
+
+    // the comments of the tasks are kept
+    let config_file = ConfigFile::new(
+      &text,
+      Url::from_file_path(root_dir().join("deno.json")).unwrap(),
+      &deno_config::ConfigParseOptions {
+        include_task_comments: true,
+      },
+    )
+    .unwrap();
+    let tasks = config_file.resolve_tasks_config().unwrap();
+    assert_eq!(tasks["clean"].definition(), "rm -rf dist");
+    assert_eq!(
+      tasks["build"],
+      deno_config::Task::Commented {
+        definition: "deno run -A build.ts".into(),
+        comments: vec!["builds the app".to_string()],
+      }
+    );
+  }
+}
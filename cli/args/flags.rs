@@ -5,6 +5,7 @@ use clap::builder::FalseyValueParser;
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -21,6 +22,8 @@ use deno_core::url::Url;
 use deno_graph::GraphKind;
 use deno_runtime::deno_permissions::parse_sys_kind;
 use deno_runtime::deno_permissions::PermissionsOptions;
+use deno_runtime::deno_permissions::PromptSocketAddress;
+use deno_runtime::deno_permissions::SocketPrompter;
 use log::debug;
 use log::Level;
 use serde::Deserialize;
@@ -34,6 +37,7 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::args::resolve_no_prompt;
 use crate::util::collections::CheckedSet;
@@ -82,6 +86,9 @@ pub struct BenchFlags {
   pub filter: Option<String>,
   pub json: bool,
   pub no_run: bool,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  pub regression_threshold: Option<u32>,
   pub watch: Option<WatchFlags>,
 }
 
@@ -97,9 +104,17 @@ pub struct CacheFlags {
   pub files: Vec<String>,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CheckReporterKind {
+  #[default]
+  Pretty,
+  Sarif,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CheckFlags {
   pub files: Vec<String>,
+  pub reporter: CheckReporterKind,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -133,6 +148,19 @@ pub enum CoverageType {
   Detailed,
   Lcov,
   Html,
+  Cobertura,
+  JsonSummary,
+}
+
+/// Minimum coverage percentages, below which `deno coverage` fails.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct CoverageThresholds {
+  pub lines: Option<u8>,
+  pub branches: Option<u8>,
+  pub functions: Option<u8>,
+  /// Whether each file has to meet the thresholds, not only all files
+  /// together.
+  pub per_file: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
@@ -142,6 +170,7 @@ pub struct CoverageFlags {
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub r#type: CoverageType,
+  pub thresholds: CoverageThresholds,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -193,6 +222,9 @@ pub struct FmtFlags {
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
   pub watch: Option<WatchFlags>,
+  /// The git ref to format the changed files against.
+  pub changed: Option<String>,
+  pub changed_lines: bool,
 }
 
 impl FmtFlags {
@@ -271,6 +303,9 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub sarif: bool,
+  pub baseline: Option<String>,
+  pub write_baseline: bool,
   pub watch: Option<WatchFlags>,
 }
 
@@ -288,10 +323,18 @@ pub struct ReplFlags {
   pub is_default_command: bool,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LearnPermissionsFormat {
+  Flags,
+  Config,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct RunFlags {
   pub script: String,
   pub watch: Option<WatchFlagsWithPaths>,
+  /// Runs with all the permissions and prints the ones that were used.
+  pub learn_permissions: Option<LearnPermissionsFormat>,
 }
 
 impl RunFlags {
@@ -300,6 +343,7 @@ impl RunFlags {
     Self {
       script,
       watch: None,
+      learn_permissions: None,
     }
   }
 
@@ -347,6 +391,8 @@ pub struct WatchFlagsWithPaths {
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  pub recursive: bool,
+  pub filter: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -356,6 +402,15 @@ pub enum TestReporterConfig {
   Dot,
   Junit,
   Tap,
+  Json,
+}
+
+/// A `--shard=<INDEX>/<COUNT>` selection of the collected test modules.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TestShard {
+  /// One-based index of the shard to run.
+  pub index: usize,
+  pub count: usize,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -365,15 +420,22 @@ pub struct TestFlags {
   pub coverage_dir: Option<String>,
   pub clean: bool,
   pub fail_fast: Option<NonZeroUsize>,
+  pub retries: usize,
+  pub timeout: Option<u64>,
+  pub only_failed: bool,
   pub files: FileFlags,
   pub allow_none: bool,
   pub filter: Option<String>,
   pub shuffle: Option<u64>,
+  pub shard: Option<TestShard>,
+  pub shard_durations: Option<String>,
   pub concurrent_jobs: Option<NonZeroUsize>,
   pub trace_leaks: bool,
   pub watch: Option<WatchFlags>,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub json_path: Option<String>,
+  pub update_snapshots: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -563,6 +625,9 @@ pub struct Flags {
   pub permissions: PermissionFlags,
 }
 
+/// Seconds to wait for the answer of a prompt sent to the prompt socket.
+const DEFAULT_PERMISSION_PROMPT_TIMEOUT: u64 = 30;
+
 #[derive(Clone, Debug, Eq, PartialEq, Default, Serialize, Deserialize)]
 pub struct PermissionFlags {
   pub allow_all: bool,
@@ -583,6 +648,14 @@ pub struct PermissionFlags {
   pub allow_write: Option<Vec<String>>,
   pub deny_write: Option<Vec<String>>,
   pub no_prompt: bool,
+  /// The name of the permission set of the config file to use.
+  pub permission_set: Option<String>,
+  /// The file where the permission checks are written as JSON lines.
+  pub permission_audit: Option<String>,
+  /// The socket address where the permission prompts are sent as JSON lines.
+  pub permission_prompt_socket: Option<String>,
+  /// How many seconds to wait for the answer of a prompt sent to the socket.
+  pub permission_prompt_timeout: Option<u64>,
 }
 
 impl PermissionFlags {
@@ -604,6 +677,7 @@ impl PermissionFlags {
       || self.deny_sys.is_some()
       || self.allow_write.is_some()
       || self.deny_write.is_some()
+      || self.permission_set.is_some()
   }
 
   pub fn to_options(
@@ -665,6 +739,43 @@ impl PermissionFlags {
       prompt: !resolve_no_prompt(self),
     })
   }
+
+  /// Starts writing the permission checks to the audit log file, when one
+  /// was given.
+  pub fn init_audit_log(
+    &self,
+    initial_cwd: Option<&Path>,
+  ) -> Result<(), AnyError> {
+    let Some(path) = &self.permission_audit else {
+      return Ok(());
+    };
+    let path = match initial_cwd {
+      Some(initial_cwd) => initial_cwd.join(path),
+      None => PathBuf::from(path),
+    };
+    let file = std::fs::File::create(&path).with_context(|| {
+      format!("Failed creating permission audit log {}", path.display())
+    })?;
+    deno_runtime::deno_permissions::set_audit_log(Box::new(file));
+    Ok(())
+  }
+
+  /// Sends the permission prompts to the socket, when one was given.
+  pub fn init_prompt_socket(&self) -> Result<(), AnyError> {
+    let Some(address) = &self.permission_prompt_socket else {
+      return Ok(());
+    };
+    let address = address.parse::<PromptSocketAddress>()?;
+    let timeout = Duration::from_secs(
+      self
+        .permission_prompt_timeout
+        .unwrap_or(DEFAULT_PERMISSION_PROMPT_TIMEOUT),
+    );
+    deno_runtime::deno_permissions::set_prompter(Box::new(
+      SocketPrompter::new(address, timeout),
+    ));
+    Ok(())
+  }
 }
 
 fn join_paths(allowlist: &[String], d: &str) -> String {
@@ -677,7 +788,8 @@ fn join_paths(allowlist: &[String], d: &str) -> String {
 
 impl Flags {
   /// Return list of permission arguments that are equivalent
-  /// to the ones used to create `self`.
+  /// to the ones used to create `self`. A permission set has to be resolved
+  /// into the flags first, since it depends on the config file.
   pub fn to_permission_args(&self) -> Vec<String> {
     let mut args = vec![];
 
@@ -945,6 +1057,8 @@ impl Flags {
   pub fn has_permission_in_argv(&self) -> bool {
     self.argv.iter().any(|arg| {
       arg == "--allow-all"
+        || arg == "-P"
+        || arg.starts_with("--permission-set")
         || arg == "--allow-hrtime"
         || arg == "--deny-hrtime"
         || arg.starts_with("--allow-env")
@@ -1398,7 +1512,12 @@ and report results to standard output:
 Directory arguments are expanded to all contained files matching the
 glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
 
-  deno bench src/",
+  deno bench src/
+
+Save the results as a baseline, and compare later runs with it:
+
+  deno bench --save-baseline=main
+  deno bench --baseline=main --regression-threshold=10",
     )
     .defer(|cmd| {
       runtime_args(cmd, true, false)
@@ -1437,6 +1556,29 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
             .help("Cache bench modules, but don't run benchmarks")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("save-baseline")
+            .long("save-baseline")
+            .value_name("NAME")
+            .require_equals(true)
+            .help("Save the results of the benchmarks as a named baseline"),
+        )
+        .arg(
+          Arg::new("baseline")
+            .long("baseline")
+            .value_name("NAME")
+            .require_equals(true)
+            .help("Compare the results of the benchmarks with a saved baseline"),
+        )
+        .arg(
+          Arg::new("regression-threshold")
+            .long("regression-threshold")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .requires("baseline")
+            .value_parser(value_parser!(u32))
+            .help("Fail if a benchmark is significantly slower than the baseline by more than this percentage"),
+        )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
@@ -1530,6 +1672,12 @@ Unless --reload is specified, this command will not re-download already cached d
           .conflicts_with("no-remote")
           .hide(true)
       )
+      .arg(
+        Arg::new("reporter")
+          .long("reporter")
+          .help("Select reporter to use. Default to 'pretty'.")
+          .value_parser(["pretty", "sarif"])
+      )
       .arg(
         Arg::new("file")
           .num_args(1..)
@@ -1665,6 +1813,26 @@ Write a report using the lcov format:
 Generate html reports from lcov:
 
   genhtml -o html_cov cov.lcov
+
+Write a report using the Cobertura XML format:
+
+  deno coverage --cobertura --output=cobertura.xml cov_profile/
+
+Write a summary of the coverage as JSON:
+
+  deno coverage --json-summary --output=coverage-summary.json cov_profile/
+
+Fail when less than 80% of the lines of each file are covered:
+
+  deno coverage --threshold-lines=80 --threshold-per-file cov_profile/
+
+Thresholds can also be set in the config file:
+
+  {
+    \"coverage\": {
+      \"thresholds\": { \"lines\": 80, \"branches\": 70, \"functions\": 80 }
+    }
+  }
 ",
     )
     .defer(|cmd| {
@@ -1704,14 +1872,33 @@ Generate html reports from lcov:
             .help("Output coverage report in lcov format")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("cobertura")
+            .long("cobertura")
+            .help("Output coverage report in Cobertura XML format")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("json-summary")
+            .long("json-summary")
+            .help("Output a summary of the coverage report in JSON format")
+            .action(ArgAction::SetTrue),
+        )
+        .group(
+          ArgGroup::new("file_report")
+            .args(["lcov", "cobertura", "json-summary"])
+            .multiple(false),
+        )
         .arg(
           Arg::new("output")
-            .requires("lcov")
+            .requires("file_report")
             .long("output")
             .value_parser(value_parser!(String))
-            .help("Output file (defaults to stdout) for lcov")
+            .help(
+              "Output file (defaults to stdout) for lcov, cobertura or json-summary",
+            )
             .long_help(
-              "Exports the coverage report in lcov format to the given file.
+              "Exports the coverage report in lcov, Cobertura or JSON summary format to the given file.
     Filename should be passed along with '=' For example '--output=foo.lcov'
     If no --output arg is specified then the report is written to stdout.",
             )
@@ -1732,6 +1919,36 @@ Generate html reports from lcov:
             .help("Output coverage report in detailed format in the terminal.")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("threshold-lines")
+            .long("threshold-lines")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help("Fail if less than PERCENT of the lines are covered"),
+        )
+        .arg(
+          Arg::new("threshold-branches")
+            .long("threshold-branches")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help("Fail if less than PERCENT of the branches are covered"),
+        )
+        .arg(
+          Arg::new("threshold-functions")
+            .long("threshold-functions")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help("Fail if less than PERCENT of the functions are covered"),
+        )
+        .arg(
+          Arg::new("threshold-per-file")
+            .long("threshold-per-file")
+            .help("Check the coverage thresholds for each file, not only for all files together")
+            .action(ArgAction::SetTrue),
+        )
         .arg(
           Arg::new("files")
             .num_args(0..)
@@ -1933,12 +2150,19 @@ fn fmt_subcommand() -> Command {
   Command::new("fmt")
     .about("Format source files")
     .long_about(
-      "Auto-format JavaScript, TypeScript, Markdown, and JSON files.
+      "Auto-format JavaScript, TypeScript, Markdown, JSON, CSS, HTML, YAML, and SQL
+files.
 
   deno fmt
   deno fmt myfile1.ts myfile2.ts
   deno fmt --check
 
+Only format the files changed against a git ref (defaults to HEAD), or only
+their changed lines:
+
+  deno fmt --changed
+  deno fmt --changed=main --changed-lines
+
 Format stdin and write to stdout:
 
   cat file.ts | deno fmt -
@@ -1968,7 +2192,8 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             // prefer using ts for formatting instead of js because ts works in more scenarios
             .default_value("ts")
             .value_parser([
-              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "ipynb",
+              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "ipynb", "css",
+              "scss", "less", "html", "yaml", "yml", "sql",
             ]),
         )
         .arg(
@@ -1987,6 +2212,22 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .required(false)
             .value_hint(ValueHint::AnyPath),
         )
+        .arg(
+          Arg::new("changed")
+            .long("changed")
+            .num_args(0..=1)
+            .default_missing_value("HEAD")
+            .require_equals(true)
+            .value_name("GIT_REF")
+            .help("Only format the files changed against a git ref, including uncommitted and untracked files. Defaults to HEAD."),
+        )
+        .arg(
+          Arg::new("changed-lines")
+            .long("changed-lines")
+            .requires("changed")
+            .action(ArgAction::SetTrue)
+            .help("Only format the lines changed against the git ref of --changed"),
+        )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
@@ -2081,9 +2322,10 @@ Without any additional arguments, 'deno info' shows:
 
 DENO_DIR: Directory containing Deno-managed files.
 Remote modules cache: Subdirectory containing downloaded remote modules.
-TypeScript compiler cache: Subdirectory containing TS compiler output.",
+TypeScript compiler cache: Subdirectory containing TS compiler output.
+Permissions: Effective permissions, when a permission set or flags are used.",
       )
-    .defer(|cmd| cmd
+    .defer(|cmd| permission_args(cmd)
       .arg(Arg::new("file").required(false).value_hint(ValueHint::FilePath))
       .arg(reload_arg().requires("file"))
       .arg(ca_file_arg())
@@ -2347,6 +2589,11 @@ List available rules:
 
   deno lint --rules
 
+Record the current problems in a baseline file, so that later runs only report
+new problems:
+
+  deno lint --write-baseline
+
 Ignore diagnostics on the next line by preceding it with an ignore comment and
 rule name:
 
@@ -2425,6 +2672,28 @@ Ignore linting a file by adding an ignore comment at the top of the file:
             .action(ArgAction::SetTrue)
             .conflicts_with("json"),
         )
+        .arg(
+          Arg::new("sarif")
+            .long("sarif")
+            .help("Output lint result in SARIF format")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["json", "compact"]),
+        )
+        .arg(
+          Arg::new("baseline")
+            .long("baseline")
+            .value_name("FILE")
+            .help("Path of the lint baseline file [default: deno-lint-baseline.json in the workspace root]")
+            .long_help("Path of the lint baseline file. Problems recorded in it are not reported, and the ones that were fixed are removed from it. Defaults to deno-lint-baseline.json in the root of the workspace, which is used when it exists.")
+            .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+          Arg::new("write-baseline")
+            .long("write-baseline")
+            .help("Record the current problems in the lint baseline file instead of reporting them")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("rules"),
+        )
         .arg(
           Arg::new("files")
             .num_args(1..)
@@ -2476,6 +2745,16 @@ fn run_subcommand() -> Command {
     )
     .arg(env_file_arg())
     .arg(no_code_cache_arg())
+    .arg(
+      Arg::new("learn-permissions")
+        .long("learn-permissions")
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_missing_value("flags")
+        .value_parser(["flags", "config"])
+        .value_name("FORMAT")
+        .help("Run with all permissions and print the narrowest permission flags, or permission set of the config file, that allow what was used"),
+    )
     .about("Run a JavaScript or TypeScript program")
     .long_about(
       "Run a JavaScript or TypeScript program
@@ -2562,7 +2841,16 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+Run a task in each workspace member that defines it, with the members that
+others depend on first:
+
+  deno task --recursive build
+
+Only run it in the workspace members whose name or directory match a pattern:
+
+  deno task --filter \"@scope/*\" build",
     )
     .defer(|cmd| {
       cmd
@@ -2576,6 +2864,22 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(
+          Arg::new("recursive")
+            .long("recursive")
+            .short('r')
+            .help("Run the task in each workspace member that defines it")
+            .conflicts_with("cwd")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("filter")
+            .long("filter")
+            .short('f')
+            .value_name("PATTERN")
+            .help("Run the task in the workspace members whose name or directory match the pattern. Implies --recursive")
+            .conflicts_with("cwd"),
+        )
     })
 }
 
@@ -2641,6 +2945,29 @@ Directory arguments are expanded to all contained files matching the glob
         .value_name("N")
         .value_parser(value_parser!(NonZeroUsize)),
     )
+    .arg(
+      Arg::new("retries")
+        .long("retries")
+        .value_name("N")
+        .help("Re-run failing tests up to N times. Tests that pass after being retried are reported as flaky.")
+        .require_equals(true)
+        .value_parser(value_parser!(usize)),
+    )
+    .arg(
+      Arg::new("timeout")
+        .long("timeout")
+        .value_name("MS")
+        .help("Fail tests that take longer than MS milliseconds. The remaining tests of a module with a timed out test are cancelled.")
+        .require_equals(true)
+        .value_parser(value_parser!(u64).range(1..)),
+    )
+    .arg(
+      Arg::new("only-failed")
+        .long("only-failed")
+        .help("Only run the tests that failed in the previous run. Runs all tests if the test files changed since.")
+        .conflicts_with("watch")
+        .action(ArgAction::SetTrue),
+    )
     .arg(
       Arg::new("allow-none")
         .long("allow-none")
@@ -2662,6 +2989,24 @@ Directory arguments are expanded to all contained files matching the glob
         .require_equals(true)
         .value_parser(value_parser!(u64)),
     )
+    .arg(
+      Arg::new("shard")
+        .long("shard")
+        .value_name("INDEX/COUNT")
+        .help("Split the test modules into COUNT shards and only run the INDEX-th one (1-based). The split only depends on the paths of the modules relative to the current directory, and on the durations of --shard-durations when given.")
+        .require_equals(true)
+        .conflicts_with("watch")
+        .value_parser(test_shard_parser),
+    )
+    .arg(
+      Arg::new("shard-durations")
+        .long("shard-durations")
+        .value_name("FILE")
+        .help("A file with the durations of the test modules, to share between CI machines. With --shard, the shards are balanced by these durations. Without it, the durations of the run are recorded to the file.")
+        .require_equals(true)
+        .conflicts_with("watch")
+        .value_hint(ValueHint::FilePath),
+    )
     .arg(
       Arg::new("coverage")
         .long("coverage")
@@ -2720,16 +3065,51 @@ Note: running multiple `deno test --clean` calls in series or parallel for the s
         .value_hint(ValueHint::FilePath)
         .help("Write a JUnit XML test report to PATH. Use '-' to write to stdout which is the default when PATH is not provided.")
     )
+    .arg(
+      Arg::new("json-path")
+        .long("json-path")
+        .value_name("PATH")
+        .value_hint(ValueHint::FilePath)
+        .help("Write a JSON lines test report to PATH, with one event per line. Use '-' to write to stdout.")
+    )
+    .arg(
+      Arg::new("update-snapshots")
+        .long("update-snapshots")
+        .help("Update snapshots asserted with 't.assertSnapshot()' and remove obsolete ones")
+        .conflicts_with("no-run")
+        .action(ArgAction::SetTrue),
+    )
     .arg(
       Arg::new("reporter")
         .long("reporter")
         .help("Select reporter to use. Default to 'pretty'.")
-        .value_parser(["pretty", "dot", "junit", "tap"])
+        .value_parser(["pretty", "dot", "junit", "tap", "json"])
     )
     .arg(env_file_arg())
   )
 }
 
+fn test_shard_parser(value: &str) -> Result<TestShard, String> {
+  let Some((index, count)) = value.split_once('/') else {
+    return Err(format!(
+      "Invalid shard '{value}', expected the form <INDEX>/<COUNT>"
+    ));
+  };
+  let index = index
+    .parse::<usize>()
+    .map_err(|_| format!("Invalid shard index '{index}'"))?;
+  let count = count
+    .parse::<usize>()
+    .map_err(|_| format!("Invalid shard count '{count}'"))?;
+  if count == 0 {
+    return Err("Shard count must be greater than 0".to_string());
+  }
+  if index == 0 || index > count {
+    return Err(format!("Shard index must be between 1 and {count}"));
+  }
+  Ok(TestShard { index, count })
+}
+
 fn types_subcommand() -> Command {
   Command::new("types")
     .about("Print runtime TypeScript declarations")
@@ -2908,13 +3288,14 @@ fn compile_args_without_check_args(app: Command) -> Command {
 }
 
 static ALLOW_READ_HELP: &str = concat!(
-  "Allow file system read access. Optionally specify allowed paths.\n",
+  "Allow file system read access. Optionally specify allowed paths, which can be glob patterns.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --allow-read\n",
-  "  --allow-read=\"/etc,/var/log.txt\""
+  "  --allow-read=\"/etc,/var/log.txt\"\n",
+  "  --allow-read=\"./data/**/*.json\""
 );
 
 static DENY_READ_HELP: &str = concat!(
@@ -2928,13 +3309,14 @@ static DENY_READ_HELP: &str = concat!(
 );
 
 static ALLOW_WRITE_HELP: &str = concat!(
-  "Allow file system write access. Optionally specify allowed paths.\n",
+  "Allow file system write access. Optionally specify allowed paths, which can be glob patterns.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --allow-write\n",
-  "  --allow-write=\"/etc,/var/log.txt\""
+  "  --allow-write=\"/etc,/var/log.txt\"\n",
+  "  --allow-write=\"./out/*.log\""
 );
 
 static DENY_WRITE_HELP: &str = concat!(
@@ -2948,13 +3330,14 @@ static DENY_WRITE_HELP: &str = concat!(
 );
 
 static ALLOW_NET_HELP: &str = concat!(
-  "Allow network access. Optionally specify allowed IP addresses and host names, with ports as necessary.\n",
+  "Allow network access. Optionally specify allowed IP addresses and host names, with ports or ranges of ports as necessary. A host starting with \"*.\" allows all its subdomains.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --allow-net\n",
-  "  --allow-net=\"localhost:8080,deno.land\""
+  "  --allow-net=\"localhost:8080,deno.land\"\n",
+  "  --allow-net=\"*.internal.example.com,localhost:8000-8100\""
 );
 
 static DENY_NET_HELP: &str = concat!(
@@ -3068,6 +3451,14 @@ static ALLOW_ALL_HELP: &str = concat!(
   "/basics/permissions\n"
 );
 
+static PERMISSION_SET_HELP: &str = concat!(
+  "Use a permission set from the \"permissions\" section of the config file, ",
+  "adding the permissions of the other flags to it.\n",
+  "Without a name, the \"default\" set is used.\n",
+  "  -P                          Use the \"default\" set\n",
+  "  --permission-set=server     Use the \"server\" set\n"
+);
+
 fn permission_args(app: Command) -> Command {
   app
     .arg(
@@ -3254,6 +3645,40 @@ fn permission_args(app: Command) -> Command {
         .action(ArgAction::SetTrue)
         .help(ALLOW_ALL_HELP),
     )
+    .arg(
+      Arg::new("permission-set")
+        .short('P')
+        .long("permission-set")
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_missing_value("default")
+        .value_name("NAME")
+        .help(PERMISSION_SET_HELP),
+    )
+    .arg(
+      Arg::new("permission-audit")
+        .long("permission-audit")
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Write every permission check to a file, as JSON lines with the kind, descriptor, API, result and JS location")
+        .value_hint(ValueHint::FilePath),
+    )
+    .arg(
+      Arg::new("permission-prompt-socket")
+        .long("permission-prompt-socket")
+        .require_equals(true)
+        .value_name("ADDRESS")
+        .help("Send the permission prompts as JSON lines to a local socket, like 127.0.0.1:9000 or unix:/tmp/prompts.sock, for an IDE or supervisor to answer them"),
+    )
+    .arg(
+      Arg::new("permission-prompt-timeout")
+        .long("permission-prompt-timeout")
+        .require_equals(true)
+        .value_name("SECONDS")
+        .value_parser(value_parser!(u64))
+        .requires("permission-prompt-socket")
+        .help("Deny the permission prompts sent to the socket that aren't answered in time [default: 30]"),
+    )
     .arg(
       Arg::new("no-prompt")
         .long("no-prompt")
@@ -3760,12 +4185,18 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
 
   let no_run = matches.get_flag("no-run");
+  let save_baseline = matches.remove_one::<String>("save-baseline");
+  let baseline = matches.remove_one::<String>("baseline");
+  let regression_threshold = matches.remove_one::<u32>("regression-threshold");
 
   flags.subcommand = DenoSubcommand::Bench(BenchFlags {
     files: FileFlags { include, ignore },
     filter,
     json,
     no_run,
+    save_baseline,
+    baseline,
+    regression_threshold,
     watch: watch_arg_parse(matches),
   });
 }
@@ -3808,7 +4239,11 @@ fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("all") || matches.get_flag("remote") {
     flags.type_check_mode = TypeCheckMode::All;
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+  let reporter = match matches.remove_one::<String>("reporter").as_deref() {
+    Some("sarif") => CheckReporterKind::Sarif,
+    _ => CheckReporterKind::Pretty,
+  };
+  flags.subcommand = DenoSubcommand::Check(CheckFlags { files, reporter });
 }
 
 fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -3885,6 +4320,10 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
   let r#type = if matches.get_flag("lcov") {
     CoverageType::Lcov
+  } else if matches.get_flag("cobertura") {
+    CoverageType::Cobertura
+  } else if matches.get_flag("json-summary") {
+    CoverageType::JsonSummary
   } else if matches.get_flag("html") {
     CoverageType::Html
   } else if matches.get_flag("detailed") {
@@ -3893,6 +4332,12 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     CoverageType::Summary
   };
   let output = matches.remove_one::<String>("output");
+  let thresholds = CoverageThresholds {
+    lines: matches.remove_one::<u8>("threshold-lines"),
+    branches: matches.remove_one::<u8>("threshold-branches"),
+    functions: matches.remove_one::<u8>("threshold-functions"),
+    per_file: matches.get_flag("threshold-per-file"),
+  };
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -3902,6 +4347,7 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     include,
     exclude,
     r#type,
+    thresholds,
   });
 }
 
@@ -4027,6 +4473,8 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     prose_wrap,
     no_semicolons,
     watch: watch_arg_parse(matches),
+    changed: matches.remove_one::<String>("changed"),
+    changed_lines: matches.get_flag("changed-lines"),
   });
 }
 
@@ -4046,6 +4494,7 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   lock_args_parse(flags, matches);
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
+  permission_args_parse(flags, matches);
   let json = matches.get_flag("json");
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
@@ -4143,6 +4592,9 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let sarif = matches.get_flag("sarif");
+  let baseline = matches.remove_one::<String>("baseline");
+  let write_baseline = matches.get_flag("write-baseline");
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
       include: files,
@@ -4155,6 +4607,9 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     maybe_rules_exclude,
     json,
     compact,
+    sarif,
+    baseline,
+    write_baseline,
     watch: watch_arg_parse(matches),
   });
 }
@@ -4201,9 +4656,20 @@ fn run_parse(
 
   ext_arg_parse(flags, matches);
 
+  let learn_permissions = matches
+    .remove_one::<String>("learn-permissions")
+    .map(|format| match format.as_str() {
+      "config" => LearnPermissionsFormat::Config,
+      _ => LearnPermissionsFormat::Flags,
+    });
+  if learn_permissions.is_some() {
+    flags.allow_all();
+  }
+
   flags.subcommand = DenoSubcommand::Run(RunFlags {
     script,
     watch: watch_arg_parse_with_paths(matches),
+    learn_permissions,
   });
 
   Ok(())
@@ -4270,9 +4736,12 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     .map(ConfigFlag::Path)
     .unwrap_or(ConfigFlag::Discover);
 
+  let filter = matches.remove_one::<String>("filter");
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    recursive: matches.get_flag("recursive") || filter.is_some(),
+    filter,
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -4342,6 +4811,12 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     None
   };
 
+  let shard = matches.remove_one::<TestShard>("shard");
+  let shard_durations = matches.remove_one::<String>("shard-durations");
+  let retries = matches.remove_one::<usize>("retries").unwrap_or(0);
+  let timeout = matches.remove_one::<u64>("timeout");
+  let only_failed = matches.get_flag("only-failed");
+
   if let Some(script_arg) = matches.remove_many::<String>("script_arg") {
     flags.argv.extend(script_arg);
   }
@@ -4384,6 +4859,8 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
 
   let junit_path = matches.remove_one::<String>("junit-path");
+  let json_path = matches.remove_one::<String>("json-path");
+  let update_snapshots = matches.get_flag("update-snapshots");
 
   let reporter =
     if let Some(reporter) = matches.remove_one::<String>("reporter") {
@@ -4392,13 +4869,19 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
         "junit" => TestReporterConfig::Junit,
         "dot" => TestReporterConfig::Dot,
         "tap" => TestReporterConfig::Tap,
+        "json" => TestReporterConfig::Json,
         _ => unreachable!(),
       }
     } else {
       TestReporterConfig::Pretty
     };
 
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+  if matches!(
+    reporter,
+    TestReporterConfig::Dot
+      | TestReporterConfig::Tap
+      | TestReporterConfig::Json
+  ) {
     flags.log_level = Some(Level::Error);
   }
 
@@ -4408,15 +4891,22 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     coverage_dir: matches.remove_one::<String>("coverage"),
     clean,
     fail_fast,
+    retries,
+    timeout,
+    only_failed,
     files: FileFlags { include, ignore },
     filter,
     shuffle,
+    shard,
+    shard_durations,
     allow_none,
     concurrent_jobs,
     trace_leaks,
     watch: watch_arg_parse(matches),
     reporter,
     junit_path,
+    json_path,
+    update_snapshots,
   });
 }
 
@@ -4574,6 +5064,25 @@ fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     flags.allow_all();
   }
 
+  if let Some(permission_set) = matches.remove_one::<String>("permission-set") {
+    flags.permissions.permission_set = Some(permission_set);
+  }
+
+  if let Some(path) = matches.remove_one::<String>("permission-audit") {
+    flags.permissions.permission_audit = Some(path);
+  }
+
+  if let Some(address) =
+    matches.remove_one::<String>("permission-prompt-socket")
+  {
+    flags.permissions.permission_prompt_socket = Some(address);
+  }
+
+  if let Some(timeout) = matches.remove_one::<u64>("permission-prompt-timeout")
+  {
+    flags.permissions.permission_prompt_timeout = Some(timeout);
+  }
+
   if matches.get_flag("no-prompt") {
     flags.permissions.no_prompt = true;
   }
@@ -4993,6 +5502,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5018,6 +5528,7 @@ mod tests {
             no_clear_screen: true,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5043,6 +5554,7 @@ mod tests {
             no_clear_screen: true,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5068,6 +5580,7 @@ mod tests {
             no_clear_screen: true,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5095,6 +5608,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5124,6 +5638,7 @@ mod tests {
             no_clear_screen: true,
             exclude: vec![],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5153,6 +5668,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![String::from("foo")],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5178,6 +5694,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![String::from("bar")],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5204,6 +5721,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![String::from("foo"), String::from("bar")],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5230,6 +5748,7 @@ mod tests {
             no_clear_screen: false,
             exclude: vec![String::from("baz"), String::from("qux"),],
           }),
+          learn_permissions: None,
         }),
         code_cache_enabled: true,
         ..Flags::default()
@@ -5525,8 +6044,8 @@ mod tests {
   }
 
   #[test]
-  fn allow_read() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-read", "gist.ts"]);
+  fn permission_set() {
+    let r = flags_from_vec(svec!["deno", "run", "-P", "gist.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -5534,18 +6053,21 @@ mod tests {
           "gist.ts".to_string()
         )),
         permissions: PermissionFlags {
-          allow_read: Some(vec![]),
+          permission_set: Some("default".to_string()),
           ..Default::default()
         },
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
-  }
 
-  #[test]
-  fn deny_read() {
-    let r = flags_from_vec(svec!["deno", "run", "--deny-read", "gist.ts"]);
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permission-set=server",
+      "--allow-env=PORT",
+      "gist.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
@@ -5553,7 +6075,8 @@ mod tests {
           "gist.ts".to_string()
         )),
         permissions: PermissionFlags {
-          deny_read: Some(vec![]),
+          permission_set: Some("server".to_string()),
+          allow_env: Some(svec!["PORT"]),
           ..Default::default()
         },
         code_cache_enabled: true,
@@ -5563,35 +6086,55 @@ mod tests {
   }
 
   #[test]
-  fn allow_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+  fn permission_prompt_socket() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permission-prompt-socket=unix:/tmp/prompts.sock",
+      "--permission-prompt-timeout=5",
+      "gist.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string(),
+          "gist.ts".to_string()
         )),
         permissions: PermissionFlags {
-          allow_hrtime: true,
+          permission_prompt_socket: Some("unix:/tmp/prompts.sock".to_string()),
+          permission_prompt_timeout: Some(5),
           ..Default::default()
         },
         code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permission-prompt-timeout=5",
+      "gist.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn deny_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+  fn permission_audit() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permission-audit=audit.jsonl",
+      "gist.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "gist.ts".to_string(),
+          "gist.ts".to_string()
         )),
         permissions: PermissionFlags {
-          deny_hrtime: true,
+          permission_audit: Some("audit.jsonl".to_string()),
           ..Default::default()
         },
         code_cache_enabled: true,
@@ -5601,28 +6144,50 @@ mod tests {
   }
 
   #[test]
-  fn double_hyphen() {
-    // notice that flags passed after double dash will not
-    // be parsed to Flags but instead forwarded to
-    // script args as Deno.args
+  fn learn_permissions() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--allow-write",
-      "script.ts",
-      "--",
-      "-D",
-      "--allow-net"
+      "--learn-permissions=config",
+      "gist.ts"
     ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "gist.ts".to_string(),
+          watch: None,
+          learn_permissions: Some(LearnPermissionsFormat::Config),
+        }),
+        permissions: PermissionFlags {
+          allow_all: true,
+          allow_net: Some(vec![]),
+          allow_env: Some(vec![]),
+          allow_run: Some(vec![]),
+          allow_read: Some(vec![]),
+          allow_sys: Some(vec![]),
+          allow_write: Some(vec![]),
+          allow_ffi: Some(vec![]),
+          allow_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_read() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-read", "gist.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
-          "script.ts".to_string(),
+          "gist.ts".to_string()
         )),
-        argv: svec!["--", "-D", "--allow-net"],
         permissions: PermissionFlags {
-          allow_write: Some(vec![]),
+          allow_read: Some(vec![]),
           ..Default::default()
         },
         code_cache_enabled: true,
@@ -5632,13 +6197,101 @@ mod tests {
   }
 
   #[test]
-  fn fmt() {
-    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
+  fn deny_read() {
+    let r = flags_from_vec(svec!["deno", "run", "--deny-read", "gist.ts"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string()
+        )),
+        permissions: PermissionFlags {
+          deny_read: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          allow_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "gist.ts".to_string(),
+        )),
+        permissions: PermissionFlags {
+          deny_hrtime: true,
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn double_hyphen() {
+    // notice that flags passed after double dash will not
+    // be parsed to Flags but instead forwarded to
+    // script args as Deno.args
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-write",
+      "script.ts",
+      "--",
+      "-D",
+      "--allow-net"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        argv: svec!["--", "-D", "--allow-net"],
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn fmt() {
+    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
           files: FileFlags {
             include: vec!["script_1.ts".to_string(), "script_2.ts".to_string()],
             ignore: vec![],
@@ -5650,6 +6303,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5673,6 +6328,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5696,6 +6353,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5719,6 +6378,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Some(Default::default()),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5746,7 +6407,9 @@ mod tests {
             hmr: false,
             no_clear_screen: true,
             exclude: vec![],
-          })
+          }),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5777,6 +6440,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Some(Default::default()),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5800,6 +6465,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -5831,6 +6498,8 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Some(Default::default()),
+          changed: None,
+          changed_lines: false,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ext: Some("ts".to_string()),
@@ -5867,6 +6536,8 @@ mod tests {
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -5897,11 +6568,36 @@ mod tests {
           prose_wrap: None,
           no_semicolons: Some(false),
           watch: Default::default(),
+          changed: None,
+          changed_lines: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--changed"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Fmt(FmtFlags {
+        changed: Some("HEAD".to_string()),
+        ..Default::default()
+      })
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "fmt", "--changed=main", "--changed-lines"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Fmt(FmtFlags {
+        changed: Some("main".to_string()),
+        changed_lines: true,
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--changed-lines"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -5922,6 +6618,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5950,6 +6649,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -5979,6 +6681,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -6010,6 +6715,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6032,6 +6740,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6059,6 +6770,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6087,6 +6801,9 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6109,6 +6826,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -6138,6 +6858,9 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -6168,12 +6891,74 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          sarif: false,
+          baseline: None,
+          write_baseline: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--sarif", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec!["script_1.ts".to_string()],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: true,
+          baseline: None,
+          write_baseline: false,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--sarif", "--json"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--write-baseline",
+      "--baseline",
+      "baseline.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: false,
+          baseline: Some("baseline.json".to_string()),
+          write_baseline: true,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
@@ -6210,6 +6995,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Check(CheckFlags {
           files: svec!["script.ts"],
+          reporter: CheckReporterKind::Pretty,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -6223,6 +7009,7 @@ mod tests {
         Flags {
           subcommand: DenoSubcommand::Check(CheckFlags {
             files: svec!["script.ts"],
+            reporter: CheckReporterKind::Pretty,
           }),
           type_check_mode: TypeCheckMode::All,
           ..Flags::default()
@@ -6241,6 +7028,20 @@ mod tests {
         clap::error::ErrorKind::ArgumentConflict
       );
     }
+
+    let r =
+      flags_from_vec(svec!["deno", "check", "--reporter=sarif", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          reporter: CheckReporterKind::Sarif,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
@@ -8287,6 +9088,9 @@ mod tests {
           no_run: true,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: Some("- foo".to_string()),
           allow_none: true,
           files: FileFlags {
@@ -8294,6 +9098,8 @@ mod tests {
             ignore: vec![],
           },
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           concurrent_jobs: None,
           trace_leaks: true,
           coverage_dir: Some("cov".to_string()),
@@ -8301,6 +9107,8 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -8375,9 +9183,14 @@ mod tests {
           reporter: Default::default(),
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8388,6 +9201,8 @@ mod tests {
           clean: false,
           watch: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8412,9 +9227,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: Some(NonZeroUsize::new(3).unwrap()),
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8426,6 +9246,8 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8454,9 +9276,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8468,6 +9295,8 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8578,6 +9407,41 @@ mod tests {
 
     let r = flags_from_vec(svec!["deno", "test", "--junit-path"]);
     assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Json,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--json-path=report.jsonl"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          json_path: Some("report.jsonl".to_string()),
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
@@ -8590,9 +9454,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: Some(1),
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8604,6 +9473,132 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--shard=3/8",
+      "--shard-durations=durations.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          shard: Some(TestShard { index: 3, count: 8 }),
+          shard_durations: Some("durations.json".to_string()),
+          ..TestFlags::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--shard=0/8"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=9/8"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=1/0"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--shard=3"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_retries() {
+    let r = flags_from_vec(svec!["deno", "test", "--retries=2"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          retries: 2,
+          ..TestFlags::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--retries=-1"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_timeout() {
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=5000"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          timeout: Some(5000),
+          ..TestFlags::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=0"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_only_failed() {
+    let r = flags_from_vec(svec!["deno", "test", "--only-failed"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          only_failed: true,
+          ..TestFlags::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--only-failed", "--watch"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_update_snapshots() {
+    let r = flags_from_vec(svec!["deno", "test", "--update-snapshots"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          update_snapshots: true,
+          ..TestFlags::default()
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8625,9 +9620,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8639,6 +9639,8 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8659,9 +9661,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec!["./".to_string()],
             ignore: vec![],
@@ -8673,6 +9680,8 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8695,9 +9704,14 @@ mod tests {
           no_run: false,
           doc: false,
           fail_fast: None,
+          retries: 0,
+          timeout: None,
+          only_failed: false,
           filter: None,
           allow_none: false,
           shuffle: None,
+          shard: None,
+          shard_durations: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -8713,6 +9727,8 @@ mod tests {
           }),
           reporter: Default::default(),
           junit_path: None,
+          json_path: None,
+          update_snapshots: false,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -9237,12 +10253,101 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(String::from("foo.lcov")),
+          ..CoverageFlags::default()
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn coverage_with_cobertura_and_json_summary() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--cobertura",
+      "--output=cobertura.xml",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Cobertura,
+          output: Some(String::from("cobertura.xml")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--json-summary"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["coverage".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::JsonSummary,
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--lcov", "--cobertura"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "coverage", "--output=foo.xml"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_thresholds() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--threshold-lines=80",
+      "--threshold-branches=70",
+      "--threshold-functions=0",
+      "--threshold-per-file",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          thresholds: CoverageThresholds {
+            lines: Some(80),
+            branches: Some(70),
+            functions: Some(0),
+            per_file: true,
+          },
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--threshold-lines=101"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn coverage_with_default_files() {
     let r = flags_from_vec(svec!["deno", "coverage",]);
@@ -9388,6 +10493,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -9401,6 +10508,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -9413,12 +10522,51 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
     );
   }
 
+  #[test]
+  fn task_subcommand_recursive() {
+    let r = flags_from_vec(svec!["deno", "task", "-r", "build", "--quiet"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          recursive: true,
+          filter: None,
+        }),
+        argv: svec!["--quiet"],
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "task", "--filter", "@scope/*", "build"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          recursive: true,
+          filter: Some("@scope/*".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "task", "-r", "--cwd", "foo", "build"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn task_subcommand_double_hyphen() {
     let r = flags_from_vec(svec![
@@ -9437,6 +10585,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -9453,6 +10603,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -9470,6 +10622,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -9486,6 +10640,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -9502,6 +10658,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -9520,6 +10678,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          recursive: false,
+          filter: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -9540,6 +10700,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
         }),
         ..Flags::default()
       }
@@ -9555,6 +10717,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9571,6 +10735,8 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          recursive: false,
+          filter: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9615,6 +10781,9 @@ mod tests {
           filter: Some("- foo".to_string()),
           json: true,
           no_run: true,
+          save_baseline: None,
+          baseline: None,
+          regression_threshold: None,
           files: FileFlags {
             include: vec!["dir1/".to_string(), "dir2/".to_string()],
             ignore: vec![],
@@ -9650,6 +10819,9 @@ mod tests {
           filter: None,
           json: false,
           no_run: false,
+          save_baseline: None,
+          baseline: None,
+          regression_threshold: None,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
@@ -9666,6 +10838,44 @@ mod tests {
     );
   }
 
+  #[test]
+  fn bench_with_baselines() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--save-baseline=new",
+      "--baseline=main",
+      "--regression-threshold=10",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          filter: None,
+          json: false,
+          no_run: false,
+          save_baseline: Some("new".to_string()),
+          baseline: Some("main".to_string()),
+          regression_threshold: Some(10),
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          watch: None,
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "bench", "--regression-threshold=10"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_with_check() {
     let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
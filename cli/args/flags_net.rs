@@ -26,11 +26,30 @@ impl FromStr for BarePort {
   }
 }
 
-pub fn validator(host_and_port: &str) -> Result<String, String> {
-  if Url::parse(&format!("internal://{host_and_port}")).is_ok()
+/// Splits off a range of ports, like in "localhost:8000-8100", which isn't a
+/// valid port of a URL.
+fn split_port_range(host_and_port: &str) -> Option<(&str, &str)> {
+  let (host, ports) = host_and_port.rsplit_once(':')?;
+  let (start, end) = ports.split_once('-')?;
+  let start = start.parse::<u16>().ok()?;
+  let end = end.parse::<u16>().ok()?;
+  (start <= end).then_some((host, ports))
+}
+
+fn is_host_and_port(host_and_port: &str) -> bool {
+  Url::parse(&format!("internal://{host_and_port}")).is_ok()
     || host_and_port.parse::<IpAddr>().is_ok()
-    || host_and_port.parse::<BarePort>().is_ok()
-  {
+}
+
+pub fn validator(host_and_port: &str) -> Result<String, String> {
+  let is_valid = match split_port_range(host_and_port) {
+    Some((host, _)) => host.is_empty() || is_host_and_port(host),
+    None => {
+      is_host_and_port(host_and_port)
+        || host_and_port.parse::<BarePort>().is_ok()
+    }
+  };
+  if is_valid {
     Ok(host_and_port.to_string())
   } else {
     Err(format!("Bad host:port pair: {host_and_port}"))
@@ -39,13 +58,23 @@ pub fn validator(host_and_port: &str) -> Result<String, String> {
 
 /// Expands "bare port" paths (eg. ":8080") into full paths with hosts. It
 /// expands to such paths into 3 paths with following hosts: `0.0.0.0:port`,
-/// `127.0.0.1:port` and `localhost:port`.
+/// `127.0.0.1:port` and `localhost:port`. Ranges of ports (eg. ":8000-8100")
+/// are expanded the same way.
 pub fn parse(paths: Vec<String>) -> clap::error::Result<Vec<String>> {
   let mut out: Vec<String> = vec![];
   for host_and_port in paths.iter() {
-    if Url::parse(&format!("internal://{host_and_port}")).is_ok()
-      || host_and_port.parse::<IpAddr>().is_ok()
-    {
+    if let Some((host, ports)) = split_port_range(host_and_port) {
+      if host.is_empty() {
+        for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
+          out.push(format!("{}:{}", host, ports));
+        }
+        continue;
+      } else if is_host_and_port(host) {
+        out.push(host_and_port.to_owned());
+        continue;
+      }
+    }
+    if is_host_and_port(host_and_port) {
       out.push(host_and_port.to_owned())
     } else if let Ok(port) = host_and_port.parse::<BarePort>() {
       // we got bare port, let's add default hosts
@@ -172,6 +201,29 @@ mod tests {
     assert_eq!(actual, expected);
   }
 
+  #[test]
+  fn parse_net_args_wildcards_and_port_ranges() {
+    let entries = svec![
+      "*.internal.example.com",
+      "*.example.com:443",
+      "localhost:8000-8100",
+      ":9000-9010"
+    ];
+    let expected = svec![
+      "*.internal.example.com",
+      "*.example.com:443",
+      "localhost:8000-8100",
+      "0.0.0.0:9000-9010",
+      "127.0.0.1:9000-9010",
+      "localhost:9000-9010"
+    ];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+
+    assert!(parse(svec!["localhost:8100-8000"]).is_err());
+    assert!(parse(svec!["localhost:8000-99999"]).is_err());
+  }
+
   #[test]
   fn parse_net_args_ipv6() {
     let entries =
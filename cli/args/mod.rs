@@ -6,6 +6,7 @@ mod flags_net;
 mod import_map;
 mod lockfile;
 mod package_json;
+mod permission_sets;
 
 use deno_ast::SourceMapOption;
 use deno_config::workspace::CreateResolverOptions;
@@ -19,6 +20,9 @@ use deno_config::WorkspaceLintConfig;
 use deno_core::normalize_path;
 use deno_core::resolve_url_or_path;
 use deno_graph::GraphKind;
+use deno_json::CliConfigFs;
+use deno_json::CliConfigSections;
+use deno_json::CliConfigSectionsMap;
 use deno_npm::npm_rc::NpmRc;
 use deno_npm::npm_rc::ResolvedNpmRc;
 use deno_npm::resolution::ValidSerializedNpmResolutionSnapshot;
@@ -41,6 +45,8 @@ pub use deno_config::TsConfig;
 pub use deno_config::TsConfigForEmit;
 pub use deno_config::TsConfigType;
 pub use deno_config::TsTypeLib;
+pub use deno_json::FmtLanguageOptions;
+pub use deno_json::FmtLanguagesConfig;
 pub use flags::*;
 pub use lockfile::CliLockfile;
 pub use package_json::PackageJsonInstallDepsProvider;
@@ -254,6 +260,9 @@ pub struct WorkspaceBenchOptions {
   pub filter: Option<String>,
   pub json: bool,
   pub no_run: bool,
+  pub save_baseline: Option<String>,
+  pub baseline: Option<String>,
+  pub regression_threshold: Option<u32>,
 }
 
 impl WorkspaceBenchOptions {
@@ -262,6 +271,9 @@ impl WorkspaceBenchOptions {
       filter: bench_flags.filter.clone(),
       json: bench_flags.json,
       no_run: bench_flags.no_run,
+      save_baseline: bench_flags.save_baseline.clone(),
+      baseline: bench_flags.baseline.clone(),
+      regression_threshold: bench_flags.regression_threshold,
     }
   }
 }
@@ -290,6 +302,7 @@ impl BenchOptions {
 #[derive(Clone, Debug)]
 pub struct FmtOptions {
   pub options: FmtOptionsConfig,
+  pub languages: FmtLanguagesConfig,
   pub files: FilePatterns,
 }
 
@@ -303,17 +316,21 @@ impl FmtOptions {
   pub fn new_with_base(base: PathBuf) -> Self {
     Self {
       options: FmtOptionsConfig::default(),
+      languages: FmtLanguagesConfig::default(),
       files: FilePatterns::new_with_base(base),
     }
   }
 
   pub fn resolve(
     fmt_config: FmtConfig,
+    mut languages: FmtLanguagesConfig,
     fmt_flags: &FmtFlags,
     maybe_flags_base: Option<&Path>,
   ) -> Result<Self, AnyError> {
+    remove_fmt_language_flag_overrides(&mut languages, fmt_flags);
     Ok(Self {
       options: resolve_fmt_options(fmt_flags, fmt_config.options),
+      languages,
       files: resolve_files(
         fmt_config.files,
         &fmt_flags.files,
@@ -323,6 +340,34 @@ impl FmtOptions {
   }
 }
 
+/// The flags override the options of all the languages.
+fn remove_fmt_language_flag_overrides(
+  languages: &mut FmtLanguagesConfig,
+  fmt_flags: &FmtFlags,
+) {
+  for options in [&mut languages.css, &mut languages.html, &mut languages.yaml]
+  {
+    if fmt_flags.use_tabs.is_some() {
+      options.use_tabs = None;
+    }
+    if fmt_flags.line_width.is_some() {
+      options.line_width = None;
+    }
+    if fmt_flags.indent_width.is_some() {
+      options.indent_width = None;
+    }
+    if fmt_flags.single_quote.is_some() {
+      options.single_quote = None;
+    }
+  }
+  if fmt_flags.use_tabs.is_some() {
+    languages.sql.use_tabs = None;
+  }
+  if fmt_flags.indent_width.is_some() {
+    languages.sql.indent_width = None;
+  }
+}
+
 fn resolve_fmt_options(
   fmt_flags: &FmtFlags,
   mut options: FmtOptionsConfig,
@@ -365,13 +410,20 @@ pub struct WorkspaceTestOptions {
   pub doc: bool,
   pub no_run: bool,
   pub fail_fast: Option<NonZeroUsize>,
+  pub retries: usize,
+  pub timeout: Option<u64>,
+  pub only_failed: bool,
   pub allow_none: bool,
   pub filter: Option<String>,
   pub shuffle: Option<u64>,
+  pub shard: Option<TestShard>,
+  pub shard_durations: Option<String>,
   pub concurrent_jobs: NonZeroUsize,
   pub trace_leaks: bool,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub json_path: Option<String>,
+  pub update_snapshots: bool,
 }
 
 impl WorkspaceTestOptions {
@@ -383,12 +435,19 @@ impl WorkspaceTestOptions {
         .unwrap_or_else(|| NonZeroUsize::new(1).unwrap()),
       doc: test_flags.doc,
       fail_fast: test_flags.fail_fast,
+      retries: test_flags.retries,
+      timeout: test_flags.timeout,
+      only_failed: test_flags.only_failed,
       filter: test_flags.filter.clone(),
       no_run: test_flags.no_run,
       shuffle: test_flags.shuffle,
+      shard: test_flags.shard,
+      shard_durations: test_flags.shard_durations.clone(),
       trace_leaks: test_flags.trace_leaks,
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path.clone(),
+      json_path: test_flags.json_path.clone(),
+      update_snapshots: test_flags.update_snapshots,
     }
   }
 }
@@ -420,22 +479,37 @@ pub enum LintReporterKind {
   Pretty,
   Json,
   Compact,
+  Sarif,
 }
 
 #[derive(Clone, Debug)]
 pub struct WorkspaceLintOptions {
   pub reporter_kind: LintReporterKind,
+  pub baseline: Option<LintBaselineOptions>,
+}
+
+pub const LINT_BASELINE_FILE_NAME: &str = "deno-lint-baseline.json";
+
+#[derive(Clone, Debug)]
+pub struct LintBaselineOptions {
+  pub path: PathBuf,
+  /// Record the current problems instead of reporting the new ones.
+  pub write: bool,
 }
 
 impl WorkspaceLintOptions {
   pub fn resolve(
     lint_config: &WorkspaceLintConfig,
     lint_flags: &LintFlags,
+    initial_cwd: &Path,
+    workspace_root_dir: &Path,
   ) -> Result<Self, AnyError> {
     let mut maybe_reporter_kind = if lint_flags.json {
       Some(LintReporterKind::Json)
     } else if lint_flags.compact {
       Some(LintReporterKind::Compact)
+    } else if lint_flags.sarif {
+      Some(LintReporterKind::Sarif)
     } else {
       None
     };
@@ -445,6 +519,7 @@ impl WorkspaceLintOptions {
       maybe_reporter_kind = match lint_config.report.as_deref() {
         Some("json") => Some(LintReporterKind::Json),
         Some("compact") => Some(LintReporterKind::Compact),
+        Some("sarif") => Some(LintReporterKind::Sarif),
         Some("pretty") => Some(LintReporterKind::Pretty),
         Some(_) => {
           bail!("Invalid lint report type in config file")
@@ -452,8 +527,25 @@ impl WorkspaceLintOptions {
         None => None,
       }
     }
+    let baseline = match &lint_flags.baseline {
+      Some(path) => {
+        let path = initial_cwd.join(path);
+        if !lint_flags.write_baseline && !path.exists() {
+          bail!("Lint baseline file not found: {}", path.display());
+        }
+        Some(path)
+      }
+      None => {
+        let path = workspace_root_dir.join(LINT_BASELINE_FILE_NAME);
+        (lint_flags.write_baseline || path.exists()).then_some(path)
+      }
+    };
     Ok(Self {
       reporter_kind: maybe_reporter_kind.unwrap_or_default(),
+      baseline: baseline.map(|path| LintBaselineOptions {
+        path,
+        write: lint_flags.write_baseline,
+      }),
     })
   }
 }
@@ -463,6 +555,8 @@ pub struct LintOptions {
   pub rules: LintRulesConfig,
   pub files: FilePatterns,
   pub fix: bool,
+  /// Modules with rules written in JavaScript or TypeScript.
+  pub plugins: Vec<ModuleSpecifier>,
 }
 
 impl Default for LintOptions {
@@ -477,11 +571,13 @@ impl LintOptions {
       rules: Default::default(),
       files: FilePatterns::new_with_base(base),
       fix: false,
+      plugins: Vec::new(),
     }
   }
 
   pub fn resolve(
     lint_config: LintConfig,
+    plugins: Vec<ModuleSpecifier>,
     lint_flags: LintFlags,
     maybe_flags_base: Option<&Path>,
   ) -> Result<Self, AnyError> {
@@ -498,6 +594,7 @@ impl LintOptions {
         lint_flags.maybe_rules_exclude,
       ),
       fix: lint_flags.fix,
+      plugins,
     })
   }
 }
@@ -783,19 +880,40 @@ pub struct CliOptions {
   maybe_lockfile: Option<Arc<CliLockfile>>,
   overrides: CliOptionOverrides,
   pub workspace: Arc<Workspace>,
+  config_sections: Arc<CliConfigSectionsMap>,
   pub disable_deprecated_api_warning: bool,
   pub verbose_deprecated_api_warning: bool,
 }
 
 impl CliOptions {
   pub fn new(
-    flags: Flags,
+    mut flags: Flags,
     initial_cwd: PathBuf,
     maybe_lockfile: Option<Arc<CliLockfile>>,
     npmrc: Arc<ResolvedNpmRc>,
     workspace: Arc<Workspace>,
+    config_sections: Arc<CliConfigSectionsMap>,
     force_global_cache: bool,
   ) -> Result<Self, AnyError> {
+    if let Some(name) = flags.permissions.permission_set.take() {
+      let permission_set =
+        match workspace.resolve_start_ctx().maybe_deno_json() {
+          Some(config_file) => permission_sets::resolve_permission_set(
+            config_file,
+            &config_sections.get(config_file),
+            &name,
+          )?,
+          None => bail!(
+            "The permission set \"{}\" requires a config file with a \"permissions\" section.",
+            name
+          ),
+        };
+      flags.permissions = permission_sets::layer_permission_flags(
+        permission_set,
+        &flags.permissions,
+      );
+    }
+
     if let Some(insecure_allowlist) =
       flags.unsafely_ignore_certificate_errors.as_ref()
     {
@@ -862,6 +980,7 @@ impl CliOptions {
       maybe_vendor_folder,
       overrides: Default::default(),
       workspace,
+      config_sections,
       disable_deprecated_api_warning,
       verbose_deprecated_api_warning,
     })
@@ -871,6 +990,7 @@ impl CliOptions {
     let initial_cwd =
       std::env::current_dir().with_context(|| "Failed getting cwd.")?;
     let config_fs_adapter = DenoConfigFsAdapter::new(&RealFs);
+    let config_fs = CliConfigFs::new(&config_fs_adapter);
     let resolve_workspace_discover_options = || {
       let additional_config_file_names: &'static [&'static str] =
         if matches!(flags.subcommand, DenoSubcommand::Publish(..)) {
@@ -892,7 +1012,7 @@ impl CliOptions {
         log::debug!("package.json auto-discovery is disabled");
       }
       WorkspaceDiscoverOptions {
-        fs: &config_fs_adapter,
+        fs: &config_fs,
         pkg_json_cache: Some(
           &deno_runtime::deno_node::PackageJsonThreadLocalCache,
         ),
@@ -957,10 +1077,20 @@ impl CliOptions {
       maybe_lock_file.map(Arc::new),
       npmrc,
       Arc::new(workspace),
+      Arc::new(config_fs.take_sections()),
       false,
     )
   }
 
+  /// Gets the sections of a config file of the workspace that are handled by
+  /// the CLI.
+  pub fn config_sections(
+    &self,
+    config_file: &ConfigFile,
+  ) -> Arc<CliConfigSections> {
+    self.config_sections.get(config_file)
+  }
+
   #[inline(always)]
   pub fn initial_cwd(&self) -> &Path {
     &self.initial_cwd
@@ -1374,7 +1504,41 @@ impl CliOptions {
     ctx: &WorkspaceMemberContext,
   ) -> Result<FmtOptions, AnyError> {
     let fmt_config = ctx.to_fmt_config()?;
-    FmtOptions::resolve(fmt_config, fmt_flags, Some(&self.initial_cwd))
+    let languages = self.resolve_fmt_languages_config(ctx)?;
+    FmtOptions::resolve(
+      fmt_config,
+      languages,
+      fmt_flags,
+      Some(&self.initial_cwd),
+    )
+  }
+
+  /// Resolves the options of the formatters of other languages of a member,
+  /// which fall back to the ones of the workspace root.
+  fn resolve_fmt_languages_config(
+    &self,
+    ctx: &WorkspaceMemberContext,
+  ) -> Result<FmtLanguagesConfig, AnyError> {
+    let Some(member_config) = ctx.maybe_deno_json() else {
+      return Ok(FmtLanguagesConfig::default());
+    };
+    let root_config = self
+      .workspace
+      .root_folder()
+      .1
+      .deno_json
+      .as_ref()
+      .filter(|root| root.specifier != member_config.specifier);
+    let mut languages = self
+      .config_sections(member_config)
+      .to_fmt_languages_config()?;
+    if let Some(root_config) = root_config {
+      let root_languages = self
+        .config_sections(root_config)
+        .to_fmt_languages_config()?;
+      languages = languages.or(root_languages);
+    }
+    Ok(languages)
   }
 
   pub fn resolve_workspace_lint_options(
@@ -1382,7 +1546,18 @@ impl CliOptions {
     lint_flags: &LintFlags,
   ) -> Result<WorkspaceLintOptions, AnyError> {
     let lint_config = self.workspace.to_lint_config()?;
-    WorkspaceLintOptions::resolve(&lint_config, lint_flags)
+    let root_dir = self
+      .workspace
+      .root_folder()
+      .0
+      .to_file_path()
+      .unwrap_or_else(|_| self.initial_cwd().to_path_buf());
+    WorkspaceLintOptions::resolve(
+      &lint_config,
+      lint_flags,
+      self.initial_cwd(),
+      &root_dir,
+    )
   }
 
   pub fn resolve_lint_options_for_members(
@@ -1408,7 +1583,43 @@ impl CliOptions {
     ctx: &WorkspaceMemberContext,
   ) -> Result<LintOptions, AnyError> {
     let lint_config = ctx.to_lint_config()?;
-    LintOptions::resolve(lint_config, lint_flags, Some(&self.initial_cwd))
+    let plugins = self.resolve_lint_plugins(ctx)?;
+    LintOptions::resolve(
+      lint_config,
+      plugins,
+      lint_flags,
+      Some(&self.initial_cwd),
+    )
+  }
+
+  /// Resolves the "lint.plugins" of a member along with the ones of the
+  /// workspace root.
+  fn resolve_lint_plugins(
+    &self,
+    ctx: &WorkspaceMemberContext,
+  ) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    let Some(member_config) = ctx.maybe_deno_json() else {
+      return Ok(Vec::new());
+    };
+    let root_config = self
+      .workspace
+      .root_folder()
+      .1
+      .deno_json
+      .as_ref()
+      .filter(|root| root.specifier != member_config.specifier);
+    let mut plugins = Vec::new();
+    for config_file in root_config.into_iter().chain([member_config]) {
+      let config_plugins = self
+        .config_sections(config_file)
+        .to_lint_plugins(&config_file.specifier)?;
+      for plugin in config_plugins {
+        if !plugins.contains(&plugin) {
+          plugins.push(plugin);
+        }
+      }
+    }
+    Ok(plugins)
   }
 
   pub fn resolve_lint_config(
@@ -1432,6 +1643,32 @@ impl CliOptions {
     })
   }
 
+  /// Resolves the coverage thresholds from the flags, falling back to the
+  /// `coverage` section of the root config file.
+  pub fn resolve_coverage_thresholds(
+    &self,
+    flags: &CoverageThresholds,
+  ) -> Result<CoverageThresholds, AnyError> {
+    let config = match &self.workspace.root_folder().1.deno_json {
+      Some(config_file) => {
+        self.config_sections(config_file).to_coverage_config()?
+      }
+      None => Default::default(),
+    }
+    .thresholds;
+    for threshold in [config.lines, config.branches, config.functions] {
+      if threshold.is_some_and(|t| t > 100) {
+        bail!("Coverage thresholds must be percentages between 0 and 100");
+      }
+    }
+    Ok(CoverageThresholds {
+      lines: flags.lines.or(config.lines),
+      branches: flags.branches.or(config.branches),
+      functions: flags.functions.or(config.functions),
+      per_file: flags.per_file || config.per_file,
+    })
+  }
+
   pub fn resolve_workspace_test_options(
     &self,
     test_flags: &TestFlags,
@@ -1616,6 +1853,12 @@ impl CliOptions {
     &self.flags.permissions
   }
 
+  /// The permission flags equivalent to the effective permissions, including
+  /// the ones of the permission set.
+  pub fn permission_args(&self) -> Vec<String> {
+    self.flags.to_permission_args()
+  }
+
   pub fn permissions_options(&self) -> Result<PermissionsOptions, AnyError> {
     self.flags.permissions.to_options(Some(&self.initial_cwd))
   }
@@ -0,0 +1,292 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Named sets of permissions declared in the "permissions" section of a
+//! config file. A set is used with `--permission-set=<name>` (or `-P` for the
+//! "default" set) and the permission flags of the command are added to it.
+
+use std::path::Path;
+
+use deno_config::ConfigFile;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::normalize_path;
+
+use super::deno_json::CliConfigSections;
+use super::deno_json::PermissionKindsConfig;
+use super::deno_json::PermissionListConfig;
+use super::flags_net;
+use super::PermissionFlags;
+
+fn resolve_list(
+  list: &Option<PermissionListConfig>,
+  resolve_item: impl Fn(&str) -> Result<String, AnyError>,
+) -> Result<Option<Vec<String>>, AnyError> {
+  match list {
+    None | Some(PermissionListConfig::All(false)) => Ok(None),
+    Some(PermissionListConfig::All(true)) => Ok(Some(Vec::new())),
+    // unlike on the command line, an empty list doesn't allow anything
+    Some(PermissionListConfig::List(items)) if items.is_empty() => Ok(None),
+    Some(PermissionListConfig::List(items)) => items
+      .iter()
+      .map(|item| resolve_item(item))
+      .collect::<Result<Vec<_>, _>>()
+      .map(Some),
+  }
+}
+
+struct ResolvedKinds {
+  read: Option<Vec<String>>,
+  write: Option<Vec<String>>,
+  net: Option<Vec<String>>,
+  env: Option<Vec<String>>,
+  run: Option<Vec<String>>,
+  ffi: Option<Vec<String>>,
+  sys: Option<Vec<String>>,
+  hrtime: bool,
+}
+
+/// Resolves the paths relative to the directory of the config file.
+fn resolve_kinds(
+  kinds: &PermissionKindsConfig,
+  config_dir: &Path,
+) -> Result<ResolvedKinds, AnyError> {
+  let resolve_path = |path: &str| {
+    Ok(
+      normalize_path(config_dir.join(path))
+        .to_string_lossy()
+        .into_owned(),
+    )
+  };
+  let keep = |item: &str| Ok(item.to_string());
+  Ok(ResolvedKinds {
+    read: resolve_list(&kinds.read, resolve_path)?,
+    write: resolve_list(&kinds.write, resolve_path)?,
+    // expands the bare ports like the --allow-net flag
+    net: resolve_list(&kinds.net, keep)?
+      .map(flags_net::parse)
+      .transpose()
+      .map_err(|err| anyhow!("{}", err))?,
+    env: resolve_list(&kinds.env, keep)?,
+    // commands can be paths too
+    run: resolve_list(&kinds.run, |command| {
+      if command.starts_with("./") || command.starts_with("../") {
+        resolve_path(command)
+      } else {
+        keep(command)
+      }
+    })?,
+    ffi: resolve_list(&kinds.ffi, resolve_path)?,
+    sys: resolve_list(&kinds.sys, keep)?,
+    hrtime: kinds.hrtime,
+  })
+}
+
+/// Resolves a permission set of the config file, with its paths relative to
+/// the config file.
+pub fn resolve_permission_set(
+  config_file: &ConfigFile,
+  config_sections: &CliConfigSections,
+  name: &str,
+) -> Result<PermissionFlags, AnyError> {
+  let Some(sets) =
+    config_sections.to_permissions_config().with_context(|| {
+      format!("Invalid permission sets in {}", config_file.specifier)
+    })?
+  else {
+    bail!(
+      "The permission set \"{}\" was not found, because {} has no \"permissions\" section.",
+      name,
+      config_file.specifier
+    );
+  };
+  let Some(set) = sets.get(name) else {
+    bail!(
+      "The permission set \"{}\" was not found in {}. Available sets: {}",
+      name,
+      config_file.specifier,
+      sets.keys().cloned().collect::<Vec<_>>().join(", ")
+    );
+  };
+  let config_dir = config_file
+    .specifier
+    .to_file_path()
+    .ok()
+    .and_then(|path| path.parent().map(|p| p.to_path_buf()))
+    .unwrap_or_default();
+  let allow = resolve_kinds(&set.allow(), &config_dir)?;
+  let deny = resolve_kinds(&set.deny, &config_dir)?;
+  let all = |list: Option<Vec<String>>| {
+    if set.all {
+      Some(Vec::new())
+    } else {
+      list
+    }
+  };
+  Ok(PermissionFlags {
+    allow_all: set.all,
+    allow_env: all(allow.env),
+    deny_env: deny.env,
+    allow_hrtime: set.all || allow.hrtime,
+    deny_hrtime: deny.hrtime,
+    allow_ffi: all(allow.ffi),
+    deny_ffi: deny.ffi,
+    allow_net: all(allow.net),
+    deny_net: deny.net,
+    allow_read: all(allow.read),
+    deny_read: deny.read,
+    allow_run: all(allow.run),
+    deny_run: deny.run,
+    allow_sys: all(allow.sys),
+    deny_sys: deny.sys,
+    allow_write: all(allow.write),
+    deny_write: deny.write,
+    ..Default::default()
+  })
+}
+
+/// Adds the permissions of the command line to the ones of a permission set.
+pub fn layer_permission_flags(
+  base: PermissionFlags,
+  flags: &PermissionFlags,
+) -> PermissionFlags {
+  fn layer_list(
+    base: Option<Vec<String>>,
+    other: &Option<Vec<String>>,
+  ) -> Option<Vec<String>> {
+    match (base, other) {
+      (None, other) => other.clone(),
+      (base, None) => base,
+      (Some(base), Some(other)) if base.is_empty() || other.is_empty() => {
+        Some(Vec::new())
+      }
+      (Some(mut base), Some(other)) => {
+        for item in other {
+          if !base.contains(item) {
+            base.push(item.clone());
+          }
+        }
+        Some(base)
+      }
+    }
+  }
+
+  PermissionFlags {
+    allow_all: base.allow_all || flags.allow_all,
+    allow_env: layer_list(base.allow_env, &flags.allow_env),
+    deny_env: layer_list(base.deny_env, &flags.deny_env),
+    allow_hrtime: base.allow_hrtime || flags.allow_hrtime,
+    deny_hrtime: base.deny_hrtime || flags.deny_hrtime,
+    allow_ffi: layer_list(base.allow_ffi, &flags.allow_ffi),
+    deny_ffi: layer_list(base.deny_ffi, &flags.deny_ffi),
+    allow_net: layer_list(base.allow_net, &flags.allow_net),
+    deny_net: layer_list(base.deny_net, &flags.deny_net),
+    allow_read: layer_list(base.allow_read, &flags.allow_read),
+    deny_read: layer_list(base.deny_read, &flags.deny_read),
+    allow_run: layer_list(base.allow_run, &flags.allow_run),
+    deny_run: layer_list(base.deny_run, &flags.deny_run),
+    allow_sys: layer_list(base.allow_sys, &flags.allow_sys),
+    deny_sys: layer_list(base.deny_sys, &flags.deny_sys),
+    allow_write: layer_list(base.allow_write, &flags.allow_write),
+    deny_write: layer_list(base.deny_write, &flags.deny_write),
+    no_prompt: flags.no_prompt,
+    permission_set: None,
+    permission_audit: flags.permission_audit.clone(),
+    permission_prompt_socket: flags.permission_prompt_socket.clone(),
+    permission_prompt_timeout: flags.permission_prompt_timeout,
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_core::serde_json;
+  use deno_core::serde_json::json;
+  use deno_core::url::Url;
+
+  fn config_file(
+    value: serde_json::Value,
+  ) -> (test_util::TempDir, ConfigFile, CliConfigSections) {
+    let temp_dir = test_util::TempDir::new();
+    temp_dir.write("deno.json", value.to_string());
+    let specifier =
+      Url::from_file_path(temp_dir.path().join("deno.json")).unwrap();
+    let (text, config_sections) =
+      CliConfigSections::take_from_text(value.to_string());
+    let config_file = ConfigFile::new(
+      &text,
+      specifier,
+      &deno_config::ConfigParseOptions::default(),
+    )
+    .unwrap();
+    (temp_dir, config_file, config_sections)
+  }
+
+  #[test]
+  fn permission_set_with_flags() {
+    let (temp_dir, config_file, config_sections) = config_file(json!({
+      "permissions": {
+        "default": {
+          "read": ["./data"],
+          "net": ["api.example.com:443"],
+          "env": true,
+          "write": [],
+          "deny": { "env": ["SECRET"] }
+        },
+        "dev": { "all": true }
+      }
+    }));
+    let set = resolve_permission_set(&config_file, &config_sections, "default")
+      .unwrap();
+    let data_dir = temp_dir.path().join("data").to_string_lossy().into_owned();
+    assert_eq!(set.allow_read, Some(vec![data_dir.clone()]));
+    assert_eq!(set.allow_net, Some(vec!["api.example.com:443".to_string()]));
+    assert_eq!(set.allow_env, Some(vec![]));
+    assert_eq!(set.allow_write, None);
+    assert_eq!(set.deny_env, Some(vec!["SECRET".to_string()]));
+
+    let flags = layer_permission_flags(
+      set,
+      &PermissionFlags {
+        allow_read: Some(vec!["/tmp".to_string()]),
+        allow_write: Some(vec!["/tmp".to_string()]),
+        no_prompt: true,
+        ..Default::default()
+      },
+    );
+    assert_eq!(flags.allow_read, Some(vec![data_dir, "/tmp".to_string()]));
+    assert_eq!(flags.allow_write, Some(vec!["/tmp".to_string()]));
+    assert!(flags.no_prompt);
+
+    let set =
+      resolve_permission_set(&config_file, &config_sections, "dev").unwrap();
+    assert!(set.allow_all);
+    assert_eq!(set.allow_read, Some(vec![]));
+
+    let err = resolve_permission_set(&config_file, &config_sections, "prod")
+      .unwrap_err();
+    assert!(err.to_string().contains("Available sets: default, dev"));
+  }
+
+  #[test]
+  fn permission_set_unknown_keys() {
+    let (_temp_dir, config, config_sections) = config_file(json!({
+      "permissions": {
+        "default": { "read": true, "raed": ["./data"] }
+      }
+    }));
+    let err =
+      resolve_permission_set(&config, &config_sections, "default").unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown field `raed`"));
+
+    let (_temp_dir, config, config_sections) = config_file(json!({
+      "permissions": {
+        "default": { "deny": { "all": true } }
+      }
+    }));
+    let err =
+      resolve_permission_set(&config, &config_sections, "default").unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown field `all`"));
+  }
+}
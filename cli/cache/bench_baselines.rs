@@ -0,0 +1,153 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheFailure;
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+pub static BENCH_BASELINES_CACHE_DB: CacheDBConfiguration =
+  CacheDBConfiguration {
+    table_initializer: concat!(
+      "CREATE TABLE IF NOT EXISTS benchbaselines (",
+      "baseline TEXT NOT NULL,",
+      "specifier TEXT NOT NULL,",
+      "bench_group TEXT NOT NULL,",
+      "name TEXT NOT NULL,",
+      "stats TEXT NOT NULL,",
+      "PRIMARY KEY (baseline, specifier, bench_group, name)",
+      ");",
+    ),
+    // keep the baselines on upgrade, so that versions can be compared
+    on_version_change: "",
+    preheat_queries: &[],
+    // If the cache fails, just ignore all caching attempts
+    on_failure: CacheFailure::Blackhole,
+  };
+
+/// The measurement of a bench saved in a baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchBaselineRecord {
+  pub specifier: String,
+  pub group: Option<String>,
+  pub name: String,
+  /// The serialized bench stats.
+  pub stats: String,
+}
+
+/// Named bench results saved with `deno bench --save-baseline` and compared
+/// against with `deno bench --baseline`.
+#[derive(Clone)]
+pub struct BenchBaselinesCache(CacheDB);
+
+impl BenchBaselinesCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_baseline(&self, baseline: &str) -> Vec<BenchBaselineRecord> {
+    match self.get_baseline_result(baseline) {
+      Ok(records) => records,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving bench baseline: {err}");
+        } else {
+          // fail silently when not debugging
+          Vec::new()
+        }
+      }
+    }
+  }
+
+  fn get_baseline_result(
+    &self,
+    baseline: &str,
+  ) -> Result<Vec<BenchBaselineRecord>, AnyError> {
+    self.0.with_connection(|conn| {
+      let mut stmt = conn.prepare_cached(
+        "SELECT specifier, bench_group, name, stats FROM benchbaselines WHERE baseline=?1 ORDER BY specifier, bench_group, name",
+      )?;
+      let mut rows = stmt.query(params![baseline])?;
+      let mut records = Vec::new();
+      while let Some(row) = rows.next()? {
+        let group: String = row.get(1)?;
+        records.push(BenchBaselineRecord {
+          specifier: row.get(0)?,
+          group: if group.is_empty() { None } else { Some(group) },
+          name: row.get(2)?,
+          stats: row.get(3)?,
+        });
+      }
+      Ok(records)
+    })
+  }
+
+  pub fn set_measurement(&self, baseline: &str, record: &BenchBaselineRecord) {
+    let result = self.0.execute(
+      "INSERT OR REPLACE INTO benchbaselines (baseline, specifier, bench_group, name, stats) VALUES (?1, ?2, ?3, ?4, ?5)",
+      params![
+        baseline,
+        record.specifier,
+        record.group.as_deref().unwrap_or(""),
+        record.name,
+        record.stats
+      ],
+    );
+    if let Err(err) = result {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error saving bench baseline: {err}");
+      } else {
+        log::debug!("Error saving bench baseline: {}", err);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn bench_baselines_cache_general_use() {
+    let conn = CacheDB::in_memory(&BENCH_BASELINES_CACHE_DB, "1.0.0");
+    let cache = BenchBaselinesCache::new(conn);
+
+    let record1 = BenchBaselineRecord {
+      specifier: "file:///a_bench.ts".to_string(),
+      group: None,
+      name: "a".to_string(),
+      stats: "{\"avg\":1}".to_string(),
+    };
+    let record2 = BenchBaselineRecord {
+      specifier: "file:///a_bench.ts".to_string(),
+      group: Some("group".to_string()),
+      name: "a".to_string(),
+      stats: "{\"avg\":2}".to_string(),
+    };
+    assert_eq!(cache.get_baseline("main"), vec![]);
+    cache.set_measurement("main", &record2);
+    cache.set_measurement("main", &record1);
+    cache.set_measurement("other", &record1);
+    assert_eq!(
+      cache.get_baseline("main"),
+      vec![record1.clone(), record2.clone()]
+    );
+
+    // saving again replaces the measurement
+    let record1 = BenchBaselineRecord {
+      stats: "{\"avg\":3}".to_string(),
+      ..record1
+    };
+    cache.set_measurement("main", &record1);
+    assert_eq!(
+      cache.get_baseline("main"),
+      vec![record1.clone(), record2.clone()]
+    );
+
+    // changing the cli version should keep the baselines
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = BenchBaselinesCache::new(conn);
+    assert_eq!(cache.get_baseline("main").len(), 2);
+  }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
 
+use super::bench_baselines::BENCH_BASELINES_CACHE_DB;
 use super::cache_db::CacheDB;
 use super::cache_db::CacheDBConfiguration;
 use super::check::TYPE_CHECK_CACHE_DB;
@@ -14,6 +15,8 @@ use super::fast_check::FAST_CHECK_CACHE_DB;
 use super::incremental::INCREMENTAL_CACHE_DB;
 use super::module_info::MODULE_INFO_CACHE_DB;
 use super::node::NODE_ANALYSIS_CACHE_DB;
+use super::task_cache::TASK_CACHE_DB;
+use super::test_failures::TEST_FAILURES_CACHE_DB;
 
 pub struct Caches {
   dir_provider: Arc<DenoDirProvider>,
@@ -24,6 +27,9 @@ pub struct Caches {
   node_analysis_db: OnceCell<CacheDB>,
   type_checking_cache_db: OnceCell<CacheDB>,
   code_cache_db: OnceCell<CacheDB>,
+  test_failures_db: OnceCell<CacheDB>,
+  bench_baselines_db: OnceCell<CacheDB>,
+  task_cache_db: OnceCell<CacheDB>,
 }
 
 impl Caches {
@@ -37,6 +43,9 @@ impl Caches {
       node_analysis_db: Default::default(),
       type_checking_cache_db: Default::default(),
       code_cache_db: Default::default(),
+      test_failures_db: Default::default(),
+      bench_baselines_db: Default::default(),
+      task_cache_db: Default::default(),
     }
   }
 
@@ -139,4 +148,40 @@ impl Caches {
         .map(|dir| dir.code_cache_db_file_path()),
     )
   }
+
+  pub fn test_failures_db(&self) -> CacheDB {
+    Self::make_db(
+      &self.test_failures_db,
+      &TEST_FAILURES_CACHE_DB,
+      self
+        .dir_provider
+        .get_or_create()
+        .ok()
+        .map(|dir| dir.test_failures_db_file_path()),
+    )
+  }
+
+  pub fn bench_baselines_db(&self) -> CacheDB {
+    Self::make_db(
+      &self.bench_baselines_db,
+      &BENCH_BASELINES_CACHE_DB,
+      self
+        .dir_provider
+        .get_or_create()
+        .ok()
+        .map(|dir| dir.bench_baselines_db_file_path()),
+    )
+  }
+
+  pub fn task_cache_db(&self) -> CacheDB {
+    Self::make_db(
+      &self.task_cache_db,
+      &TASK_CACHE_DB,
+      self
+        .dir_provider
+        .get_or_create()
+        .ok()
+        .map(|dir| dir.task_cache_db_file_path()),
+    )
+  }
 }
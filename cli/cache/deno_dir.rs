@@ -121,6 +121,24 @@ impl DenoDir {
     self.root.join("check_cache_v2")
   }
 
+  /// Path for the cache of the tests that failed in their last run.
+  pub fn test_failures_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("test_failures_cache_v1")
+  }
+
+  /// Path for the saved bench baselines.
+  pub fn bench_baselines_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("bench_baselines_v1")
+  }
+
+  /// Path for the hashes of the inputs and outputs of tasks.
+  pub fn task_cache_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
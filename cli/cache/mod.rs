@@ -24,6 +24,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
 
+mod bench_baselines;
 mod cache_db;
 mod caches;
 mod check;
@@ -37,7 +38,11 @@ mod incremental;
 mod module_info;
 mod node;
 mod parsed_source;
+mod task_cache;
+mod test_failures;
 
+pub use bench_baselines::BenchBaselineRecord;
+pub use bench_baselines::BenchBaselinesCache;
 pub use cache_db::CacheDBHash;
 pub use caches::Caches;
 pub use check::TypeCheckCache;
@@ -54,6 +59,10 @@ pub use module_info::ModuleInfoCache;
 pub use node::NodeAnalysisCache;
 pub use parsed_source::LazyGraphSourceParser;
 pub use parsed_source::ParsedSourceCache;
+pub use task_cache::TaskCache;
+pub use task_cache::TaskCacheHashes;
+pub use test_failures::TestFailureRecord;
+pub use test_failures::TestFailuresCache;
 
 /// Permissions used to save a file in the disk caches.
 pub const CACHE_PERM: u32 = 0o644;
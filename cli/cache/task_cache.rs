@@ -0,0 +1,143 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheDBHash;
+use super::cache_db::CacheFailure;
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+pub static TASK_CACHE_DB: CacheDBConfiguration = CacheDBConfiguration {
+  table_initializer: concat!(
+    "CREATE TABLE IF NOT EXISTS taskcache (",
+    "folder TEXT NOT NULL,",
+    "name TEXT NOT NULL,",
+    "inputs_hash INTEGER NOT NULL,",
+    "outputs_hash INTEGER NOT NULL,",
+    "PRIMARY KEY (folder, name)",
+    ");",
+  ),
+  on_version_change: "DELETE FROM taskcache;",
+  preheat_queries: &[],
+  // If the cache fails, just ignore all caching attempts
+  on_failure: CacheFailure::Blackhole,
+};
+
+/// The hashes of the inputs and outputs of a task after its last
+/// successful run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskCacheHashes {
+  pub inputs: CacheDBHash,
+  pub outputs: CacheDBHash,
+}
+
+/// Cache used to skip a task of `deno task` when its inputs and outputs
+/// didn't change since its last successful run.
+#[derive(Clone)]
+pub struct TaskCache(CacheDB);
+
+impl TaskCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_hashes(
+    &self,
+    folder: &str,
+    name: &str,
+  ) -> Option<TaskCacheHashes> {
+    match self.get_hashes_result(folder, name) {
+      Ok(hashes) => hashes,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving task hashes: {err}");
+        } else {
+          // fail silently when not debugging
+          None
+        }
+      }
+    }
+  }
+
+  fn get_hashes_result(
+    &self,
+    folder: &str,
+    name: &str,
+  ) -> Result<Option<TaskCacheHashes>, AnyError> {
+    let query = "SELECT inputs_hash, outputs_hash FROM taskcache WHERE folder=?1 AND name=?2 LIMIT 1";
+    let res = self.0.query_row(query, params![folder, name], |row| {
+      Ok(TaskCacheHashes {
+        inputs: row.get(0)?,
+        outputs: row.get(1)?,
+      })
+    })?;
+    Ok(res)
+  }
+
+  pub fn set_hashes(&self, folder: &str, name: &str, hashes: TaskCacheHashes) {
+    let result = self.0.execute(
+      "INSERT OR REPLACE INTO taskcache (folder, name, inputs_hash, outputs_hash) VALUES (?1, ?2, ?3, ?4)",
+      params![folder, name, hashes.inputs, hashes.outputs],
+    );
+    if let Err(err) = result {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error saving task hashes: {err}");
+      } else {
+        log::debug!("Error saving task hashes: {}", err);
+      }
+    }
+  }
+
+  pub fn remove_hashes(&self, folder: &str, name: &str) {
+    let result = self.0.execute(
+      "DELETE FROM taskcache WHERE folder=?1 AND name=?2",
+      params![folder, name],
+    );
+    if let Err(err) = result {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error removing task hashes: {err}");
+      } else {
+        log::debug!("Error removing task hashes: {}", err);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn task_cache_general_use() {
+    let conn = CacheDB::in_memory(&TASK_CACHE_DB, "1.0.0");
+    let cache = TaskCache::new(conn);
+    let folder = "file:///project/";
+
+    assert_eq!(cache.get_hashes(folder, "build"), None);
+    let hashes = TaskCacheHashes {
+      inputs: CacheDBHash::new(1),
+      outputs: CacheDBHash::new(2),
+    };
+    cache.set_hashes(folder, "build", hashes);
+    assert_eq!(cache.get_hashes(folder, "build"), Some(hashes));
+    assert_eq!(cache.get_hashes("file:///other/", "build"), None);
+
+    let hashes = TaskCacheHashes {
+      inputs: CacheDBHash::new(3),
+      outputs: CacheDBHash::new(4),
+    };
+    cache.set_hashes(folder, "build", hashes);
+    assert_eq!(cache.get_hashes(folder, "build"), Some(hashes));
+
+    cache.remove_hashes(folder, "build");
+    assert_eq!(cache.get_hashes(folder, "build"), None);
+
+    // changing the cli version should clear
+    cache.set_hashes(folder, "build", hashes);
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = TaskCache::new(conn);
+    assert_eq!(cache.get_hashes(folder, "build"), None);
+  }
+}
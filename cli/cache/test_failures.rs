@@ -0,0 +1,161 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheDBHash;
+use super::cache_db::CacheFailure;
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+pub static TEST_FAILURES_CACHE_DB: CacheDBConfiguration =
+  CacheDBConfiguration {
+    table_initializer: concat!(
+      "CREATE TABLE IF NOT EXISTS testfailures (",
+      "specifier TEXT NOT NULL,",
+      "name TEXT NOT NULL,",
+      "source_hash INTEGER NOT NULL,",
+      "PRIMARY KEY (specifier, name)",
+      ");",
+    ),
+    on_version_change: "DELETE FROM testfailures;",
+    preheat_queries: &[],
+    // If the cache fails, just ignore all caching attempts
+    on_failure: CacheFailure::Blackhole,
+  };
+
+/// A test that failed the last time it was run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailureRecord {
+  pub specifier: ModuleSpecifier,
+  pub name: String,
+  /// Hash of the source of the test module when the test failed.
+  pub source_hash: CacheDBHash,
+}
+
+/// The cache of which tests failed in their last run. Used by
+/// `deno test --only-failed`.
+#[derive(Clone)]
+pub struct TestFailuresCache(CacheDB);
+
+impl TestFailuresCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_failures(&self) -> Vec<TestFailureRecord> {
+    match self.get_failures_result() {
+      Ok(failures) => failures,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving test failures: {err}");
+        } else {
+          // fail silently when not debugging
+          Vec::new()
+        }
+      }
+    }
+  }
+
+  fn get_failures_result(&self) -> Result<Vec<TestFailureRecord>, AnyError> {
+    self.0.with_connection(|conn| {
+      let mut stmt = conn.prepare_cached(
+        "SELECT specifier, name, source_hash FROM testfailures ORDER BY specifier, name",
+      )?;
+      let mut rows = stmt.query([])?;
+      let mut failures = Vec::new();
+      while let Some(row) = rows.next()? {
+        let specifier: String = row.get(0)?;
+        let Ok(specifier) = ModuleSpecifier::parse(&specifier) else {
+          continue;
+        };
+        failures.push(TestFailureRecord {
+          specifier,
+          name: row.get(1)?,
+          source_hash: row.get(2)?,
+        });
+      }
+      Ok(failures)
+    })
+  }
+
+  pub fn set_failed(
+    &self,
+    specifier: &ModuleSpecifier,
+    name: &str,
+    source_hash: CacheDBHash,
+  ) {
+    let result = self.0.execute(
+      "INSERT OR REPLACE INTO testfailures (specifier, name, source_hash) VALUES (?1, ?2, ?3)",
+      params![specifier.to_string(), name, source_hash],
+    );
+    if let Err(err) = result {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error saving test failure: {err}");
+      } else {
+        log::debug!("Error saving test failure: {}", err);
+      }
+    }
+  }
+
+  /// Removes all the failures of a test module, including the ones of tests
+  /// that were renamed or deleted since they failed.
+  pub fn clear_module(&self, specifier: &ModuleSpecifier) {
+    let result = self.0.execute(
+      "DELETE FROM testfailures WHERE specifier=?1",
+      params![specifier.to_string()],
+    );
+    if let Err(err) = result {
+      // should never error here, but if it ever does don't fail
+      if cfg!(debug_assertions) {
+        panic!("Error removing test failures: {err}");
+      } else {
+        log::debug!("Error removing test failures: {}", err);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn test_failures_cache_general_use() {
+    let conn = CacheDB::in_memory(&TEST_FAILURES_CACHE_DB, "1.0.0");
+    let cache = TestFailuresCache::new(conn);
+
+    let specifier1 = ModuleSpecifier::parse("file:///a_test.ts").unwrap();
+    let specifier2 = ModuleSpecifier::parse("file:///b_test.ts").unwrap();
+    assert_eq!(cache.get_failures(), vec![]);
+    cache.set_failed(&specifier2, "b", CacheDBHash::new(2));
+    cache.set_failed(&specifier1, "a", CacheDBHash::new(1));
+    cache.set_failed(&specifier1, "a", CacheDBHash::new(3));
+    assert_eq!(
+      cache.get_failures(),
+      vec![
+        TestFailureRecord {
+          specifier: specifier1.clone(),
+          name: "a".to_string(),
+          source_hash: CacheDBHash::new(3),
+        },
+        TestFailureRecord {
+          specifier: specifier2.clone(),
+          name: "b".to_string(),
+          source_hash: CacheDBHash::new(2),
+        },
+      ]
+    );
+
+    // clearing a module removes all of its failures
+    cache.set_failed(&specifier1, "renamed", CacheDBHash::new(3));
+    cache.clear_module(&specifier1);
+    assert_eq!(cache.get_failures().len(), 1);
+
+    // changing the cli version should clear
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = TestFailuresCache::new(conn);
+    assert_eq!(cache.get_failures(), vec![]);
+  }
+}
@@ -12,6 +12,7 @@ const {
   op_test_event_step_result_ok,
   op_test_event_step_wait,
   op_test_get_origin,
+  op_test_snapshot_assert,
 } = core.ops;
 const {
   ArrayPrototypeFilter,
@@ -21,6 +22,7 @@ const {
   Map,
   MapPrototypeGet,
   MapPrototypeSet,
+  NumberIsInteger,
   SafeArrayIterator,
   SymbolToStringTag,
   TypeError,
@@ -127,6 +129,9 @@ function assertExit(fn, isTest) {
 
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
+    if (!("parent" in desc)) {
+      resetTestState(desc);
+    }
     try {
       if (desc.ignore) {
         return "ignored";
@@ -209,6 +214,10 @@ const registerTestIdRetBufU8 = new Uint8Array(registerTestIdRetBuf.buffer);
 // As long as we're using one isolate per test, we can cache the origin since it won't change
 let cachedOrigin = undefined;
 
+/** Number of snapshots asserted so far, keyed by snapshot name.
+ * @type {Map<string, number>} */
+const snapshotCounts = new Map();
+
 function testInner(
   nameOrFnOrOptions,
   optionsOrFn,
@@ -302,6 +311,22 @@ function testInner(
 
   testDesc = { ...testDesc, ...overrides };
 
+  if (
+    testDesc.retry !== undefined &&
+    (!NumberIsInteger(testDesc.retry) || testDesc.retry < 0)
+  ) {
+    throw new TypeError("The 'retry' option must be a non-negative integer");
+  }
+  if (
+    testDesc.timeout !== undefined &&
+    (!NumberIsInteger(testDesc.timeout) || testDesc.timeout < 0 ||
+      testDesc.timeout > 0x7fffffff)
+  ) {
+    throw new TypeError(
+      "The 'timeout' option must be a non-negative integer of milliseconds",
+    );
+  }
+
   // Delete this prop in case the user passed it. It's used to detect steps.
   delete testDesc.parent;
 
@@ -320,6 +345,8 @@ function testInner(
     testDesc.only,
     testDesc.sanitizeOps,
     testDesc.sanitizeResources,
+    testDesc.retry ?? -1,
+    testDesc.timeout ?? -1,
     testDesc.location.fileName,
     testDesc.location.lineNumber,
     testDesc.location.columnNumber,
@@ -331,9 +358,28 @@ function testInner(
     context: createTestContext(testDesc),
     children: [],
     completed: false,
+    snapshotNames: [],
   });
 }
 
+/**
+ * Lets a test start over when it is retried after failing.
+ * @param desc {TestDescription}
+ */
+function resetTestState(desc) {
+  const state = MapPrototypeGet(testStates, desc.id);
+  state.children = [];
+  state.completed = false;
+  for (const name of new SafeArrayIterator(state.snapshotNames)) {
+    MapPrototypeSet(
+      snapshotCounts,
+      name,
+      MapPrototypeGet(snapshotCounts, name) - 1,
+    );
+  }
+  state.snapshotNames = [];
+}
+
 // Main test function provided by Deno.
 function test(
   nameOrFnOrOptions,
@@ -366,6 +412,20 @@ function usesSanitizer(desc) {
   return desc.sanitizeResources || desc.sanitizeOps || desc.sanitizeExit;
 }
 
+/** @param actual {unknown} */
+function serializeSnapshot(actual) {
+  return DenoNs.inspect(actual, {
+    depth: Infinity,
+    sorted: true,
+    trailingComma: true,
+    compact: false,
+    iterableLimit: Infinity,
+    strAbbreviateSize: Infinity,
+    breakLength: Infinity,
+    escapeSequences: false,
+  });
+}
+
 function stepReportResult(desc, result, elapsed) {
   const state = MapPrototypeGet(testStates, desc.id);
   for (const childDesc of state.children) {
@@ -411,6 +471,26 @@ function createTestContext(desc) {
      * File Uri of the test code.
      */
     origin: desc.origin,
+    /**
+     * @param actual {unknown}
+     * @param options {{ name?: string, serializer?: (actual: unknown) => string } | undefined}
+     */
+    assertSnapshot(actual, options = { __proto__: null }) {
+      const name = options.name ?? getFullName(desc);
+      const count = (MapPrototypeGet(snapshotCounts, name) ?? 0) + 1;
+      MapPrototypeSet(snapshotCounts, name, count);
+      ArrayPrototypePush(
+        MapPrototypeGet(testStates, rootId).snapshotNames,
+        name,
+      );
+      const serialized = options.serializer
+        ? options.serializer(actual)
+        : serializeSnapshot(actual);
+      if (typeof serialized !== "string") {
+        throw new TypeError("Snapshot serializer must return a string.");
+      }
+      op_test_snapshot_assert(`${name} ${count}`, serialized);
+    },
     /**
      * @param nameOrFnOrOptions {string | TestStepDefinition | ((t: TestContext) => void | Promise<void>)}
      * @param maybeFn {((t: TestContext) => void | Promise<void>) | undefined}
@@ -9,6 +9,9 @@ use super::tsc;
 
 use crate::args::jsr_url;
 use crate::tools::lint::create_linter;
+use crate::tools::lint::plugins::LintPluginHost;
+use crate::tools::lint::type_aware;
+use crate::tsc::TypeAwareLintDiagnostic;
 use deno_lint::linter::LintConfig;
 use deno_runtime::fs_util::specifier_to_file_path;
 
@@ -173,39 +176,59 @@ fn as_lsp_range(
 pub fn get_lint_references(
   parsed_source: &deno_ast::ParsedSource,
   lint_rules: Vec<&'static dyn LintRule>,
+  plugin_host: Option<&LintPluginHost>,
   lint_config: LintConfig,
 ) -> Result<Vec<Reference>, AnyError> {
   let linter = create_linter(lint_rules);
-  let lint_diagnostics = linter.lint_with_ast(parsed_source, lint_config);
+  let mut lint_diagnostics = linter.lint_with_ast(parsed_source, lint_config);
+  if let Some(plugin_host) = plugin_host {
+    lint_diagnostics.extend(plugin_host.lint(parsed_source)?);
+  }
 
   Ok(
     lint_diagnostics
       .into_iter()
-      .map(|d| Reference {
-        range: as_lsp_range_from_diagnostic(&d),
-        category: Category::Lint {
-          message: d.message,
-          code: d.code,
-          hint: d.hint,
-          quick_fixes: d
-            .fixes
+      .map(as_lint_reference)
+      .collect(),
+  )
+}
+
+/// Gets the references of the diagnostics of the type-aware lint rules, which
+/// are reported by tsc.
+pub fn get_type_aware_lint_references(
+  parsed_source: &deno_ast::ParsedSource,
+  diagnostics: Vec<TypeAwareLintDiagnostic>,
+) -> Vec<Reference> {
+  type_aware::to_lint_diagnostics(parsed_source, diagnostics)
+    .into_iter()
+    .map(as_lint_reference)
+    .collect()
+}
+
+fn as_lint_reference(d: LintDiagnostic) -> Reference {
+  Reference {
+    range: as_lsp_range_from_diagnostic(&d),
+    category: Category::Lint {
+      message: d.message,
+      code: d.code,
+      hint: d.hint,
+      quick_fixes: d
+        .fixes
+        .into_iter()
+        .map(|f| DataQuickFix {
+          description: f.description.to_string(),
+          changes: f
+            .changes
             .into_iter()
-            .map(|f| DataQuickFix {
-              description: f.description.to_string(),
-              changes: f
-                .changes
-                .into_iter()
-                .map(|change| DataQuickFixChange {
-                  range: as_lsp_range(change.range, &d.text_info),
-                  new_text: change.new_text.to_string(),
-                })
-                .collect(),
+            .map(|change| DataQuickFixChange {
+              range: as_lsp_range(change.range, &d.text_info),
+              new_text: change.new_text.to_string(),
             })
             .collect(),
-        },
-      })
-      .collect(),
-  )
+        })
+        .collect(),
+    },
+  }
 }
 
 fn code_as_string(code: &Option<lsp::NumberOrString>) -> String {
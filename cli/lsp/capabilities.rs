@@ -109,7 +109,7 @@ pub fn server_capabilities(
       resolve_provider: Some(true),
     }),
     document_formatting_provider: Some(OneOf::Left(true)),
-    document_range_formatting_provider: None,
+    document_range_formatting_provider: Some(OneOf::Left(true)),
     document_on_type_formatting_provider: None,
     selection_range_provider: Some(SelectionRangeProviderCapability::Simple(
       true,
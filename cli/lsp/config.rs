@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use super::logging::lsp_log;
+use crate::args::deno_json::CliConfigFs;
+use crate::args::deno_json::CliConfigSections;
 use crate::args::discover_npmrc;
 use crate::args::CliLockfile;
 use crate::args::ConfigFile;
@@ -11,6 +13,7 @@ use crate::cache::FastInsecureHasher;
 use crate::file_fetcher::FileFetcher;
 use crate::lsp::logging::lsp_warn;
 use crate::tools::lint::get_configured_rules;
+use crate::tools::lint::plugins::resolve_lint_plugins;
 use crate::tools::lint::ConfiguredRules;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use deno_ast::MediaType;
@@ -1135,8 +1138,10 @@ impl ConfigData {
     file_fetcher: Option<&Arc<FileFetcher>>,
   ) -> Self {
     if let Some(specifier) = config_file_specifier {
+      let config_fs_adapter = DenoConfigFsAdapter::new(&RealFs);
+      let config_fs = CliConfigFs::new(&config_fs_adapter);
       match ConfigFile::from_specifier(
-        &DenoConfigFsAdapter::new(&RealFs),
+        &config_fs,
         specifier.clone(),
         &deno_config::ConfigParseOptions::default(),
       ) {
@@ -1145,8 +1150,10 @@ impl ConfigData {
             "  Resolved Deno configuration file: \"{}\"",
             config_file.specifier.as_str()
           );
+          let config_sections = config_fs.take_sections().get(&config_file);
           Self::load_inner(
             Some(config_file),
+            config_sections,
             scope,
             workspace_root,
             settings,
@@ -1162,6 +1169,7 @@ impl ConfigData {
           );
           let mut data = Self::load_inner(
             None,
+            Default::default(),
             scope,
             workspace_root,
             settings,
@@ -1185,13 +1193,21 @@ impl ConfigData {
         }
       }
     } else {
-      Self::load_inner(None, scope, workspace_root, settings, file_fetcher)
-        .await
+      Self::load_inner(
+        None,
+        Default::default(),
+        scope,
+        workspace_root,
+        settings,
+        file_fetcher,
+      )
+      .await
     }
   }
 
   async fn load_inner(
     config_file: Option<ConfigFile>,
+    config_sections: Arc<CliConfigSections>,
     scope: &ModuleSpecifier,
     workspace_root: Option<&ConfigData>,
     settings: &Settings,
@@ -1232,7 +1248,14 @@ impl ConfigData {
         .and_then(|config_file| {
           config_file
             .to_fmt_config()
-            .and_then(|o| FmtOptions::resolve(o, &Default::default(), None))
+            .and_then(|o| {
+              let mut languages = config_sections.to_fmt_languages_config()?;
+              if let Some(workspace_data) = workspace_root {
+                languages =
+                  languages.or(workspace_data.fmt_options.languages.clone());
+              }
+              FmtOptions::resolve(o, languages, &Default::default(), None)
+            })
             .inspect_err(|err| {
               lsp_warn!("  Couldn't read formatter configuration: {}", err)
             })
@@ -1260,7 +1283,19 @@ impl ConfigData {
         .and_then(|config_file| {
           config_file
             .to_lint_config()
-            .and_then(|o| LintOptions::resolve(o, Default::default(), None))
+            .and_then(|o| {
+              let mut plugins = workspace_root
+                .map(|data| data.lint_options.plugins.clone())
+                .unwrap_or_default();
+              for plugin in
+                config_sections.to_lint_plugins(&config_file.specifier)?
+              {
+                if !plugins.contains(&plugin) {
+                  plugins.push(plugin);
+                }
+              }
+              LintOptions::resolve(o, plugins, Default::default(), None)
+            })
             .inspect_err(|err| {
               lsp_warn!("  Couldn't read lint configuration: {}", err)
             })
@@ -1268,11 +1303,14 @@ impl ConfigData {
         })
         .map(Arc::new)
         .unwrap_or_default();
-      let lint_rules = Arc::new(get_configured_rules(
-        lint_options.rules.clone(),
-        config_file.as_ref(),
-      ));
-      (lint_options, lint_rules)
+      let mut lint_rules =
+        get_configured_rules(lint_options.rules.clone(), config_file.as_ref());
+      lint_rules.plugins = resolve_lint_plugins(&lint_options)
+        .inspect_err(|err| {
+          lsp_warn!("  Couldn't resolve lint plugins: {}", err)
+        })
+        .unwrap_or_default();
+      (lint_options, Arc::new(lint_rules))
     });
 
     let ts_config = LspTsConfig::new(config_file.as_ref());
@@ -1763,6 +1801,7 @@ impl ConfigTree {
     let data = Arc::new(
       ConfigData::load_inner(
         Some(config_file),
+        Default::default(),
         &scope,
         None,
         &Default::default(),
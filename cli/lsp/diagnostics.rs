@@ -18,9 +18,11 @@ use super::urls::LspUrlMap;
 use crate::args::LintOptions;
 use crate::graph_util;
 use crate::graph_util::enhanced_resolution_error_message;
+use crate::lsp::logging::lsp_warn;
 use crate::lsp::lsp_custom::DiagnosticBatchNotificationParams;
 use crate::resolver::SloppyImportsResolution;
 use crate::resolver::SloppyImportsResolver;
+use crate::tools::lint::ConfiguredRules;
 use crate::util::path::to_percent_decoded_str;
 
 use deno_ast::MediaType;
@@ -40,7 +42,6 @@ use deno_graph::Resolution;
 use deno_graph::ResolutionError;
 use deno_graph::SpecifierError;
 use deno_lint::linter::LintConfig;
-use deno_lint::rules::LintRule;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node;
 use deno_runtime::tokio_util::create_basic_runtime;
@@ -84,6 +85,8 @@ type DiagnosticVec = Vec<DiagnosticRecord>;
 pub enum DiagnosticSource {
   Deno,
   Lint,
+  /// The lint rules that need type information, which run in tsc.
+  TypeAwareLint,
   Ts,
 }
 
@@ -91,7 +94,7 @@ impl DiagnosticSource {
   pub fn as_lsp_source(&self) -> &'static str {
     match self {
       Self::Deno => "deno",
-      Self::Lint => "deno-lint",
+      Self::Lint | Self::TypeAwareLint => "deno-lint",
       Self::Ts => "deno-ts",
     }
   }
@@ -533,6 +536,39 @@ impl DiagnosticsServer {
                     }
                   }
 
+                  if !token.is_cancelled() {
+                    let mark = performance
+                      .mark("lsp.update_diagnostics_type_aware_lint");
+                    let diagnostics = generate_type_aware_lint_diagnostics(
+                      snapshot.clone(),
+                      &config,
+                      &ts_server,
+                      token.clone(),
+                    )
+                    .await
+                    .map_err(|err| {
+                      if !token.is_cancelled() {
+                        error!(
+                          "Error generating type-aware lint diagnostics: {}",
+                          err
+                        );
+                      }
+                    })
+                    .unwrap_or_default();
+                    if !token.is_cancelled() {
+                      messages_len += diagnostics_publisher
+                        .publish(
+                          DiagnosticSource::TypeAwareLint,
+                          diagnostics,
+                          &url_map,
+                          snapshot.documents.as_ref(),
+                          &token,
+                        )
+                        .await;
+                      performance.measure(mark);
+                    }
+                  }
+
                   if let Some(batch_index) = batch_index {
                     diagnostics_publisher
                       .client
@@ -843,7 +879,7 @@ fn generate_lint_diagnostics(
           &document,
           &lint_options,
           lint_config,
-          lint_rules.rules.clone(),
+          &lint_rules,
         ),
       },
     });
@@ -855,16 +891,24 @@ fn generate_document_lint_diagnostics(
   document: &Document,
   lint_options: &LintOptions,
   lint_config: LintConfig,
-  lint_rules: Vec<&'static dyn LintRule>,
+  lint_rules: &ConfiguredRules,
 ) -> Vec<lsp::Diagnostic> {
   if !lint_options.files.matches_specifier(document.specifier()) {
     return Vec::new();
   }
   match document.maybe_parsed_source() {
     Some(Ok(parsed_source)) => {
-      if let Ok(references) =
-        analysis::get_lint_references(parsed_source, lint_rules, lint_config)
-      {
+      let plugin_host = lint_rules
+        .plugin_host()
+        .inspect_err(|err| lsp_warn!("Couldn't load lint plugins: {:#}", err))
+        .ok()
+        .flatten();
+      if let Ok(references) = analysis::get_lint_references(
+        parsed_source,
+        lint_rules.rules.clone(),
+        plugin_host.as_deref(),
+        lint_config,
+      ) {
         references
           .into_iter()
           .map(|r| r.to_diagnostic())
@@ -881,6 +925,82 @@ fn generate_document_lint_diagnostics(
   }
 }
 
+/// Generates the diagnostics of the type-aware lint rules, which are run by
+/// tsc on the open documents that are linted.
+async fn generate_type_aware_lint_diagnostics(
+  snapshot: Arc<language_server::StateSnapshot>,
+  config: &Config,
+  ts_server: &tsc::TsServer,
+  token: CancellationToken,
+) -> Result<DiagnosticVec, AnyError> {
+  let config_data_by_scope = config.tree.data_by_scope();
+  let documents = snapshot
+    .documents
+    .documents(DocumentsFilter::OpenDiagnosable);
+  let mut specifiers = Vec::new();
+  let mut diagnostics_vec = Vec::new();
+  for document in &documents {
+    let specifier = document.specifier();
+    if specifier.scheme() != "file"
+      || !config.specifier_enabled(specifier)
+      || !config.workspace_settings_for_specifier(specifier).lint
+      || snapshot.resolver.in_node_modules(specifier)
+    {
+      continue;
+    }
+    let is_linted = config
+      .tree
+      .scope_for_specifier(specifier)
+      .and_then(|s| config_data_by_scope.get(s))
+      .is_some_and(|d| {
+        !d.lint_rules.type_aware_rules.is_empty()
+          && d.lint_options.files.matches_specifier(specifier)
+      });
+    if is_linted {
+      specifiers.push(specifier.clone());
+    } else {
+      // clear the diagnostics of documents that aren't linted anymore
+      diagnostics_vec.push(DiagnosticRecord {
+        specifier: specifier.clone(),
+        versioned: VersionedDiagnostics {
+          version: document.maybe_lsp_version(),
+          diagnostics: Vec::new(),
+        },
+      });
+    }
+  }
+  let lint_diagnostics_map = if specifiers.is_empty() {
+    Default::default()
+  } else {
+    ts_server
+      .get_lint_diagnostics(snapshot.clone(), specifiers, token)
+      .await?
+  };
+  for (specifier, lint_diagnostics) in lint_diagnostics_map {
+    let Some(document) = snapshot.documents.get(&specifier) else {
+      continue;
+    };
+    let diagnostics = match document.maybe_parsed_source() {
+      Some(Ok(parsed_source)) => analysis::get_type_aware_lint_references(
+        parsed_source,
+        lint_diagnostics,
+      )
+      .into_iter()
+      .map(|r| r.to_diagnostic())
+      .collect(),
+      _ => Vec::new(),
+    };
+    diagnostics_vec.push(DiagnosticRecord {
+      specifier,
+      versioned: VersionedDiagnostics {
+        version: document.maybe_lsp_version(),
+        diagnostics,
+      },
+    });
+  }
+  Ok(diagnostics_vec)
+}
+
 async fn generate_ts_diagnostics(
   snapshot: Arc<language_server::StateSnapshot>,
   config: &Config,
@@ -88,6 +88,7 @@ use super::tsc::GetCompletionDetailsArgs;
 use super::tsc::TsServer;
 use super::urls;
 use crate::args::create_default_npmrc;
+use crate::args::deno_json::CliConfigFs;
 use crate::args::get_root_cert_store;
 use crate::args::CaData;
 use crate::args::CacheSetting;
@@ -102,6 +103,7 @@ use crate::lsp::logging::init_log_file;
 use crate::lsp::tsc::file_text_changes_to_workspace_edit;
 use crate::lsp::urls::LspUrlKind;
 use crate::tools::fmt::format_file;
+use crate::tools::fmt::format_file_ranges;
 use crate::tools::fmt::format_parsed_source;
 use crate::tools::upgrade::check_for_upgrades_for_lsp;
 use crate::tools::upgrade::upgrade_check_enabled;
@@ -1311,13 +1313,41 @@ impl Inner {
     &self,
     params: DocumentFormattingParams,
   ) -> LspResult<Option<Vec<TextEdit>>> {
-    let file_referrer = (self
-      .documents
-      .is_valid_file_referrer(&params.text_document.uri))
-    .then(|| params.text_document.uri.clone());
-    let mut specifier = self
-      .url_map
-      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark_with_args("lsp.formatting", &params);
+    let result = self
+      .format_document(&params.text_document.uri, &params.options, None)
+      .await;
+    self.performance.measure(mark);
+    result
+  }
+
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mark = self
+      .performance
+      .mark_with_args("lsp.range_formatting", &params);
+    let result = self
+      .format_document(
+        &params.text_document.uri,
+        &params.options,
+        Some(params.range),
+      )
+      .await;
+    self.performance.measure(mark);
+    result
+  }
+
+  async fn format_document(
+    &self,
+    uri: &Url,
+    options: &FormattingOptions,
+    range: Option<Range>,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let file_referrer =
+      (self.documents.is_valid_file_referrer(uri)).then(|| uri.clone());
+    let mut specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
     // skip formatting any files ignored by the config file
     if !self
       .config
@@ -1336,35 +1366,54 @@ impl Inner {
     // Detect vendored paths. Vendor file URLs will normalize to their remote
     // counterparts, but for formatting we want to favour the file URL.
     // TODO(nayeemrmn): Implement `Document::file_resource_path()` or similar.
-    if specifier.scheme() != "file"
-      && params.text_document.uri.scheme() == "file"
-    {
-      specifier = params.text_document.uri.clone();
+    if specifier.scheme() != "file" && uri.scheme() == "file" {
+      specifier = uri.clone();
     }
     let file_path = specifier_to_file_path(&specifier).map_err(|err| {
       error!("{:#}", err);
       LspError::invalid_request()
     })?;
-    let mark = self.performance.mark_with_args("lsp.formatting", &params);
 
     // spawn a blocking task to allow doing other work while this is occurring
     let text_edits = deno_core::unsync::spawn_blocking({
-      let mut fmt_options = self
-        .config
-        .tree
-        .fmt_options_for_specifier(&specifier)
-        .options
-        .clone();
-      fmt_options.use_tabs = Some(!params.options.insert_spaces);
-      fmt_options.indent_width = Some(params.options.tab_size as u8);
+      let config_fmt_options =
+        self.config.tree.fmt_options_for_specifier(&specifier);
+      let mut fmt_options = config_fmt_options.options.clone();
+      fmt_options.use_tabs = Some(!options.insert_spaces);
+      fmt_options.indent_width = Some(options.tab_size as u8);
+      let languages = config_fmt_options.languages.clone();
       let document = document.clone();
       move || {
-        let format_result = match document.maybe_parsed_source() {
-          Some(Ok(parsed_source)) => {
-            format_parsed_source(parsed_source, &fmt_options)
+        let format_result = match (document.maybe_parsed_source(), range) {
+          (_, Some(range)) => {
+            // give the file path an extension that matches the language, as
+            // below
+            let file_path = document
+              .maybe_language_id()
+              .and_then(|id| id.as_extension())
+              .map(|ext| file_path.with_extension(ext))
+              .unwrap_or(file_path);
+            // a range ending at the start of a line doesn't include it
+            let end_line = if range.end.character == 0
+              && range.end.line > range.start.line
+            {
+              range.end.line
+            } else {
+              range.end.line + 1
+            };
+            format_file_ranges(
+              &file_path,
+              document.content(),
+              &[range.start.line as usize..end_line as usize],
+              &fmt_options,
+              &languages,
+            )
+          }
+          (Some(Ok(parsed_source)), None) => {
+            format_parsed_source(parsed_source, &fmt_options, &languages)
           }
-          Some(Err(err)) => Err(anyhow!("{:#}", err)),
-          None => {
+          (Some(Err(err)), None) => Err(anyhow!("{:#}", err)),
+          (None, None) => {
             // the file path is only used to determine what formatter should
             // be used to format the file, so give the filepath an extension
             // that matches what the user selected as the language
@@ -1374,7 +1423,12 @@ impl Inner {
               .map(|ext| file_path.with_extension(ext))
               .unwrap_or(file_path);
             // it's not a js/ts file, so attempt to format its contents
-            format_file(&file_path, document.content(), &fmt_options)
+            format_file(
+              &file_path,
+              document.content(),
+              &fmt_options,
+              &languages,
+            )
           }
         };
         match format_result {
@@ -1394,7 +1448,6 @@ impl Inner {
     .await
     .unwrap();
 
-    self.performance.measure(mark);
     if let Some(text_edits) = text_edits {
       if text_edits.is_empty() {
         Ok(None)
@@ -3277,6 +3330,16 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.inner.read().await.formatting(params).await
   }
 
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    if !self.init_flag.is_raised() {
+      self.init_flag.wait_raised().await;
+    }
+    self.inner.read().await.range_formatting(params).await
+  }
+
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
     if !self.init_flag.is_raised() {
       self.init_flag.wait_raised().await;
@@ -3556,12 +3619,15 @@ impl Inner {
       .and_then(|d| d.scope.to_file_path().ok())
       .unwrap_or_else(|| self.initial_cwd.clone());
     // todo: we need a way to convert config data to a Workspace
+    let config_fs_adapter =
+      DenoConfigFsAdapter::new(&deno_runtime::deno_fs::RealFs);
+    let config_fs = CliConfigFs::new(&config_fs_adapter);
     let workspace = Arc::new(Workspace::discover(
       deno_config::workspace::WorkspaceDiscoverStart::Dirs(&[
         initial_cwd.clone()
       ]),
       &WorkspaceDiscoverOptions {
-        fs: &DenoConfigFsAdapter::new(&deno_runtime::deno_fs::RealFs),
+        fs: &config_fs,
         pkg_json_cache: None,
         config_parse_options: deno_config::ConfigParseOptions {
           include_task_comments: false,
@@ -3599,6 +3665,7 @@ impl Inner {
         .and_then(|d| d.npmrc.clone())
         .unwrap_or_else(create_default_npmrc),
       workspace,
+      Arc::new(config_fs.take_sections()),
       force_global_cache,
     )?;
 
@@ -304,6 +304,9 @@ impl TestRun {
               filter,
               shuffle: None,
               trace_leaks: false,
+              retries: 0,
+              timeout: None,
+              snapshot_mode: Some(test::TestSnapshotMode::resolve(false)),
             },
           ))
         }
@@ -371,6 +374,13 @@ impl TestRun {
                   test::TestResult::Cancelled => {
                     summary.failed += 1;
                   }
+                  test::TestResult::TimedOut(timeout) => {
+                    summary.failed += 1;
+                    summary.failures.push((
+                      (&description).into(),
+                      test::TestFailure::TimedOut(timeout.clone()),
+                    ));
+                  }
                 }
                 reporter.report_result(&description, &result, elapsed);
               }
@@ -407,6 +417,8 @@ impl TestRun {
                 );
               }
             }
+            test::TestEvent::Retry(..) => {}
+            test::TestEvent::Snapshots(_) => {}
             test::TestEvent::Completed => {
               reporter.report_completed();
             }
@@ -666,6 +678,14 @@ impl LspTestReporter {
           duration: Some(elapsed as u32),
         })
       }
+      test::TestResult::TimedOut(timeout) => {
+        let desc = self.tests.get(&desc.id).unwrap();
+        self.progress(lsp_custom::TestRunProgressMessage::Failed {
+          test: desc.as_test_identifier(&self.tests),
+          messages: as_test_messages(timeout.to_string(), false),
+          duration: Some(elapsed as u32),
+        })
+      }
     }
   }
 
@@ -503,6 +503,67 @@ impl TsServer {
     Ok(diagnostics_map)
   }
 
+  /// Gets the diagnostics of the type-aware lint rules configured for the
+  /// scope of each specifier.
+  pub async fn get_lint_diagnostics(
+    &self,
+    snapshot: Arc<StateSnapshot>,
+    specifiers: Vec<ModuleSpecifier>,
+    token: CancellationToken,
+  ) -> Result<
+    IndexMap<ModuleSpecifier, Vec<crate::tsc::TypeAwareLintDiagnostic>>,
+    AnyError,
+  > {
+    let config_data_by_scope = snapshot.config.tree.data_by_scope();
+    let mut specifiers_by_scope = BTreeMap::new();
+    for specifier in specifiers {
+      let scope = snapshot
+        .config
+        .tree
+        .scope_for_specifier(&specifier)
+        .cloned();
+      let specifiers = specifiers_by_scope.entry(scope).or_insert(vec![]);
+      specifiers.push(self.specifier_map.denormalize(&specifier));
+    }
+    let mut results = FuturesOrdered::new();
+    for (scope, specifiers) in specifiers_by_scope {
+      let rules = scope
+        .as_ref()
+        .and_then(|s| config_data_by_scope.get(s))
+        .map(|d| d.lint_rules.type_aware_rules.clone())
+        .unwrap_or_default();
+      if rules.is_empty() {
+        continue;
+      }
+      let req = TscRequest::GetLintDiagnostics((
+        specifiers,
+        rules,
+        snapshot.project_version,
+      ));
+      results.push_back(self.request_with_cancellation::<IndexMap<
+        String,
+        Vec<crate::tsc::TypeAwareLintDiagnostic>,
+      >>(
+        snapshot.clone(), req, scope, token.clone()
+      ));
+    }
+    let mut diagnostics_map = IndexMap::new();
+    while let Some(raw_diagnostics) = results.next().await {
+      let raw_diagnostics = raw_diagnostics
+        .inspect_err(|err| {
+          if !token.is_cancelled() {
+            lsp_warn!("Error generating type-aware lint diagnostics: {err}");
+          }
+        })
+        .unwrap_or_default();
+      for (specifier, diagnostics) in raw_diagnostics {
+        diagnostics_map
+          .insert(self.specifier_map.normalize(&specifier)?, diagnostics);
+      }
+    }
+    Ok(diagnostics_map)
+  }
+
   pub async fn cleanup_semantic_cache(&self, snapshot: Arc<StateSnapshot>) {
     for scope in snapshot
       .config
@@ -5119,6 +5180,7 @@ pub struct JsNull;
 #[derive(Debug, Clone, Serialize)]
 pub enum TscRequest {
   GetDiagnostics((Vec<String>, usize)),
+  GetLintDiagnostics((Vec<String>, Vec<&'static str>, usize)),
   GetAssets,
 
   CleanupSemanticCache,
@@ -5238,6 +5300,9 @@ impl TscRequest {
       TscRequest::GetDiagnostics(args) => {
         ("$getDiagnostics", Some(serde_v8::to_v8(scope, args)?))
       }
+      TscRequest::GetLintDiagnostics(args) => {
+        ("$getLintDiagnostics", Some(serde_v8::to_v8(scope, args)?))
+      }
       TscRequest::FindReferences(args) => {
         ("findReferences", Some(serde_v8::to_v8(scope, args)?))
       }
@@ -5333,6 +5398,7 @@ impl TscRequest {
   fn method(&self) -> &'static str {
     match self {
       TscRequest::GetDiagnostics(_) => "$getDiagnostics",
+      TscRequest::GetLintDiagnostics(_) => "$getLintDiagnostics",
       TscRequest::CleanupSemanticCache => "cleanupSemanticCache",
       TscRequest::FindReferences(_) => "findReferences",
       TscRequest::GetNavigationTree(_) => "getNavigationTree",
@@ -28,6 +28,7 @@ mod version;
 mod worker;
 
 use crate::args::flags_from_vec;
+use crate::args::CheckReporterKind;
 use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::args::DENO_FUTURE;
@@ -124,9 +125,13 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       let factory = CliFactory::from_flags(flags)?;
       let main_graph_container =
         factory.main_module_graph_container().await?;
-      main_graph_container
+      let result = main_graph_container
         .load_and_type_check_files(&check_flags.files)
-        .await
+        .await;
+      match check_flags.reporter {
+        CheckReporterKind::Pretty => result.map(|()| 0),
+        CheckReporterKind::Sarif => tools::check::report_sarif(result),
+      }
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -174,11 +179,21 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       spawn_subcommand(async move { tools::repl::run(flags, repl_flags).await })
     }
     DenoSubcommand::Run(run_flags) => spawn_subcommand(async move {
-      if run_flags.is_stdin() {
+      if let Some(format) = run_flags.learn_permissions {
+        tools::run::learn_permissions::start_learning(
+          format,
+          env::current_dir().context("Failed getting cwd.")?,
+        );
+      }
+      let result = if run_flags.is_stdin() {
         tools::run::run_from_stdin(flags).await
       } else {
         tools::run::run_script(WorkerExecutionMode::Run, flags, run_flags.watch).await
+      };
+      if run_flags.learn_permissions.is_some() {
+        tools::run::learn_permissions::print_learned_permissions();
       }
+      result
     }),
     DenoSubcommand::Serve(serve_flags) => spawn_subcommand(async move {
       tools::run::run_script(WorkerExecutionMode::Serve, flags, serve_flags.watch).await
@@ -395,6 +410,10 @@ fn resolve_flags_and_init(
   init_v8_flags(&default_v8_flags, &flags.v8_flags, get_v8_flags_from_env());
   deno_core::JsRuntime::init_platform(None);
   util::logger::init(flags.log_level);
+  flags
+    .permissions
+    .init_audit_log(env::current_dir().ok().as_deref())?;
+  flags.permissions.init_prompt_socket()?;
 
   Ok(flags)
 }
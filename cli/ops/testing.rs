@@ -6,6 +6,7 @@ use crate::tools::test::TestEvent;
 use crate::tools::test::TestEventSender;
 use crate::tools::test::TestFailure;
 use crate::tools::test::TestLocation;
+use crate::tools::test::TestSnapshotState;
 use crate::tools::test::TestStepDescription;
 use crate::tools::test::TestStepResult;
 
@@ -30,6 +31,7 @@ deno_core::extension!(deno_test,
     op_register_test,
     op_register_test_step,
     op_test_get_origin,
+    op_test_snapshot_assert,
     op_test_event_step_wait,
     op_test_event_step_result_ok,
     op_test_event_step_result_ignored,
@@ -105,6 +107,8 @@ fn op_register_test(
   only: bool,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  #[smi] retry: i32,
+  #[smi] timeout: i32,
   #[string] file_name: String,
   #[smi] line_number: u32,
   #[smi] column_number: u32,
@@ -125,6 +129,8 @@ fn op_register_test(
     only,
     sanitize_ops,
     sanitize_resources,
+    retry: usize::try_from(retry).ok(),
+    timeout: u64::try_from(timeout).ok(),
     origin: origin.clone(),
     location: TestLocation {
       file_name,
@@ -144,6 +150,20 @@ fn op_test_get_origin(state: &mut OpState) -> String {
   state.borrow::<ModuleSpecifier>().to_string()
 }
 
+#[op2(fast)]
+fn op_test_snapshot_assert(
+  state: &mut OpState,
+  #[string] name: String,
+  #[string] actual: String,
+) -> Result<(), AnyError> {
+  let Some(snapshots) = state.try_borrow_mut::<TestSnapshotState>() else {
+    return Err(generic_error(
+      "Snapshot assertions are only supported when running `deno test`",
+    ));
+  };
+  snapshots.assert(name, actual)
+}
+
 #[op2(fast)]
 #[smi]
 #[allow(clippy::too_many_arguments)]
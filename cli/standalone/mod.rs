@@ -604,6 +604,8 @@ pub async fn run(
     }),
   };
 
+  metadata.permissions.init_audit_log(maybe_cwd.as_deref())?;
+  metadata.permissions.init_prompt_socket()?;
   let permissions = {
     let mut permissions =
       metadata.permissions.to_options(maybe_cwd.as_deref())?;
@@ -361,6 +361,76 @@ pub mod reporter {
 
     s
   }
+
+  #[derive(Clone, Debug, PartialEq)]
+  pub struct Comparison {
+    /// The change of the average time, in percent of the baseline.
+    pub percent: f64,
+    /// Whether the change stands out from the spread of the measurements.
+    pub significant: bool,
+  }
+
+  /// Compares the stats of a benchmark with the ones saved in a baseline.
+  ///
+  /// The change is significant when the two runs barely overlap, that is when
+  /// even the fastest iteration of one run is slower than 75% of the
+  /// iterations of the other.
+  pub fn compare(
+    stats: &BenchmarkStats,
+    baseline: &BenchmarkStats,
+  ) -> Comparison {
+    let percent = if baseline.avg > 0.0 {
+      (stats.avg - baseline.avg) / baseline.avg * 100.0
+    } else {
+      0.0
+    };
+    Comparison {
+      percent,
+      significant: stats.min > baseline.p75 || baseline.min > stats.p75,
+    }
+  }
+
+  pub fn baseline_comparison(
+    baseline_name: &str,
+    baseline: &BenchmarkStats,
+    comparison: &Comparison,
+    options: &Options,
+  ) -> String {
+    let size = options.size;
+    let mut s = String::new();
+
+    s.push_str(
+      &colors::gray(format!("{:<size$}", format!("  vs {baseline_name}")))
+        .to_string(),
+    );
+    s.push_str(&format!(
+      "{:>30}",
+      format!("{}/iter", colors::yellow(fmt_duration(baseline.avg)))
+    ));
+
+    let percent = format!("{:>+13.2}%", comparison.percent);
+    if !comparison.significant {
+      s.push_str(&format!(
+        "{} {}",
+        colors::gray(percent),
+        colors::gray("(no significant change)")
+      ));
+    } else if comparison.percent > 0.0 {
+      s.push_str(&format!(
+        "{} {}",
+        colors::red(percent),
+        colors::red("slower")
+      ));
+    } else {
+      s.push_str(&format!(
+        "{} {}",
+        colors::green(percent),
+        colors::green("faster")
+      ));
+    }
+
+    s
+  }
 }
 
 #[cfg(test)]
@@ -382,6 +452,34 @@ mod tests {
     assert_eq!(into_decimal_and_fractional_parts(10.99), (11, 0));
   }
 
+  fn stats(avg: f64, min: f64, p75: f64) -> reporter::BenchmarkStats {
+    reporter::BenchmarkStats {
+      avg,
+      min,
+      max: p75 * 2.0,
+      p75,
+      p99: p75 * 1.5,
+      p995: p75 * 1.6,
+    }
+  }
+
+  #[test]
+  fn test_compare() {
+    let baseline = stats(100.0, 80.0, 110.0);
+
+    let comparison = reporter::compare(&stats(105.0, 90.0, 115.0), &baseline);
+    assert_eq!(comparison.percent, 5.0);
+    assert!(!comparison.significant);
+
+    let comparison = reporter::compare(&stats(150.0, 120.0, 160.0), &baseline);
+    assert_eq!(comparison.percent, 50.0);
+    assert!(comparison.significant);
+
+    let comparison = reporter::compare(&stats(50.0, 40.0, 60.0), &baseline);
+    assert_eq!(comparison.percent, -50.0);
+    assert!(comparison.significant);
+  }
+
   #[test]
   fn test_avg_to_iter_per_s() {
     assert_eq!(avg_to_iter_per_s(55.85), "17,905,103.0");
@@ -3,6 +3,8 @@
 use crate::args::BenchFlags;
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::cache::BenchBaselineRecord;
+use crate::cache::BenchBaselinesCache;
 use crate::colors;
 use crate::display::write_json_to_stdout;
 use crate::factory::CliFactory;
@@ -25,6 +27,7 @@ use deno_core::error::JsError;
 use deno_core::futures::future;
 use deno_core::futures::stream;
 use deno_core::futures::StreamExt;
+use deno_core::serde_json;
 use deno_core::serde_v8;
 use deno_core::unsync::spawn;
 use deno_core::unsync::spawn_blocking;
@@ -40,6 +43,7 @@ use indexmap::IndexSet;
 use log::Level;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
@@ -59,6 +63,9 @@ struct BenchSpecifierOptions {
   filter: TestFilter,
   json: bool,
   log_level: Option<log::Level>,
+  baseline: Option<Arc<SavedBaseline>>,
+  save_baseline: Option<String>,
+  regression_threshold: Option<u32>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -134,14 +141,140 @@ impl BenchReport {
   }
 }
 
+impl BenchStats {
+  fn mitata_stats(&self) -> mitata::reporter::BenchmarkStats {
+    mitata::reporter::BenchmarkStats {
+      avg: self.avg,
+      min: self.min,
+      max: self.max,
+      p75: self.p75,
+      p99: self.p99,
+      p995: self.p995,
+    }
+  }
+}
+
+/// Identifies a bench across runs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BenchKey {
+  origin: String,
+  group: Option<String>,
+  name: String,
+}
+
+impl From<&BenchDescription> for BenchKey {
+  fn from(desc: &BenchDescription) -> Self {
+    Self {
+      origin: desc.origin.clone(),
+      group: desc.group.clone(),
+      name: desc.name.clone(),
+    }
+  }
+}
+
+/// The results of a previous run, saved with `--save-baseline`.
+#[derive(Debug)]
+pub struct SavedBaseline {
+  name: String,
+  stats: HashMap<BenchKey, BenchStats>,
+}
+
+impl SavedBaseline {
+  fn load(cache: &BenchBaselinesCache, name: &str) -> Result<Self, AnyError> {
+    let stats = cache
+      .get_baseline(name)
+      .into_iter()
+      .filter_map(|record| {
+        let stats = serde_json::from_str::<BenchStats>(&record.stats).ok()?;
+        let key = BenchKey {
+          origin: record.specifier,
+          group: record.group,
+          name: record.name,
+        };
+        Some((key, stats))
+      })
+      .collect::<HashMap<_, _>>();
+    if stats.is_empty() {
+      return Err(generic_error(format!(
+        "Bench baseline \"{name}\" not found. Save it first with --save-baseline={name}"
+      )));
+    }
+    Ok(Self {
+      name: name.to_string(),
+      stats,
+    })
+  }
+
+  fn get(&self, desc: &BenchDescription) -> Option<&BenchStats> {
+    self.stats.get(&BenchKey::from(desc))
+  }
+}
+
+fn save_baseline(
+  cache: &BenchBaselinesCache,
+  name: &str,
+  measurements: &[(BenchDescription, BenchStats)],
+) {
+  for (desc, stats) in measurements {
+    if desc.warmup {
+      continue;
+    }
+    cache.set_measurement(
+      name,
+      &BenchBaselineRecord {
+        specifier: desc.origin.clone(),
+        group: desc.group.clone(),
+        name: desc.name.clone(),
+        stats: serde_json::to_string(stats).unwrap(),
+      },
+    );
+  }
+}
+
+/// Errors with the benches that are significantly slower than in the
+/// baseline by more than `threshold` percent.
+fn check_regressions(
+  baseline: &SavedBaseline,
+  measurements: &[(BenchDescription, BenchStats)],
+  threshold: u32,
+) -> Result<(), AnyError> {
+  let regressions = measurements
+    .iter()
+    .filter(|(desc, _)| !desc.warmup)
+    .filter_map(|(desc, stats)| {
+      let baseline_stats = baseline.get(desc)?;
+      let comparison = mitata::reporter::compare(
+        &stats.mitata_stats(),
+        &baseline_stats.mitata_stats(),
+      );
+      (comparison.significant && comparison.percent > threshold as f64)
+        .then(|| format!("  {}: {:+.2}%", desc.name, comparison.percent))
+    })
+    .collect::<Vec<_>>();
+  if regressions.is_empty() {
+    return Ok(());
+  }
+  Err(generic_error(format!(
+    "Bench failed because {} regressed by more than {threshold}% compared to baseline \"{}\":\n{}",
+    if regressions.len() == 1 {
+      "1 benchmark".to_string()
+    } else {
+      format!("{} benchmarks", regressions.len())
+    },
+    baseline.name,
+    regressions.join("\n")
+  )))
+}
+
 fn create_reporter(
   show_output: bool,
   json: bool,
+  baseline: Option<Arc<SavedBaseline>>,
 ) -> Box<dyn BenchReporter + Send> {
   if json {
     return Box::new(JsonReporter::new());
   }
-  Box::new(ConsoleReporter::new(show_output))
+  Box::new(ConsoleReporter::new(show_output, baseline))
 }
 
 /// Run a single specifier as an executable bench module.
@@ -269,6 +402,7 @@ async fn bench_specifiers(
   permissions: &Permissions,
   specifiers: Vec<ModuleSpecifier>,
   options: BenchSpecifierOptions,
+  baselines_cache: BenchBaselinesCache,
 ) -> Result<(), AnyError> {
   let (sender, mut receiver) = unbounded_channel::<BenchEvent>();
   let log_level = options.log_level;
@@ -299,8 +433,11 @@ async fn bench_specifiers(
     spawn(async move {
       let mut used_only = false;
       let mut report = BenchReport::new();
-      let mut reporter =
-        create_reporter(log_level != Some(Level::Error), options.json);
+      let mut reporter = create_reporter(
+        log_level != Some(Level::Error),
+        options.json,
+        options.baseline.clone(),
+      );
       let mut benches = IndexMap::new();
 
       while let Some(event) = receiver.recv().await {
@@ -361,6 +498,17 @@ async fn bench_specifiers(
         return Err(generic_error("Bench failed"));
       }
 
+      // only a complete and successful run replaces the saved baseline
+      if let Some(name) = &options.save_baseline {
+        save_baseline(&baselines_cache, name, &report.measurements);
+      }
+
+      if let (Some(baseline), Some(threshold)) =
+        (&options.baseline, options.regression_threshold)
+      {
+        check_regressions(baseline, &report.measurements, threshold)?;
+      }
+
       Ok(())
     })
   };
@@ -444,6 +592,14 @@ pub async fn run_benchmarks(
     return Ok(());
   }
 
+  let baselines_cache =
+    BenchBaselinesCache::new(factory.caches()?.bench_baselines_db());
+  let baseline = workspace_bench_options
+    .baseline
+    .as_ref()
+    .map(|name| SavedBaseline::load(&baselines_cache, name).map(Arc::new))
+    .transpose()?;
+
   let log_level = cli_options.log_level();
   let worker_factory =
     Arc::new(factory.create_cli_main_worker_factory().await?);
@@ -455,7 +611,11 @@ pub async fn run_benchmarks(
       filter: TestFilter::from_flag(&workspace_bench_options.filter),
       json: workspace_bench_options.json,
       log_level,
+      baseline,
+      save_baseline: workspace_bench_options.save_baseline.clone(),
+      regression_threshold: workspace_bench_options.regression_threshold,
     },
+    baselines_cache,
   )
   .await?;
 
@@ -566,6 +726,14 @@ pub async fn run_benchmarks_with_watch(
           return Ok(());
         }
 
+        let baselines_cache =
+          BenchBaselinesCache::new(factory.caches()?.bench_baselines_db());
+        let baseline = workspace_bench_options
+          .baseline
+          .as_ref()
+          .map(|name| SavedBaseline::load(&baselines_cache, name).map(Arc::new))
+          .transpose()?;
+
         let log_level = cli_options.log_level();
         bench_specifiers(
           worker_factory,
@@ -575,7 +743,11 @@ pub async fn run_benchmarks_with_watch(
             filter: TestFilter::from_flag(&workspace_bench_options.filter),
             json: workspace_bench_options.json,
             log_level,
+            baseline,
+            save_baseline: workspace_bench_options.save_baseline.clone(),
+            regression_threshold: workspace_bench_options.regression_threshold,
           },
+          baselines_cache,
         )
         .await?;
 
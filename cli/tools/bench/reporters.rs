@@ -104,12 +104,17 @@ pub struct ConsoleReporter {
   baseline: bool,
   group_measurements: Vec<(BenchDescription, BenchStats)>,
   options: Option<mitata::reporter::Options>,
+  saved_baseline: Option<Arc<SavedBaseline>>,
 }
 
 impl ConsoleReporter {
-  pub fn new(show_output: bool) -> Self {
+  pub fn new(
+    show_output: bool,
+    saved_baseline: Option<Arc<SavedBaseline>>,
+  ) -> Self {
     Self {
       show_output,
+      saved_baseline,
       group: None,
       options: None,
       baseline: false,
@@ -223,6 +228,24 @@ impl BenchReporter for ConsoleReporter {
           )
         );
 
+        if let Some(saved_baseline) = &self.saved_baseline {
+          if let Some(baseline_stats) = saved_baseline.get(&desc) {
+            let baseline_stats = baseline_stats.mitata_stats();
+            println!(
+              "{}",
+              mitata::reporter::baseline_comparison(
+                &saved_baseline.name,
+                &baseline_stats,
+                &mitata::reporter::compare(
+                  &stats.mitata_stats(),
+                  &baseline_stats
+                ),
+                options
+              )
+            );
+          }
+        }
+
         if !stats.high_precision && stats.used_explicit_timers {
           println!("{}", colors::yellow(format!("Warning: start() and end() calls in \"{}\" are ignored because it averages less\nthan 10µs per iteration. Remove them for better results.", &desc.name)));
         }
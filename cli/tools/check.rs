@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -7,6 +8,7 @@ use std::sync::Arc;
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
 use deno_graph::Module;
 use deno_graph::ModuleGraph;
 use deno_runtime::deno_node::NodeResolver;
@@ -49,6 +51,22 @@ pub struct CheckOptions {
   pub type_check_mode: TypeCheckMode,
 }
 
+/// Prints the result of type checking as a SARIF log for
+/// `deno check --reporter=sarif`, returning the exit code, which is 1 when
+/// there are diagnostics. Other errors are returned as is.
+pub fn report_sarif(result: Result<(), AnyError>) -> Result<i32, AnyError> {
+  let diagnostics = match result {
+    Ok(()) => Diagnostics::default(),
+    Err(err) => err.downcast::<Diagnostics>()?,
+  };
+  let json = serde_json::to_string_pretty(&diagnostics.to_sarif_log())?;
+  #[allow(clippy::print_stdout)]
+  {
+    println!("{}", json);
+  }
+  Ok(if diagnostics.is_empty() { 0 } else { 1 })
+}
+
 pub struct TypeChecker {
   caches: Arc<Caches>,
   cli_options: Arc<CliOptions>,
@@ -202,6 +220,7 @@ impl TypeChecker {
       maybe_tsbuildinfo,
       root_names,
       check_mode: type_check_mode,
+      lint_rules: Vec::new(),
     })?;
 
     let mut diagnostics = response.diagnostics.filter(|d| {
@@ -229,6 +248,58 @@ impl TypeChecker {
     Ok((graph, diagnostics))
   }
 
+  /// Runs type-aware lint rules on the local modules of the module graph,
+  /// returning their diagnostics by module.
+  pub async fn lint(
+    &self,
+    graph: ModuleGraph,
+    lint_rules: Vec<String>,
+  ) -> Result<
+    HashMap<ModuleSpecifier, Vec<tsc::TypeAwareLintDiagnostic>>,
+    AnyError,
+  > {
+    if graph.roots.is_empty() || lint_rules.is_empty() {
+      return Ok(Default::default());
+    }
+
+    // see check_diagnostics
+    if let Some(npm_resolver) = self.npm_resolver.as_managed() {
+      if graph.has_node_specifier {
+        npm_resolver.inject_synthetic_types_node_package().await?;
+      }
+    }
+
+    log::debug!("Type-aware linting.");
+    let ts_config = self
+      .cli_options
+      .resolve_ts_config_for_emit(TsConfigType::Check {
+        lib: self.cli_options.ts_type_lib_window(),
+      })?
+      .ts_config;
+    let hash_data = FastInsecureHasher::new_deno_versioned()
+      .write(&ts_config.as_bytes())
+      .finish();
+    let root_names = get_tsc_roots(&graph, ts_config.get_check_js());
+    let response = tsc::exec(tsc::Request {
+      config: ts_config,
+      debug: self.cli_options.log_level() == Some(log::Level::Debug),
+      graph: Arc::new(graph),
+      hash_data,
+      maybe_npm: Some(tsc::RequestNpmState {
+        node_resolver: self.node_resolver.clone(),
+        npm_resolver: self.npm_resolver.clone(),
+      }),
+      maybe_tsbuildinfo: None,
+      root_names,
+      check_mode: TypeCheckMode::Local,
+      lint_rules,
+    })?;
+
+    log::debug!("{}", response.stats);
+
+    Ok(response.lint_diagnostics)
+  }
+
   fn is_remote_diagnostic(&self, d: &tsc::Diagnostic) -> bool {
     let Some(file_name) = &d.file_name else {
       return false;
@@ -2,6 +2,7 @@
 
 use crate::args::CliOptions;
 use crate::args::CoverageFlags;
+use crate::args::CoverageThresholds;
 use crate::args::FileFlags;
 use crate::args::Flags;
 use crate::cdp;
@@ -38,6 +39,7 @@ use uuid::Uuid;
 mod merge;
 mod range_tree;
 mod reporter;
+mod threshold;
 mod util;
 use merge::ProcessCoverage;
 
@@ -485,6 +487,8 @@ pub async fn cover_files(
   let file_fetcher = factory.file_fetcher()?;
   let cli_options = factory.cli_options();
   let emitter = factory.emitter()?;
+  let thresholds =
+    cli_options.resolve_coverage_thresholds(&coverage_flags.thresholds)?;
 
   assert!(!coverage_flags.files.include.is_empty());
 
@@ -523,6 +527,8 @@ pub async fn cover_files(
   };
 
   let mut reporter = reporter::create(coverage_flags.r#type);
+  // kept to check the thresholds once all files are reported
+  let mut file_reports = Vec::new();
 
   let out_mode = match coverage_flags.output {
     Some(ref path) => match File::create(path) {
@@ -601,10 +607,18 @@ pub async fn cover_files(
 
     if !coverage_report.found_lines.is_empty() {
       reporter.report(&coverage_report, &original_source)?;
+      if thresholds != CoverageThresholds::default() {
+        file_reports.push((coverage_report, original_source.to_string()));
+      }
     }
   }
 
   reporter.done(&coverage_root);
 
+  threshold::check_thresholds(
+    &reporter::collect_summary(&file_reports),
+    &thresholds,
+  )?;
+
   Ok(())
 }
@@ -5,7 +5,10 @@ use super::CoverageReport;
 use crate::args::CoverageType;
 use crate::colors;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use deno_core::url::Url;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
@@ -21,12 +24,14 @@ pub struct CoverageStats<'a> {
   pub line_miss: usize,
   pub branch_hit: usize,
   pub branch_miss: usize,
+  pub function_hit: usize,
+  pub function_miss: usize,
   pub parent: Option<String>,
   pub file_text: Option<String>,
   pub report: Option<&'a CoverageReport>,
 }
 
-type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
+pub type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
 
 pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
   match kind {
@@ -34,6 +39,35 @@ pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
     CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
     CoverageType::Detailed => Box::new(DetailedCoverageReporter::new()),
     CoverageType::Html => Box::new(HtmlCoverageReporter::new()),
+    CoverageType::Cobertura => Box::new(CoberturaCoverageReporter::new()),
+    CoverageType::JsonSummary => Box::new(JsonSummaryCoverageReporter::new()),
+  }
+}
+
+/// Writes a report that is generated at once to the `--output` file, or to
+/// stdout if there is none.
+#[allow(clippy::print_stdout)]
+fn write_report_output(
+  file_reports: &[(CoverageReport, String)],
+  kind: &str,
+  content: &str,
+) {
+  // all the reports share the same output
+  let output = file_reports
+    .first()
+    .and_then(|(report, _)| report.output.as_ref());
+  match output {
+    Some(path) => {
+      if let Err(err) = fs::write(path, content) {
+        log::error!(
+          "Failed to write {} coverage report to {}: {}",
+          kind,
+          path.display(),
+          err
+        );
+      }
+    }
+    None => println!("{}", content),
   }
 }
 
@@ -45,63 +79,71 @@ pub trait CoverageReporter {
   ) -> Result<(), AnyError>;
 
   fn done(&mut self, _coverage_root: &Path) {}
+}
 
-  /// Collects the coverage summary of each file or directory.
-  fn collect_summary<'a>(
-    &'a self,
-    file_reports: &'a Vec<(CoverageReport, String)>,
-  ) -> CoverageSummary {
-    let urls = file_reports.iter().map(|rep| &rep.0.url).collect();
-    let root = match util::find_root(urls)
-      .and_then(|root_path| root_path.to_file_path().ok())
-    {
-      Some(path) => path,
-      None => return HashMap::new(),
-    };
-    // summary by file or directory
-    // tuple of (line hit, line miss, branch hit, branch miss, parent)
-    let mut summary = HashMap::new();
-    summary.insert("".to_string(), CoverageStats::default()); // root entry
-    for (report, file_text) in file_reports {
-      let path = report.url.to_file_path().unwrap();
-      let relative_path = path.strip_prefix(&root).unwrap();
-      let mut file_text = Some(file_text.to_string());
-
-      let mut summary_path = Some(relative_path);
-      // From leaf to root, adds up the coverage stats
-      while let Some(path) = summary_path {
-        let path_str = path.to_str().unwrap().to_string();
-        let parent = path
-          .parent()
-          .and_then(|p| p.to_str())
-          .map(|p| p.to_string());
-        let stats = summary.entry(path_str).or_insert(CoverageStats {
-          parent,
-          file_text,
-          report: Some(report),
-          ..CoverageStats::default()
-        });
-
-        stats.line_hit += report
-          .found_lines
-          .iter()
-          .filter(|(_, count)| *count > 0)
-          .count();
-        stats.line_miss += report
-          .found_lines
-          .iter()
-          .filter(|(_, count)| *count == 0)
-          .count();
-        stats.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
-        stats.branch_miss +=
-          report.branches.iter().filter(|b| !b.is_hit).count();
-
-        file_text = None;
-        summary_path = path.parent();
-      }
+/// Collects the coverage summary of each file or directory.
+pub fn collect_summary(
+  file_reports: &[(CoverageReport, String)],
+) -> CoverageSummary {
+  let urls = file_reports.iter().map(|rep| &rep.0.url).collect();
+  let root = match util::find_root(urls)
+    .and_then(|root_path| root_path.to_file_path().ok())
+  {
+    Some(path) => path,
+    None => return HashMap::new(),
+  };
+  // summary by file or directory
+  // tuple of (line hit, line miss, branch hit, branch miss, parent)
+  let mut summary = HashMap::new();
+  summary.insert("".to_string(), CoverageStats::default()); // root entry
+  for (report, file_text) in file_reports {
+    let path = report.url.to_file_path().unwrap();
+    let relative_path = path.strip_prefix(&root).unwrap();
+    let mut file_text = Some(file_text.to_string());
+
+    let mut summary_path = Some(relative_path);
+    // From leaf to root, adds up the coverage stats
+    while let Some(path) = summary_path {
+      let path_str = path.to_str().unwrap().to_string();
+      let parent = path
+        .parent()
+        .and_then(|p| p.to_str())
+        .map(|p| p.to_string());
+      let stats = summary.entry(path_str).or_insert(CoverageStats {
+        parent,
+        file_text,
+        report: Some(report),
+        ..CoverageStats::default()
+      });
+
+      stats.line_hit += report
+        .found_lines
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .count();
+      stats.line_miss += report
+        .found_lines
+        .iter()
+        .filter(|(_, count)| *count == 0)
+        .count();
+      stats.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
+      stats.branch_miss += report.branches.iter().filter(|b| !b.is_hit).count();
+      stats.function_hit += report
+        .named_functions
+        .iter()
+        .filter(|f| f.execution_count > 0)
+        .count();
+      stats.function_miss += report
+        .named_functions
+        .iter()
+        .filter(|f| f.execution_count == 0)
+        .count();
+
+      file_text = None;
+      summary_path = path.parent();
     }
-    summary
   }
+  summary
 }
 
 struct SummaryCoverageReporter {
@@ -127,12 +169,16 @@ impl SummaryCoverageReporter {
       line_miss,
       branch_hit,
       branch_miss,
+      function_hit,
+      function_miss,
       ..
     } = stats;
     let (_, line_percent, line_class) =
       util::calc_coverage_display_info(*line_hit, *line_miss);
     let (_, branch_percent, branch_class) =
       util::calc_coverage_display_info(*branch_hit, *branch_miss);
+    let (_, function_percent, function_class) =
+      util::calc_coverage_display_info(*function_hit, *function_miss);
 
     let file_name = format!(
       "{node:node_max$}",
@@ -155,6 +201,14 @@ impl SummaryCoverageReporter {
       format!("{}", colors::red(&format!("{:>8.1}", branch_percent)))
     };
 
+    let function_percent = if function_class == "high" {
+      format!("{}", colors::green(&format!("{:>10.1}", function_percent)))
+    } else if function_class == "medium" {
+      format!("{}", colors::yellow(&format!("{:>10.1}", function_percent)))
+    } else {
+      format!("{}", colors::red(&format!("{:>10.1}", function_percent)))
+    };
+
     let line_percent = if line_class == "high" {
       format!("{}", colors::green(&format!("{:>6.1}", line_percent)))
     } else if line_class == "medium" {
@@ -164,9 +218,10 @@ impl SummaryCoverageReporter {
     };
 
     println!(
-      " {file_name} | {branch_percent} | {line_percent} |",
+      " {file_name} | {branch_percent} | {function_percent} | {line_percent} |",
       file_name = file_name,
       branch_percent = branch_percent,
+      function_percent = function_percent,
       line_percent = line_percent,
     );
   }
@@ -186,7 +241,7 @@ impl CoverageReporter for SummaryCoverageReporter {
   }
 
   fn done(&mut self, _coverage_root: &Path) {
-    let summary = self.collect_summary(&self.file_reports);
+    let summary = collect_summary(&self.file_reports);
     let root_stats = summary.get("").unwrap();
 
     let mut entries = summary
@@ -196,8 +251,10 @@ impl CoverageReporter for SummaryCoverageReporter {
     entries.sort_by_key(|(node, _)| node.to_owned());
     let node_max = entries.iter().map(|(node, _)| node.len()).max().unwrap();
 
-    let header =
-      format!("{node:node_max$}  | Branch % | Line % |", node = "File");
+    let header = format!(
+      "{node:node_max$}  | Branch % | Function % | Line % |",
+      node = "File"
+    );
     let separator = "-".repeat(header.len());
     println!("{}", separator);
     println!("{}", header);
@@ -398,7 +455,7 @@ impl CoverageReporter for HtmlCoverageReporter {
   }
 
   fn done(&mut self, coverage_root: &Path) {
-    let summary = self.collect_summary(&self.file_reports);
+    let summary = collect_summary(&self.file_reports);
     let now = chrono::Utc::now().to_rfc2822();
 
     for (node, stats) in &summary {
@@ -409,6 +466,10 @@ impl CoverageReporter for HtmlCoverageReporter {
       } else {
         self.create_html_summary_table(node, &summary)
       };
+      let main_content = format!(
+        "{main_content}{}",
+        self.create_html_uncovered_functions(node, &summary)
+      );
       let is_dir = stats.file_text.is_none();
       let html = self.create_html(node, is_dir, stats, &now, &main_content);
       fs::create_dir_all(report_path.parent().unwrap()).unwrap();
@@ -521,12 +582,16 @@ impl HtmlCoverageReporter {
       line_miss,
       branch_hit,
       branch_miss,
+      function_hit,
+      function_miss,
       ..
     } = stats;
     let (line_total, line_percent, line_class) =
       util::calc_coverage_display_info(*line_hit, *line_miss);
     let (branch_total, branch_percent, _) =
       util::calc_coverage_display_info(*branch_hit, *branch_miss);
+    let (function_total, function_percent, _) =
+      util::calc_coverage_display_info(*function_hit, *function_miss);
 
     format!(
       "
@@ -538,6 +603,11 @@ impl HtmlCoverageReporter {
             <span class='quiet'>Branches</span>
             <span class='fraction'>{branch_hit}/{branch_total}</span>
           </div>
+          <div class='fl pad1y space-right2'>
+            <span class='strong'>{function_percent:.2}%</span>
+            <span class='quiet'>Functions</span>
+            <span class='fraction'>{function_hit}/{function_total}</span>
+          </div>
           <div class='fl pad1y space-right2'>
             <span class='strong'>{line_percent:.2}%</span>
             <span class='quiet'>Lines</span>
@@ -577,13 +647,15 @@ impl HtmlCoverageReporter {
     children.sort();
 
     let table_rows: Vec<String> = children.iter().map(|(is_file, c)| {
-    let CoverageStats { line_hit, line_miss, branch_hit, branch_miss, .. } =
+    let CoverageStats { line_hit, line_miss, branch_hit, branch_miss, function_hit, function_miss, .. } =
       summary.get(c).unwrap();
 
     let (line_total, line_percent, line_class) =
       util::calc_coverage_display_info(*line_hit, *line_miss);
     let (branch_total, branch_percent, branch_class) =
       util::calc_coverage_display_info(*branch_hit, *branch_miss);
+    let (function_total, function_percent, function_class) =
+      util::calc_coverage_display_info(*function_hit, *function_miss);
 
     let path = Path::new(c.strip_prefix(&format!("{node}{}", std::path::MAIN_SEPARATOR)).unwrap_or(c)).to_str().unwrap();
     let path = path.replace(std::path::MAIN_SEPARATOR, "/");
@@ -600,6 +672,8 @@ impl HtmlCoverageReporter {
         </td>
         <td class='pct {branch_class}'>{branch_percent:.2}%</td>
         <td class='abs {branch_class}'>{branch_hit}/{branch_total}</td>
+        <td class='pct {function_class}'>{function_percent:.2}%</td>
+        <td class='abs {function_class}'>{function_hit}/{function_total}</td>
         <td class='pct {line_class}'>{line_percent:.2}%</td>
         <td class='abs {line_class}'>{line_hit}/{line_total}</td>
       </tr>")}).collect();
@@ -614,6 +688,8 @@ impl HtmlCoverageReporter {
             <th class='pic'></th>
             <th class='pct'>Branches</th>
             <th class='abs'></th>
+            <th class='pct'>Functions</th>
+            <th class='abs'></th>
             <th class='pct'>Lines</th>
             <th class='abs'></th>
           </tr>
@@ -625,6 +701,77 @@ impl HtmlCoverageReporter {
     )
   }
 
+  /// Creates the list of functions that were never called, in the file or in
+  /// all the files of the directory.
+  pub fn create_html_uncovered_functions(
+    &self,
+    node: &str,
+    summary: &CoverageSummary,
+  ) -> String {
+    let Some(stats) = summary.get(node) else {
+      return "".to_string();
+    };
+    let is_dir = stats.file_text.is_none();
+    let dir_prefix = format!("{node}{}", std::path::MAIN_SEPARATOR);
+    let mut files = summary
+      .iter()
+      .filter(|(path, stats)| {
+        stats.file_text.is_some()
+          && (path.as_str() == node
+            || node.is_empty()
+            || path.starts_with(&dir_prefix))
+      })
+      .collect::<Vec<_>>();
+    files.sort_by_key(|(path, _)| path.to_owned());
+
+    let items = files
+      .into_iter()
+      .flat_map(|(path, stats)| {
+        let report = stats.report.unwrap();
+        // links are relative to the page of the file or directory
+        let file_link = if is_dir {
+          let path = path.strip_prefix(&dir_prefix).unwrap_or(path);
+          format!("{}.html", path.replace(std::path::MAIN_SEPARATOR, "/"))
+        } else {
+          "".to_string()
+        };
+        let file_label = path.replace(std::path::MAIN_SEPARATOR, "/");
+        report
+          .named_functions
+          .iter()
+          .filter(|f| f.execution_count == 0)
+          .map(move |f| {
+            let line = f.line_index + 1;
+            let name = f
+              .name
+              .replace('&', "&amp;")
+              .replace('<', "&lt;")
+              .replace('>', "&gt;");
+            if is_dir {
+              format!("<li><a href='{file_link}#L{line}'>{name}</a> <span class='quiet'>{file_label}:{line}</span></li>")
+            } else {
+              format!("<li><a href='#L{line}'>{name}</a> <span class='quiet'>line {line}</span></li>")
+            }
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    if items.is_empty() {
+      return "".to_string();
+    }
+
+    let items = items.join("\n");
+    format!(
+      "
+      <div class='uncovered-functions pad1y'>
+        <h2>Uncovered functions</h2>
+        <ul>
+          {items}
+        </ul>
+      </div>"
+    )
+  }
+
   /// Creates <table> of single file code coverage.
   pub fn create_html_code_table(
     &self,
@@ -682,3 +829,261 @@ impl HtmlCoverageReporter {
     )
   }
 }
+
+struct CoberturaCoverageReporter {
+  file_reports: Vec<(CoverageReport, String)>,
+}
+
+impl CoberturaCoverageReporter {
+  pub fn new() -> CoberturaCoverageReporter {
+    CoberturaCoverageReporter {
+      file_reports: Vec::new(),
+    }
+  }
+
+  fn rate(hit: usize, miss: usize) -> String {
+    let total = hit + miss;
+    let rate = if total == 0 {
+      1.0
+    } else {
+      hit as f64 / total as f64
+    };
+    format!("{:.4}", rate)
+  }
+
+  fn escape(text: &str) -> String {
+    text
+      .replace('&', "&amp;")
+      .replace('<', "&lt;")
+      .replace('>', "&gt;")
+      .replace('"', "&quot;")
+      .replace('\'', "&apos;")
+  }
+
+  /// Creates the <class> element of a single file.
+  fn create_class(&self, node: &str, stats: &CoverageStats) -> String {
+    let report = stats.report.unwrap();
+    let filename = node.replace('\\', "/");
+    let name = Path::new(node)
+      .file_name()
+      .and_then(|name| name.to_str())
+      .unwrap_or(node);
+
+    let methods = report
+      .named_functions
+      .iter()
+      .map(|function| {
+        let line_rate = if function.execution_count > 0 { 1 } else { 0 };
+        format!(
+          "            <method name=\"{}\" signature=\"\" line-rate=\"{line_rate}\" branch-rate=\"0\" complexity=\"0\">
+              <lines>
+                <line number=\"{}\" hits=\"{}\"/>
+              </lines>
+            </method>\n",
+          Self::escape(&function.name),
+          function.line_index + 1,
+          function.execution_count,
+        )
+      })
+      .collect::<String>();
+
+    let lines = report
+      .found_lines
+      .iter()
+      .map(|(index, count)| {
+        let (branch_hit, branch_total) = report
+          .branches
+          .iter()
+          .filter(|b| b.line_index == *index)
+          .fold((0, 0), |(hit, total), b| {
+            (hit + usize::from(b.is_hit), total + 1)
+          });
+        if branch_total == 0 {
+          format!(
+            "            <line number=\"{}\" hits=\"{count}\" branch=\"false\"/>\n",
+            index + 1
+          )
+        } else {
+          format!(
+            "            <line number=\"{}\" hits=\"{count}\" branch=\"true\" condition-coverage=\"{}% ({branch_hit}/{branch_total})\"/>\n",
+            index + 1,
+            branch_hit * 100 / branch_total,
+          )
+        }
+      })
+      .collect::<String>();
+
+    format!(
+      "        <class name=\"{}\" filename=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">
+          <methods>
+{methods}          </methods>
+          <lines>
+{lines}          </lines>
+        </class>\n",
+      Self::escape(name),
+      Self::escape(&filename),
+      Self::rate(stats.line_hit, stats.line_miss),
+      Self::rate(stats.branch_hit, stats.branch_miss),
+    )
+  }
+
+  /// Creates the report with a <package> for each directory that has files.
+  fn create_xml(&self, summary: &CoverageSummary) -> String {
+    let urls = self.file_reports.iter().map(|rep| &rep.0.url).collect();
+    let source = util::find_root(urls)
+      .and_then(|root| root.to_file_path().ok())
+      .map(|path| path.to_string_lossy().to_string())
+      .unwrap_or_default();
+
+    let mut packages: BTreeMap<&str, Vec<(&String, &CoverageStats)>> =
+      BTreeMap::new();
+    for (node, stats) in summary {
+      if stats.file_text.is_some() {
+        let parent = stats.parent.as_deref().unwrap_or_default();
+        packages.entry(parent).or_default().push((node, stats));
+      }
+    }
+
+    let packages = packages
+      .into_iter()
+      .map(|(package, mut files)| {
+        files.sort_by_key(|(node, _)| node.to_owned());
+        let package_stats = summary.get(package).unwrap();
+        let name = if package.is_empty() {
+          ".".to_string()
+        } else {
+          package.replace('\\', "/")
+        };
+        let classes = files
+          .iter()
+          .map(|(node, stats)| self.create_class(node, stats))
+          .collect::<String>();
+        format!(
+          "    <package name=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">
+      <classes>
+{classes}      </classes>
+    </package>\n",
+          Self::escape(&name),
+          Self::rate(package_stats.line_hit, package_stats.line_miss),
+          Self::rate(package_stats.branch_hit, package_stats.branch_miss),
+        )
+      })
+      .collect::<String>();
+
+    let root_stats = summary.get("").unwrap();
+    format!(
+      "<?xml version=\"1.0\" ?>
+<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">
+<coverage lines-valid=\"{}\" lines-covered=\"{}\" line-rate=\"{}\" branches-valid=\"{}\" branches-covered=\"{}\" branch-rate=\"{}\" timestamp=\"{}\" complexity=\"0\" version=\"{}\">
+  <sources>
+    <source>{}</source>
+  </sources>
+  <packages>
+{packages}  </packages>
+</coverage>",
+      root_stats.line_hit + root_stats.line_miss,
+      root_stats.line_hit,
+      Self::rate(root_stats.line_hit, root_stats.line_miss),
+      root_stats.branch_hit + root_stats.branch_miss,
+      root_stats.branch_hit,
+      Self::rate(root_stats.branch_hit, root_stats.branch_miss),
+      chrono::Utc::now().timestamp_millis(),
+      env!("CARGO_PKG_VERSION"),
+      Self::escape(&source),
+    )
+  }
+}
+
+impl CoverageReporter for CoberturaCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    file_text: &str,
+  ) -> Result<(), AnyError> {
+    self
+      .file_reports
+      .push((coverage_report.clone(), file_text.to_string()));
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) {
+    let summary = collect_summary(&self.file_reports);
+    if summary.is_empty() {
+      return;
+    }
+    let xml = self.create_xml(&summary);
+    write_report_output(&self.file_reports, "Cobertura", &xml);
+  }
+}
+
+struct JsonSummaryCoverageReporter {
+  file_reports: Vec<(CoverageReport, String)>,
+}
+
+impl JsonSummaryCoverageReporter {
+  pub fn new() -> JsonSummaryCoverageReporter {
+    JsonSummaryCoverageReporter {
+      file_reports: Vec::new(),
+    }
+  }
+
+  fn counts(hit: usize, miss: usize) -> serde_json::Value {
+    let (total, percent, _) = util::calc_coverage_display_info(hit, miss);
+    json!({
+      "total": total,
+      "covered": hit,
+      "pct": (percent * 100.0).round() / 100.0,
+    })
+  }
+
+  fn stats_json(stats: &CoverageStats) -> serde_json::Value {
+    json!({
+      "lines": Self::counts(stats.line_hit, stats.line_miss),
+      "branches": Self::counts(stats.branch_hit, stats.branch_miss),
+      "functions": Self::counts(stats.function_hit, stats.function_miss),
+    })
+  }
+}
+
+impl CoverageReporter for JsonSummaryCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    file_text: &str,
+  ) -> Result<(), AnyError> {
+    self
+      .file_reports
+      .push((coverage_report.clone(), file_text.to_string()));
+    Ok(())
+  }
+
+  fn done(&mut self, _coverage_root: &Path) {
+    let summary = collect_summary(&self.file_reports);
+    let Some(root_stats) = summary.get("") else {
+      return;
+    };
+
+    let mut files = summary
+      .values()
+      .filter_map(|stats| {
+        let report = stats.report?;
+        stats.file_text.as_ref()?;
+        let path = report
+          .url
+          .to_file_path()
+          .ok()
+          .and_then(|p| p.to_str().map(|p| p.to_string()))
+          .unwrap_or_else(|| report.url.to_string());
+        Some((path, Self::stats_json(stats)))
+      })
+      .collect::<Vec<_>>();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // same shape as the istanbul "json-summary" report
+    let mut json = serde_json::Map::new();
+    json.insert("total".to_string(), Self::stats_json(root_stats));
+    json.extend(files);
+    let json = serde_json::to_string_pretty(&json).unwrap();
+    write_report_output(&self.file_reports, "JSON summary", &json);
+  }
+}
@@ -0,0 +1,144 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::reporter::CoverageStats;
+use super::reporter::CoverageSummary;
+use super::util;
+use crate::args::CoverageThresholds;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+
+/// Checks the coverage of all files together, and of each file with
+/// `per_file`, against the thresholds. Errors with the ones that are below.
+pub fn check_thresholds(
+  summary: &CoverageSummary,
+  thresholds: &CoverageThresholds,
+) -> Result<(), AnyError> {
+  let Some(root_stats) = summary.get("") else {
+    return Ok(());
+  };
+
+  let mut failures = stats_failures("All files", root_stats, thresholds);
+  if thresholds.per_file {
+    let mut entries = summary
+      .iter()
+      .filter(|(_, stats)| stats.file_text.is_some())
+      .collect::<Vec<_>>();
+    entries.sort_by_key(|(node, _)| node.to_owned());
+    for (node, stats) in entries {
+      failures.extend(stats_failures(
+        &node.replace('\\', "/"),
+        stats,
+        thresholds,
+      ));
+    }
+  }
+
+  if failures.is_empty() {
+    return Ok(());
+  }
+  Err(generic_error(format!(
+    "Coverage thresholds not met:\n{}",
+    failures.join("\n")
+  )))
+}
+
+fn stats_failures(
+  name: &str,
+  stats: &CoverageStats,
+  thresholds: &CoverageThresholds,
+) -> Vec<String> {
+  [
+    ("lines", thresholds.lines, stats.line_hit, stats.line_miss),
+    (
+      "branches",
+      thresholds.branches,
+      stats.branch_hit,
+      stats.branch_miss,
+    ),
+    (
+      "functions",
+      thresholds.functions,
+      stats.function_hit,
+      stats.function_miss,
+    ),
+  ]
+  .into_iter()
+  .filter_map(|(kind, threshold, hit, miss)| {
+    let threshold = threshold?;
+    let (_, percent, _) = util::calc_coverage_display_info(hit, miss);
+    (percent < threshold as f32)
+      .then(|| format!("  {name}: {kind} {percent:.1}% is below {threshold}%"))
+  })
+  .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  fn file_stats(line_hit: usize, line_miss: usize) -> CoverageStats<'static> {
+    CoverageStats {
+      line_hit,
+      line_miss,
+      branch_hit: 1,
+      function_miss: 1,
+      parent: Some("".to_string()),
+      file_text: Some("".to_string()),
+      ..CoverageStats::default()
+    }
+  }
+
+  #[test]
+  fn test_check_thresholds() {
+    let summary: CoverageSummary = HashMap::from([
+      (
+        "".to_string(),
+        CoverageStats {
+          line_hit: 12,
+          line_miss: 8,
+          branch_hit: 2,
+          function_miss: 2,
+          ..CoverageStats::default()
+        },
+      ),
+      ("a.ts".to_string(), file_stats(9, 1)),
+      ("b.ts".to_string(), file_stats(3, 7)),
+    ]);
+
+    let thresholds = CoverageThresholds {
+      lines: Some(60),
+      branches: Some(100),
+      ..Default::default()
+    };
+    assert!(check_thresholds(&summary, &thresholds).is_ok());
+
+    let thresholds = CoverageThresholds {
+      lines: Some(60),
+      per_file: true,
+      ..Default::default()
+    };
+    assert_eq!(
+      check_thresholds(&summary, &thresholds)
+        .unwrap_err()
+        .to_string(),
+      "Coverage thresholds not met:\n  b.ts: lines 30.0% is below 60%"
+    );
+
+    let thresholds = CoverageThresholds {
+      lines: Some(61),
+      functions: Some(50),
+      ..Default::default()
+    };
+    assert_eq!(
+      check_thresholds(&summary, &thresholds)
+        .unwrap_err()
+        .to_string(),
+      concat!(
+        "Coverage thresholds not met:\n",
+        "  All files: lines 60.0% is below 61%\n",
+        "  All files: functions 0.0% is below 50%",
+      )
+    );
+  }
+}
@@ -10,6 +10,7 @@
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::FmtFlags;
+use crate::args::FmtLanguagesConfig;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
 use crate::args::ProseWrap;
@@ -17,12 +18,20 @@ use crate::cache::Caches;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::util::diff::diff;
+use crate::util::diff::diff_lines;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
+use crate::util::git::GitChangedFiles;
 use crate::util::path::get_extension;
 use async_trait::async_trait;
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
 use deno_ast::ParsedSource;
+use deno_ast::SourceRanged;
 use deno_config::glob::FilePatterns;
 use deno_core::anyhow::anyhow;
 use deno_core::anyhow::bail;
@@ -35,11 +44,13 @@ use deno_core::unsync::spawn_blocking;
 use log::debug;
 use log::info;
 use log::warn;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
 use std::io::Read;
 use std::io::Write;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
@@ -76,8 +87,13 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
           let factory = CliFactory::from_flags(flags)?;
           let cli_options = factory.cli_options();
           let caches = factory.caches()?;
+          let changed_files = resolve_changed_files(cli_options, &fmt_flags)?;
           let mut paths_with_options_batches =
-            resolve_paths_with_options_batches(cli_options, &fmt_flags)?;
+            resolve_paths_with_options_batches(
+              cli_options,
+              &fmt_flags,
+              changed_files.as_deref(),
+            )?;
 
           for paths_with_options in &mut paths_with_options_batches {
             let _ = watcher_communicator
@@ -110,7 +126,13 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
             };
           }
 
-          format_files(caches, &fmt_flags, paths_with_options_batches).await?;
+          format_files(
+            caches,
+            &fmt_flags,
+            paths_with_options_batches,
+            changed_files,
+          )
+          .await?;
 
           Ok(())
         })
@@ -121,9 +143,19 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
     let factory = CliFactory::from_flags(flags)?;
     let caches = factory.caches()?;
     let cli_options = factory.cli_options();
-    let paths_with_options_batches =
-      resolve_paths_with_options_batches(cli_options, &fmt_flags)?;
-    format_files(caches, &fmt_flags, paths_with_options_batches).await?;
+    let changed_files = resolve_changed_files(cli_options, &fmt_flags)?;
+    let paths_with_options_batches = resolve_paths_with_options_batches(
+      cli_options,
+      &fmt_flags,
+      changed_files.as_deref(),
+    )?;
+    format_files(
+      caches,
+      &fmt_flags,
+      paths_with_options_batches,
+      changed_files,
+    )
+    .await?;
   }
 
   Ok(())
@@ -135,17 +167,33 @@ struct PathsWithOptions {
   options: FmtOptions,
 }
 
+fn resolve_changed_files(
+  cli_options: &CliOptions,
+  fmt_flags: &FmtFlags,
+) -> Result<Option<Arc<GitChangedFiles>>, AnyError> {
+  let Some(git_ref) = &fmt_flags.changed else {
+    return Ok(None);
+  };
+  let changed_files =
+    GitChangedFiles::resolve(cli_options.initial_cwd(), git_ref)?;
+  Ok(Some(Arc::new(changed_files)))
+}
+
 fn resolve_paths_with_options_batches(
   cli_options: &CliOptions,
   fmt_flags: &FmtFlags,
+  changed_files: Option<&GitChangedFiles>,
 ) -> Result<Vec<PathsWithOptions>, AnyError> {
   let members_fmt_options =
     cli_options.resolve_fmt_options_for_members(fmt_flags)?;
   let mut paths_with_options_batches =
     Vec::with_capacity(members_fmt_options.len());
   for member_fmt_options in members_fmt_options {
-    let files =
+    let mut files =
       collect_fmt_files(cli_options, member_fmt_options.files.clone())?;
+    if let Some(changed_files) = changed_files {
+      files.retain(|file| changed_files.contains(file));
+    }
     if !files.is_empty() {
       paths_with_options_batches.push(PathsWithOptions {
         base: member_fmt_options.files.base.clone(),
@@ -154,7 +202,8 @@ fn resolve_paths_with_options_batches(
       });
     }
   }
-  if paths_with_options_batches.is_empty() {
+  // not having changed files is fine
+  if paths_with_options_batches.is_empty() && changed_files.is_none() {
     return Err(generic_error("No target files found."));
   }
   Ok(paths_with_options_batches)
@@ -164,7 +213,9 @@ async fn format_files(
   caches: &Arc<Caches>,
   fmt_flags: &FmtFlags,
   paths_with_options_batches: Vec<PathsWithOptions>,
+  changed_files: Option<Arc<GitChangedFiles>>,
 ) -> Result<(), AnyError> {
+  let changed_files = changed_files.filter(|_| fmt_flags.changed_lines);
   let formatter: Box<dyn Formatter> = if fmt_flags.check {
     Box::new(CheckFormatter::default())
   } else {
@@ -180,11 +231,17 @@ async fn format_files(
     let paths = paths_with_options.paths;
     let incremental_cache = Arc::new(IncrementalCache::new(
       caches.fmt_incremental_cache_db(),
-      &fmt_options.options,
+      &(&fmt_options.options, &fmt_options.languages),
       &paths,
     ));
     formatter
-      .handle_files(paths, fmt_options.options, incremental_cache.clone())
+      .handle_files(
+        paths,
+        fmt_options.options,
+        fmt_options.languages,
+        changed_files.clone(),
+        incremental_cache.clone(),
+      )
       .await?;
     incremental_cache.wait_completion().await;
   }
@@ -204,10 +261,11 @@ fn collect_fmt_files(
 }
 
 /// Formats markdown (using <https://github.com/dprint/dprint-plugin-markdown>) and its code blocks
-/// (ts/tsx, js/jsx).
+/// (ts/tsx, js/jsx, json, css, html, yaml, sql).
 fn format_markdown(
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
 ) -> Result<Option<String>, AnyError> {
   let markdown_config = get_resolved_markdown_config(fmt_options);
   dprint_plugin_markdown::format_text(
@@ -229,6 +287,13 @@ fn format_markdown(
           | "typescript"
           | "json"
           | "jsonc"
+          | "css"
+          | "scss"
+          | "less"
+          | "html"
+          | "yaml"
+          | "yml"
+          | "sql"
       ) {
         // It's important to tell dprint proper file extension, otherwise
         // it might parse the file twice.
@@ -240,7 +305,15 @@ fn format_markdown(
 
         let fake_filename =
           PathBuf::from(format!("deno_fmt_stdin.{extension}"));
-        if matches!(extension, "json" | "jsonc") {
+        if matches!(
+          extension,
+          "css" | "scss" | "less" | "html" | "yaml" | "yml" | "sql"
+        ) {
+          let mut fmt_options = fmt_options.clone();
+          fmt_options.line_width = Some(line_width);
+          let languages = languages.without_line_widths();
+          format_file(&fake_filename, text, &fmt_options, &languages)
+        } else if matches!(extension, "json" | "jsonc") {
           let mut json_config = get_resolved_json_config(fmt_options);
           json_config.line_width = line_width;
           dprint_plugin_json::format_text(&fake_filename, text, &json_config)
@@ -273,52 +346,501 @@ pub fn format_json(
   dprint_plugin_json::format_text(file_path, file_text, &config)
 }
 
-/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, or IPYNB file.
+/// Formats CSS, SCSS and Less using <https://github.com/g-plane/malva>.
+pub fn format_css(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let syntax = match get_extension(file_path).as_deref() {
+    Some("scss") => malva::Syntax::Scss,
+    Some("less") => malva::Syntax::Less,
+    _ => malva::Syntax::Css,
+  };
+  let formatted_text = malva::format_text(
+    file_text,
+    syntax,
+    &get_resolved_malva_config(fmt_options),
+  )
+  .map_err(|err| anyhow!("{}", err))?;
+  Ok(changed_text(file_text, formatted_text))
+}
+
+/// Formats HTML using <https://github.com/g-plane/markup_fmt>, along with
+/// the scripts and styles in it.
+pub fn format_html(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
+) -> Result<Option<String>, AnyError> {
+  let html_options = languages.html.apply(fmt_options);
+  let formatted_text = markup_fmt::format_text(
+    file_text,
+    markup_fmt::Language::Html,
+    &get_resolved_markup_fmt_config(&html_options),
+    |text, hints| {
+      let fake_filename =
+        PathBuf::from(format!("deno_fmt_stdin.{}", hints.ext));
+      let mut fmt_options = fmt_options.clone();
+      fmt_options.line_width = Some(hints.print_width as u32);
+      // attribute values are double quoted
+      if hints.attr {
+        fmt_options.single_quote = Some(true);
+      }
+      let languages = languages.without_line_widths();
+      format_file(&fake_filename, text, &fmt_options, &languages).map(
+        |formatted_text| {
+          formatted_text
+            .map(Cow::Owned)
+            .unwrap_or(Cow::Borrowed(text))
+        },
+      )
+    },
+  )
+  .map_err(|err| match err {
+    markup_fmt::FormatError::Syntax(err) => anyhow!("{}", err),
+    markup_fmt::FormatError::External(errors) => anyhow!(
+      "{}",
+      errors
+        .iter()
+        .map(|err| format!("{:#}", err))
+        .collect::<Vec<_>>()
+        .join("\n")
+    ),
+  })?;
+  Ok(changed_text(file_text, formatted_text))
+}
+
+/// Formats YAML using <https://github.com/g-plane/pretty_yaml>.
+pub fn format_yaml(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted_text =
+    pretty_yaml::format_text(file_text, &get_resolved_yaml_config(fmt_options))
+      .map_err(|err| anyhow!("{}", err))?;
+  Ok(changed_text(file_text, formatted_text))
+}
+
+/// Formats SQL using <https://github.com/shssoichiro/sqlformat-rs>.
+pub fn format_sql(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
+) -> Result<Option<String>, AnyError> {
+  let sql_options = &languages.sql;
+  let use_tabs = sql_options
+    .use_tabs
+    .or(fmt_options.use_tabs)
+    .unwrap_or(false);
+  let indent_width = sql_options
+    .indent_width
+    .or(fmt_options.indent_width)
+    .unwrap_or(2);
+  let mut formatted_text = sqlformat::format(
+    file_text,
+    &sqlformat::QueryParams::None,
+    sqlformat::FormatOptions {
+      indent: if use_tabs {
+        sqlformat::Indent::Tabs
+      } else {
+        sqlformat::Indent::Spaces(indent_width)
+      },
+      uppercase: sql_options.uppercase.unwrap_or(false),
+      lines_between_queries: 2,
+      ..Default::default()
+    },
+  );
+  // sqlformat doesn't end the text with a new line
+  if !formatted_text.is_empty() {
+    formatted_text.push('\n');
+  }
+  Ok(changed_text(file_text, formatted_text))
+}
+
+/// Formats GraphQL using <https://github.com/g-plane/pretty_graphql>.
+pub fn format_graphql(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted_text = pretty_graphql::format_text(
+    file_text,
+    &get_resolved_graphql_config(fmt_options),
+  )
+  .map_err(|err| anyhow!("{}", err))?;
+  Ok(changed_text(file_text, formatted_text))
+}
+
+fn changed_text(file_text: &str, formatted_text: String) -> Option<String> {
+  if formatted_text == file_text {
+    None
+  } else {
+    Some(formatted_text)
+  }
+}
+
+/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, MD, IPYNB, CSS, SCSS,
+/// LESS, HTML, YAML or SQL file.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
 ) -> Result<Option<String>, AnyError> {
   let ext = get_extension(file_path).unwrap_or_default();
 
   match ext.as_str() {
     "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown" => {
-      format_markdown(file_text, fmt_options)
+      format_markdown(file_text, fmt_options, languages)
     }
     "json" | "jsonc" => format_json(file_path, file_text, fmt_options),
+    "css" | "scss" | "less" => {
+      format_css(file_path, file_text, &languages.css.apply(fmt_options))
+    }
+    "html" => format_html(file_text, fmt_options, languages),
+    "yaml" | "yml" => {
+      format_yaml(file_text, &languages.yaml.apply(fmt_options))
+    }
+    "sql" => format_sql(file_text, fmt_options, languages),
     "ipynb" => dprint_plugin_jupyter::format_text(
       file_text,
       |file_path: &Path, file_text: String| {
-        format_file(file_path, &file_text, fmt_options)
+        format_file(file_path, &file_text, fmt_options, languages)
       },
     ),
     _ => {
       let config = get_resolved_typescript_config(fmt_options);
-      dprint_plugin_typescript::format_text(
+      let formatted_text = dprint_plugin_typescript::format_text(
         file_path,
         file_text.to_string(),
         &config,
+      )?;
+      if !languages.embedded_templates.unwrap_or(false) {
+        return Ok(formatted_text);
+      }
+      let specifier = ModuleSpecifier::from_file_path(file_path)
+        .unwrap_or_else(|_| {
+          ModuleSpecifier::parse("file:///deno_fmt_stdin.ts").unwrap()
+        });
+      format_with_embedded_templates(
+        &specifier,
+        MediaType::from_path(file_path),
+        file_text,
+        formatted_text,
+        fmt_options,
+        languages,
       )
     }
   }
 }
 
+/// Formats only the given 0-indexed line ranges of a file, leaving the rest
+/// of the file as it is.
+///
+/// The formatters can't format only part of a file, so the whole file is
+/// formatted and only the changes that touch the ranges are kept.
+pub fn format_file_ranges(
+  file_path: &Path,
+  file_text: &str,
+  line_ranges: &[Range<usize>],
+  fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
+) -> Result<Option<String>, AnyError> {
+  let Some(formatted_text) =
+    format_file(file_path, file_text, fmt_options, languages)?
+  else {
+    return Ok(None);
+  };
+  let Some(text) =
+    keep_changes_in_line_ranges(file_text, &formatted_text, line_ranges)
+  else {
+    return Ok(None);
+  };
+  // the kept changes must only be formatting changes, so the text must still
+  // format the same way as the original text
+  let text_formatted_text =
+    format_file(file_path, &text, fmt_options, languages)?;
+  if text_formatted_text.as_deref().unwrap_or(&text) != formatted_text {
+    bail!(
+      "Failed formatting only the changed lines. Format the whole file instead."
+    );
+  }
+  Ok(Some(text))
+}
+
+/// Keeps the changes of the formatted text that touch the given 0-indexed
+/// line ranges of the original text. Returns `None` when that doesn't
+/// change the text.
+fn keep_changes_in_line_ranges(
+  file_text: &str,
+  formatted_text: &str,
+  line_ranges: &[Range<usize>],
+) -> Option<String> {
+  let orig_lines = file_text.split_inclusive('\n').collect::<Vec<_>>();
+  let edit_lines = formatted_text.split_inclusive('\n').collect::<Vec<_>>();
+  let mut text = String::with_capacity(file_text.len());
+  let mut orig_line = 0;
+  for change in diff_lines(file_text, formatted_text) {
+    text.extend(orig_lines[orig_line..change.orig.start].iter().copied());
+    let in_ranges = line_ranges.iter().any(|range| {
+      if change.orig.is_empty() {
+        // lines inserted at the start or end of a range
+        range.start <= change.orig.start && change.orig.start <= range.end
+      } else {
+        range.start < change.orig.end && change.orig.start < range.end
+      }
+    });
+    if in_ranges {
+      text.extend(edit_lines[change.edit].iter().copied());
+    } else {
+      text.extend(orig_lines[change.orig.clone()].iter().copied());
+    }
+    orig_line = change.orig.end;
+  }
+  text.extend(orig_lines[orig_line..].iter().copied());
+  changed_text(file_text, text)
+}
+
 pub fn format_parsed_source(
   parsed_source: &ParsedSource,
   fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
 ) -> Result<Option<String>, AnyError> {
-  dprint_plugin_typescript::format_parsed_source(
+  let formatted_text = dprint_plugin_typescript::format_parsed_source(
     parsed_source,
     &get_resolved_typescript_config(fmt_options),
+  )?;
+  if !languages.embedded_templates.unwrap_or(false) {
+    return Ok(formatted_text);
+  }
+  format_with_embedded_templates(
+    parsed_source.specifier(),
+    parsed_source.media_type(),
+    parsed_source.text(),
+    formatted_text,
+    fmt_options,
+    languages,
   )
 }
 
+/// Formats the embedded templates of a JS or TS file after it was formatted,
+/// so that they're indented according to the formatted code.
+fn format_with_embedded_templates(
+  specifier: &ModuleSpecifier,
+  media_type: MediaType,
+  file_text: &str,
+  formatted_text: Option<String>,
+  fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
+) -> Result<Option<String>, AnyError> {
+  let text = formatted_text.as_deref().unwrap_or(file_text);
+  let parsed_source = deno_ast::parse_program(deno_ast::ParseParams {
+    specifier: specifier.clone(),
+    text: text.into(),
+    media_type,
+    capture_tokens: false,
+    scope_analysis: false,
+    maybe_syntax: None,
+  })?;
+  let mut collector = EmbeddedTemplateCollector::default();
+  parsed_source.program().visit_with(&mut collector);
+  if collector.templates.is_empty() {
+    return Ok(formatted_text);
+  }
+
+  let file_start = parsed_source.text_info_lazy().range().start;
+  let mut result = String::with_capacity(text.len());
+  let mut last_end = 0;
+  for template in collector.templates {
+    let quasis = template
+      .quasis
+      .iter()
+      .map(|range| range.as_byte_range(file_start))
+      .collect::<Vec<_>>();
+    let content_range = quasis[0].start..quasis[quasis.len() - 1].end;
+    let Some(content) = format_embedded_template(
+      text,
+      template.language,
+      &quasis,
+      fmt_options,
+      languages,
+    ) else {
+      continue;
+    };
+    result.push_str(&text[last_end..content_range.start]);
+    result.push_str(&content);
+    last_end = content_range.end;
+  }
+  result.push_str(&text[last_end..]);
+  Ok(changed_text(file_text, result))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmbeddedLanguage {
+  Css,
+  Html,
+  Sql,
+  GraphQl,
+}
+
+struct EmbeddedTemplate {
+  language: EmbeddedLanguage,
+  /// The ranges of the text between the interpolations.
+  quasis: Vec<deno_ast::SourceRange>,
+}
+
+/// Collects the template literals with a well-known tag, without the ones
+/// nested in them.
+#[derive(Default)]
+struct EmbeddedTemplateCollector {
+  templates: Vec<EmbeddedTemplate>,
+}
+
+impl Visit for EmbeddedTemplateCollector {
+  fn visit_tagged_tpl(&mut self, node: &ast::TaggedTpl) {
+    let language = match &*node.tag {
+      ast::Expr::Ident(ident) => match ident.sym.as_str() {
+        "css" => Some(EmbeddedLanguage::Css),
+        "html" => Some(EmbeddedLanguage::Html),
+        "sql" => Some(EmbeddedLanguage::Sql),
+        "gql" | "graphql" => Some(EmbeddedLanguage::GraphQl),
+        _ => None,
+      },
+      _ => None,
+    };
+    match language {
+      Some(language) => self.templates.push(EmbeddedTemplate {
+        language,
+        quasis: node.tpl.quasis.iter().map(|quasi| quasi.range()).collect(),
+      }),
+      None => node.visit_children_with(self),
+    }
+  }
+}
+
+const EMBEDDED_PLACEHOLDER: &str = "deno_fmt_placeholder_";
+
+fn embedded_placeholder(index: usize) -> String {
+  // end with a `_` so that a placeholder isn't the start of another one
+  format!("{}{}_", EMBEDDED_PLACEHOLDER, index)
+}
+
+/// Formats the content of a template literal, replacing the interpolations
+/// with placeholders for the formatter. Returns `None` when the template
+/// can't be formatted, which leaves it as it is.
+fn format_embedded_template(
+  file_text: &str,
+  language: EmbeddedLanguage,
+  quasis: &[Range<usize>],
+  fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
+) -> Option<String> {
+  if quasis
+    .iter()
+    .any(|quasi| file_text[quasi.clone()].contains(EMBEDDED_PLACEHOLDER))
+  {
+    return None;
+  }
+  let mut content = String::new();
+  let mut interpolations = Vec::with_capacity(quasis.len() - 1);
+  for (i, quasi) in quasis.iter().enumerate() {
+    if i > 0 {
+      // the text of the interpolation, with its `${` and `}`
+      interpolations.push(&file_text[quasis[i - 1].end..quasi.start]);
+      content.push_str(&embedded_placeholder(i - 1));
+    }
+    content.push_str(&file_text[quasi.clone()]);
+  }
+  // escapes could be changed by the formatter
+  if content.trim().is_empty() || content.contains('\\') {
+    return None;
+  }
+
+  // indent the content one level more than the line of the template
+  let line_start = file_text[..quasis[0].start]
+    .rfind('\n')
+    .map(|i| i + 1)
+    .unwrap_or(0);
+  let line_indent = file_text[line_start..]
+    .chars()
+    .take_while(|c| *c == ' ' || *c == '\t')
+    .collect::<String>();
+  let indent = if fmt_options.use_tabs == Some(true) {
+    format!("{}\t", line_indent)
+  } else {
+    format!(
+      "{}{}",
+      line_indent,
+      " ".repeat(fmt_options.indent_width.unwrap_or(2) as usize)
+    )
+  };
+  let indent_width = indent
+    .chars()
+    .map(|c| {
+      if c == '\t' {
+        fmt_options.indent_width.unwrap_or(2) as u32
+      } else {
+        1
+      }
+    })
+    .sum::<u32>();
+  let mut fmt_options = fmt_options.clone();
+  fmt_options.line_width = Some(
+    fmt_options
+      .line_width
+      .unwrap_or(80)
+      .saturating_sub(indent_width)
+      .max(20),
+  );
+  let languages = languages.without_line_widths();
+
+  let formatted_text = match language {
+    EmbeddedLanguage::Css => format_css(
+      Path::new("deno_fmt_stdin.css"),
+      &content,
+      &languages.css.apply(&fmt_options),
+    ),
+    EmbeddedLanguage::Html => format_html(&content, &fmt_options, &languages),
+    EmbeddedLanguage::Sql => format_sql(&content, &fmt_options, &languages),
+    EmbeddedLanguage::GraphQl => format_graphql(&content, &fmt_options),
+  }
+  .ok()?
+  .unwrap_or_else(|| content.clone());
+  let formatted_text = formatted_text.trim();
+  if formatted_text.contains('`') || formatted_text.contains("${") {
+    return None;
+  }
+
+  let mut text = String::with_capacity(formatted_text.len() * 2);
+  text.push('\n');
+  for line in formatted_text.lines() {
+    if !line.trim().is_empty() {
+      text.push_str(&indent);
+      text.push_str(line);
+    }
+    text.push('\n');
+  }
+  text.push_str(&line_indent);
+
+  // put back the interpolations, which must all still be there
+  for (i, interpolation) in interpolations.iter().enumerate().rev() {
+    let placeholder = embedded_placeholder(i);
+    if text.matches(&placeholder).count() != 1 {
+      return None;
+    }
+    text = text.replacen(&placeholder, interpolation, 1);
+  }
+  Some(text)
+}
+
 #[async_trait]
 trait Formatter {
   async fn handle_files(
     &self,
     paths: Vec<PathBuf>,
     fmt_options: FmtOptionsConfig,
+    languages: FmtLanguagesConfig,
+    changed_files: Option<Arc<GitChangedFiles>>,
     incremental_cache: Arc<IncrementalCache>,
   ) -> Result<(), AnyError>;
 
@@ -337,6 +859,8 @@ impl Formatter for CheckFormatter {
     &self,
     paths: Vec<PathBuf>,
     fmt_options: FmtOptionsConfig,
+    languages: FmtLanguagesConfig,
+    changed_files: Option<Arc<GitChangedFiles>>,
     incremental_cache: Arc<IncrementalCache>,
   ) -> Result<(), AnyError> {
     // prevent threads outputting at the same time
@@ -354,7 +878,20 @@ impl Formatter for CheckFormatter {
           return Ok(());
         }
 
-        match format_file(&file_path, &file_text, &fmt_options) {
+        let changed_lines = changed_files
+          .as_ref()
+          .and_then(|files| files.changed_lines(&file_path));
+        let format_result = match changed_lines {
+          Some(changed_lines) => format_file_ranges(
+            &file_path,
+            &file_text,
+            changed_lines,
+            &fmt_options,
+            &languages,
+          ),
+          None => format_file(&file_path, &file_text, &fmt_options, &languages),
+        };
+        match format_result {
           Ok(Some(formatted_text)) => {
             not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
             let _g = output_lock.lock();
@@ -368,8 +905,11 @@ impl Formatter for CheckFormatter {
             // the file is the same since we don't bother checking for stable
             // formatting here. Additionally, ensure this is done during check
             // so that CIs that cache the DENO_DIR will get the benefit of
-            // incremental formatting
-            incremental_cache.update_file(&file_path, &file_text);
+            // incremental formatting. Only the changed lines were checked
+            // when formatting ranges, so the file might not be formatted.
+            if changed_lines.is_none() {
+              incremental_cache.update_file(&file_path, &file_text);
+            }
           }
           Err(e) => {
             not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
@@ -429,6 +969,8 @@ impl Formatter for RealFormatter {
     &self,
     paths: Vec<PathBuf>,
     fmt_options: FmtOptionsConfig,
+    languages: FmtLanguagesConfig,
+    changed_files: Option<Arc<GitChangedFiles>>,
     incremental_cache: Arc<IncrementalCache>,
   ) -> Result<(), AnyError> {
     let output_lock = Arc::new(Mutex::new(0)); // prevent threads outputting at the same time
@@ -445,14 +987,32 @@ impl Formatter for RealFormatter {
           return Ok(());
         }
 
-        match format_ensure_stable(
-          &file_path,
-          &file_contents.text,
-          &fmt_options,
-          format_file,
-        ) {
+        let changed_lines = changed_files
+          .as_ref()
+          .and_then(|files| files.changed_lines(&file_path));
+        let format_result = match changed_lines {
+          // the line ranges only apply to the original text, so they can't
+          // be formatted again to ensure a stable format
+          Some(changed_lines) => format_file_ranges(
+            &file_path,
+            &file_contents.text,
+            changed_lines,
+            &fmt_options,
+            &languages,
+          ),
+          None => format_ensure_stable(
+            &file_path,
+            &file_contents.text,
+            &fmt_options,
+            &languages,
+            format_file,
+          ),
+        };
+        match format_result {
           Ok(Some(formatted_text)) => {
-            incremental_cache.update_file(&file_path, &formatted_text);
+            if changed_lines.is_none() {
+              incremental_cache.update_file(&file_path, &formatted_text);
+            }
             write_file_contents(
               &file_path,
               FileContents {
@@ -465,7 +1025,9 @@ impl Formatter for RealFormatter {
             info!("{}", file_path.to_string_lossy());
           }
           Ok(None) => {
-            incremental_cache.update_file(&file_path, &file_contents.text);
+            if changed_lines.is_none() {
+              incremental_cache.update_file(&file_path, &file_contents.text);
+            }
           }
           Err(e) => {
             let _g = output_lock.lock();
@@ -507,19 +1069,21 @@ fn format_ensure_stable(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  languages: &FmtLanguagesConfig,
   fmt_func: impl Fn(
     &Path,
     &str,
     &FmtOptionsConfig,
+    &FmtLanguagesConfig,
   ) -> Result<Option<String>, AnyError>,
 ) -> Result<Option<String>, AnyError> {
-  let formatted_text = fmt_func(file_path, file_text, fmt_options)?;
+  let formatted_text = fmt_func(file_path, file_text, fmt_options, languages)?;
 
   match formatted_text {
     Some(mut current_text) => {
       let mut count = 0;
       loop {
-        match fmt_func(file_path, &current_text, fmt_options) {
+        match fmt_func(file_path, &current_text, fmt_options, languages) {
           Ok(Some(next_pass_text)) => {
             // just in case
             if next_pass_text == current_text {
@@ -574,7 +1138,12 @@ fn format_stdin(
     bail!("Failed to read from stdin");
   }
   let file_path = PathBuf::from(format!("_stdin.{ext}"));
-  let formatted_text = format_file(&file_path, &source, &fmt_options.options)?;
+  let formatted_text = format_file(
+    &file_path,
+    &source,
+    &fmt_options.options,
+    &fmt_options.languages,
+  )?;
   if fmt_flags.check {
     #[allow(clippy::print_stdout)]
     if formatted_text.is_some() {
@@ -683,6 +1252,97 @@ fn get_resolved_json_config(
   builder.build()
 }
 
+fn get_resolved_malva_config(
+  options: &FmtOptionsConfig,
+) -> malva::config::FormatOptions {
+  use malva::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      use_tabs: options.use_tabs.unwrap_or(false),
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::PreferSingle
+      } else {
+        Quotes::PreferDouble
+      },
+      ignore_comment_directive: "deno-fmt-ignore".into(),
+      ..Default::default()
+    },
+  }
+}
+
+fn get_resolved_markup_fmt_config(
+  options: &FmtOptionsConfig,
+) -> markup_fmt::config::FormatOptions {
+  use markup_fmt::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      use_tabs: options.use_tabs.unwrap_or(false),
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::Single
+      } else {
+        Quotes::Double
+      },
+      html_void_self_closing: Some(false),
+      ignore_comment_directive: "deno-fmt-ignore".into(),
+      ..Default::default()
+    },
+  }
+}
+
+fn get_resolved_yaml_config(
+  options: &FmtOptionsConfig,
+) -> pretty_yaml::config::FormatOptions {
+  use pretty_yaml::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      // YAML doesn't allow tabs for indentation
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::PreferSingle
+      } else {
+        Quotes::PreferDouble
+      },
+      ..Default::default()
+    },
+  }
+}
+
+fn get_resolved_graphql_config(
+  options: &FmtOptionsConfig,
+) -> pretty_graphql::config::FormatOptions {
+  use pretty_graphql::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      use_tabs: options.use_tabs.unwrap_or(false),
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      ignore_comment_directive: "deno-fmt-ignore".into(),
+      ..Default::default()
+    },
+  }
+}
+
 struct FileContents {
   text: String,
   had_bom: bool,
@@ -782,6 +1442,13 @@ fn is_supported_ext_fmt(path: &Path) -> bool {
         | "mdown"
         | "markdown"
         | "ipynb"
+        | "css"
+        | "scss"
+        | "less"
+        | "html"
+        | "yaml"
+        | "yml"
+        | "sql"
     )
   })
 }
@@ -816,6 +1483,15 @@ mod test {
     assert!(is_supported_ext_fmt(Path::new("foo.json")));
     assert!(is_supported_ext_fmt(Path::new("foo.JsON")));
     assert!(is_supported_ext_fmt(Path::new("foo.ipynb")));
+    assert!(is_supported_ext_fmt(Path::new("foo.css")));
+    assert!(is_supported_ext_fmt(Path::new("foo.SCSS")));
+    assert!(is_supported_ext_fmt(Path::new("foo.less")));
+    assert!(is_supported_ext_fmt(Path::new("foo.html")));
+    assert!(!is_supported_ext_fmt(Path::new("foo.htm")));
+    assert!(is_supported_ext_fmt(Path::new("foo.yaml")));
+    assert!(is_supported_ext_fmt(Path::new("foo.yml")));
+    assert!(is_supported_ext_fmt(Path::new("foo.sql")));
+    assert!(!is_supported_ext_fmt(Path::new("foo.sass")));
   }
 
   #[test]
@@ -825,7 +1501,8 @@ mod test {
       &PathBuf::from("mod.ts"),
       "1",
       &Default::default(),
-      |_, file_text, _| Ok(Some(format!("1{file_text}"))),
+      &Default::default(),
+      |_, file_text, _, _| Ok(Some(format!("1{file_text}"))),
     )
     .unwrap();
   }
@@ -836,7 +1513,8 @@ mod test {
       &PathBuf::from("mod.ts"),
       "1",
       &Default::default(),
-      |_, _, _| bail!("Error formatting."),
+      &Default::default(),
+      |_, _, _, _| bail!("Error formatting."),
     )
     .unwrap_err();
 
@@ -850,7 +1528,8 @@ mod test {
       &PathBuf::from("mod.ts"),
       "1",
       &Default::default(),
-      |_, file_text, _| {
+      &Default::default(),
+      |_, file_text, _, _| {
         if file_text == "1" {
           Ok(Some("11".to_string()))
         } else {
@@ -867,7 +1546,8 @@ mod test {
       &PathBuf::from("mod.ts"),
       "1",
       &Default::default(),
-      |_, file_text, _| {
+      &Default::default(),
+      |_, file_text, _, _| {
         if file_text == "1" {
           Ok(Some("11".to_string()))
         } else if file_text == "11" {
@@ -891,6 +1571,7 @@ mod test {
         single_quote: Some(true),
         ..Default::default()
       },
+      &Default::default(),
     )
     .unwrap()
     .unwrap();
@@ -900,4 +1581,66 @@ mod test {
       "console.log(\"there's\");\nconsole.log('hi');\nconsole.log('bye');\n",
     );
   }
+
+  #[test]
+  fn test_format_file_ranges() {
+    let file_text = "const a=1;\n\nconst b=2;\n\n\n\nconst c=3;\n";
+    let format = |line_ranges: &[Range<usize>]| {
+      format_file_ranges(
+        &PathBuf::from("mod.ts"),
+        file_text,
+        line_ranges,
+        &Default::default(),
+        &Default::default(),
+      )
+      .unwrap()
+    };
+    assert_eq!(
+      format(&[2..3]),
+      Some("const a=1;\n\nconst b = 2;\n\n\n\nconst c=3;\n".to_string())
+    );
+    assert_eq!(
+      format(&[4..7]),
+      Some("const a=1;\n\nconst b=2;\n\nconst c = 3;\n".to_string())
+    );
+    assert_eq!(
+      format(&[0..7]),
+      Some("const a = 1;\n\nconst b = 2;\n\nconst c = 3;\n".to_string())
+    );
+    // the changed lines are already formatted
+    assert_eq!(format(&[1..2]), None);
+  }
+
+  #[test]
+  fn test_language_options_override_fmt_options() {
+    let fmt_options = FmtOptionsConfig {
+      indent_width: Some(4),
+      ..Default::default()
+    };
+    let languages = FmtLanguagesConfig {
+      css: crate::args::FmtLanguageOptions {
+        indent_width: Some(2),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+    let file_text = format_file(
+      &PathBuf::from("style.css"),
+      "a{color:red}\n",
+      &fmt_options,
+      &languages,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(file_text, "a {\n  color: red;\n}\n");
+    let file_text = format_file(
+      &PathBuf::from("style.css"),
+      "a{color:red}\n",
+      &fmt_options,
+      &Default::default(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(file_text, "a {\n    color: red;\n}\n");
+  }
 }
@@ -89,6 +89,7 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
       &factory,
       info_flags.json,
       cli_options.location_flag().as_ref(),
+      cli_options.permission_args(),
     )?;
   }
   Ok(())
@@ -99,6 +100,8 @@ fn print_cache_info(
   factory: &CliFactory,
   json: bool,
   location: Option<&deno_core::url::Url>,
+  // the flags of the effective permissions, shown when there are any
+  permission_args: Vec<String>,
 ) -> Result<(), AnyError> {
   let dir = factory.deno_dir()?;
   #[allow(deprecated)]
@@ -129,6 +132,9 @@ fn print_cache_info(
     if location.is_some() {
       output["localStorage"] = serde_json::to_value(local_storage_dir)?;
     }
+    if !permission_args.is_empty() {
+      output["permissions"] = json!(permission_args);
+    }
 
     display::write_json_to_stdout(&output)
   } else {
@@ -165,6 +171,13 @@ fn print_cache_info(
         local_storage_dir.display(),
       );
     }
+    if !permission_args.is_empty() {
+      println!(
+        "{} {}",
+        colors::bold("Permissions:"),
+        permission_args.join(" ")
+      );
+    }
     Ok(())
   }
 }
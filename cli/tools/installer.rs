@@ -309,6 +309,11 @@ async fn install_global(
     .await?;
   let http_client = factory.http_client_provider();
 
+  // the shim gets the flags of the permission set, since it runs where the
+  // set's config file can't be found
+  let mut flags = flags;
+  flags.permissions = factory.cli_options().permission_flags().clone();
+
   // create the install shim
   create_install_shim(http_client, flags, install_flags_global).await
 }
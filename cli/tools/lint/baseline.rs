@@ -0,0 +1,348 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The lint baseline records the problems of a project at some point, so
+//! that new rules can be adopted without fixing all the existing problems at
+//! once. Only the problems that aren't in the baseline are reported, and the
+//! ones that were fixed are removed from it.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::SourceRange;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_lint::diagnostic::LintDiagnostic;
+use log::info;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::args::LintBaselineOptions;
+use crate::cache::FastInsecureHasher;
+use crate::util::path::relative_path;
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct LintBaselineFile {
+  version: u32,
+  /// The recorded problems by file, relative to the baseline file.
+  files: BTreeMap<String, Vec<LintBaselineEntry>>,
+}
+
+#[derive(
+  Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+struct LintBaselineEntry {
+  code: String,
+  /// A hash of the problem that doesn't depend on its position in the file,
+  /// so that it still matches after unrelated changes.
+  fingerprint: String,
+}
+
+impl LintBaselineEntry {
+  fn from_diagnostic(diagnostic: &LintDiagnostic) -> Self {
+    // use the text of the lines of the problem, ignoring changes in
+    // indentation and line breaks
+    let text_info = &diagnostic.text_info;
+    let start =
+      text_info.line_start(text_info.line_index(diagnostic.range.start));
+    let end = text_info.line_end(text_info.line_index(diagnostic.range.end));
+    let range =
+      SourceRange::new(start, end).as_byte_range(text_info.range().start);
+    let text = text_info.text_str().get(range).unwrap_or_default();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let hash = FastInsecureHasher::new_without_deno_version()
+      .write_str(&diagnostic.code)
+      .write_str(&diagnostic.message)
+      .write_str(&text)
+      .finish();
+    Self {
+      code: diagnostic.code.clone(),
+      fingerprint: format!("{:016x}", hash),
+    }
+  }
+}
+
+#[derive(Default)]
+struct LintBaselineState {
+  /// The recorded problems that are still present in the linted files.
+  files: HashMap<String, Vec<LintBaselineEntry>>,
+  linted_files: HashSet<String>,
+  ignored_count: usize,
+}
+
+pub struct LintBaseline {
+  path: PathBuf,
+  dir: PathBuf,
+  write: bool,
+  recorded: LintBaselineFile,
+  state: Mutex<LintBaselineState>,
+}
+
+impl LintBaseline {
+  pub fn load(options: &LintBaselineOptions) -> Result<Self, AnyError> {
+    let recorded = if options.path.exists() {
+      let text = std::fs::read_to_string(&options.path).with_context(|| {
+        format!("Failed reading lint baseline {}", options.path.display())
+      })?;
+      let recorded: LintBaselineFile = serde_json::from_str(&text)
+        .with_context(|| {
+          format!("Failed parsing lint baseline {}", options.path.display())
+        })?;
+      if recorded.version != BASELINE_VERSION && !options.write {
+        bail!(
+          "Unsupported lint baseline version {} in {}. Run `deno lint --write-baseline` to recreate it.",
+          recorded.version,
+          options.path.display()
+        );
+      }
+      recorded
+    } else {
+      LintBaselineFile::default()
+    };
+    Ok(Self {
+      dir: options
+        .path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default(),
+      path: options.path.clone(),
+      write: options.write,
+      recorded,
+      state: Default::default(),
+    })
+  }
+
+  fn file_key(&self, file_path: &Path) -> String {
+    relative_path(&self.dir, file_path)
+      .unwrap_or_else(|| file_path.to_path_buf())
+      .to_string_lossy()
+      .replace('\\', "/")
+  }
+
+  /// Marks files as linted, so that their recorded problems are removed
+  /// unless they're still present.
+  pub fn mark_linted(&self, file_paths: &[PathBuf]) {
+    let mut state = self.state.lock();
+    for file_path in file_paths {
+      state.linted_files.insert(self.file_key(file_path));
+    }
+  }
+
+  /// Keeps the recorded problems of a file that couldn't be linted.
+  pub fn keep_file(&self, file_path: &Path) {
+    let key = self.file_key(file_path);
+    let mut state = self.state.lock();
+    if let Some(entries) = self.recorded.files.get(&key) {
+      state.files.insert(key, entries.clone());
+    }
+  }
+
+  /// Removes the diagnostics recorded in the baseline. When writing the
+  /// baseline, all the diagnostics are recorded and none are reported.
+  pub fn filter_diagnostics(
+    &self,
+    file_path: &Path,
+    diagnostics: Vec<LintDiagnostic>,
+  ) -> Vec<LintDiagnostic> {
+    let key = self.file_key(file_path);
+    let mut state = self.state.lock();
+    state.linted_files.insert(key.clone());
+    if self.write {
+      let entries = diagnostics
+        .iter()
+        .map(LintBaselineEntry::from_diagnostic)
+        .collect::<Vec<_>>();
+      state.ignored_count += entries.len();
+      if !entries.is_empty() {
+        // the type-aware rules report the problems of a file separately
+        state.files.entry(key).or_default().extend(entries);
+      }
+      return Vec::new();
+    }
+
+    let mut remaining = HashMap::<&LintBaselineEntry, usize>::new();
+    for entry in self.recorded.files.get(&key).into_iter().flatten() {
+      *remaining.entry(entry).or_default() += 1;
+    }
+    let mut matched = Vec::new();
+    let mut reported = Vec::new();
+    for diagnostic in diagnostics {
+      let entry = LintBaselineEntry::from_diagnostic(&diagnostic);
+      match remaining.get_mut(&entry) {
+        Some(count) if *count > 0 => {
+          *count -= 1;
+          matched.push(entry);
+        }
+        _ => reported.push(diagnostic),
+      }
+    }
+    state.ignored_count += matched.len();
+    if !matched.is_empty() {
+      state.files.entry(key).or_default().extend(matched);
+    }
+    reported
+  }
+
+  /// Saves the baseline when writing it or when recorded problems were
+  /// fixed.
+  pub fn finish(&self) -> Result<(), AnyError> {
+    let state = self.state.lock();
+    let mut files = self
+      .recorded
+      .files
+      .iter()
+      .filter(|(key, _)| !state.linted_files.contains(*key))
+      .map(|(key, entries)| (key.clone(), entries.clone()))
+      .collect::<BTreeMap<_, _>>();
+    for (key, entries) in &state.files {
+      let mut entries = entries.clone();
+      entries.sort();
+      files.insert(key.clone(), entries);
+    }
+    let baseline = LintBaselineFile {
+      version: BASELINE_VERSION,
+      files,
+    };
+
+    if self.write {
+      self.save(&baseline)?;
+      info!(
+        "Recorded {} {} in the lint baseline at {}",
+        state.ignored_count,
+        if state.ignored_count == 1 {
+          "problem"
+        } else {
+          "problems"
+        },
+        self.path.display()
+      );
+      return Ok(());
+    }
+
+    let count = |file: &LintBaselineFile| {
+      file
+        .files
+        .values()
+        .map(|entries| entries.len())
+        .sum::<usize>()
+    };
+    let fixed_count = count(&self.recorded) - count(&baseline);
+    if fixed_count > 0 {
+      self.save(&baseline)?;
+      info!(
+        "Removed {} fixed {} from the lint baseline at {}",
+        fixed_count,
+        if fixed_count == 1 {
+          "problem"
+        } else {
+          "problems"
+        },
+        self.path.display()
+      );
+    }
+    if state.ignored_count > 0 {
+      info!(
+        "Ignored {} {} recorded in the lint baseline",
+        state.ignored_count,
+        if state.ignored_count == 1 {
+          "problem"
+        } else {
+          "problems"
+        },
+      );
+    }
+    Ok(())
+  }
+
+  fn save(&self, baseline: &LintBaselineFile) -> Result<(), AnyError> {
+    let mut text = serde_json::to_string_pretty(baseline)?;
+    text.push('\n');
+    std::fs::write(&self.path, text).with_context(|| {
+      format!("Failed writing lint baseline {}", self.path.display())
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_ast::MediaType;
+  use deno_ast::ModuleSpecifier;
+  use deno_lint::linter::LintConfig;
+  use deno_lint::linter::LintFileOptions;
+
+  fn lint(source_code: &str) -> Vec<LintDiagnostic> {
+    let linter =
+      super::super::create_linter(vec![deno_lint::rules::get_all_rules()
+        .into_iter()
+        .find(|rule| rule.code() == "no-explicit-any")
+        .unwrap()]);
+    let (_, diagnostics) = linter
+      .lint_file(LintFileOptions {
+        specifier: ModuleSpecifier::parse("file:///project/a.ts").unwrap(),
+        media_type: MediaType::TypeScript,
+        source_code: source_code.to_string(),
+        config: LintConfig {
+          default_jsx_factory: None,
+          default_jsx_fragment_factory: None,
+        },
+      })
+      .unwrap();
+    diagnostics
+  }
+
+  #[test]
+  fn baseline_ignores_recorded_problems() {
+    let temp_dir = test_util::TempDir::new();
+    let options = LintBaselineOptions {
+      path: temp_dir.path().join("baseline.json").to_path_buf(),
+      write: true,
+    };
+    let file_path = temp_dir.path().join("a.ts").to_path_buf();
+
+    let baseline = LintBaseline::load(&options).unwrap();
+    let diagnostics = lint("export let a: any;\nexport let b: any;\n");
+    assert_eq!(diagnostics.len(), 2);
+    assert!(baseline
+      .filter_diagnostics(&file_path, diagnostics)
+      .is_empty());
+    baseline.finish().unwrap();
+
+    // moving the problems and adding one only reports the new one
+    let options = LintBaselineOptions {
+      write: false,
+      ..options
+    };
+    let baseline = LintBaseline::load(&options).unwrap();
+    let diagnostics =
+      lint("\nexport let a: any;\nexport let b: any;\nexport let c: any[];\n");
+    let reported = baseline.filter_diagnostics(&file_path, diagnostics);
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0].text_info.line_index(reported[0].range.start), 3);
+    baseline.finish().unwrap();
+
+    // fixing a problem removes it from the baseline
+    let baseline = LintBaseline::load(&options).unwrap();
+    let diagnostics = lint("export let a: any;\n");
+    assert!(baseline
+      .filter_diagnostics(&file_path, diagnostics)
+      .is_empty());
+    baseline.finish().unwrap();
+    let baseline = LintBaseline::load(&options).unwrap();
+    assert_eq!(baseline.recorded.files["a.ts"].len(), 1);
+
+    // files that weren't linted keep their problems
+    let baseline = LintBaseline::load(&options).unwrap();
+    baseline.mark_linted(&[temp_dir.path().join("b.ts").to_path_buf()]);
+    baseline.finish().unwrap();
+    let baseline = LintBaseline::load(&options).unwrap();
+    assert_eq!(baseline.recorded.files["a.ts"].len(), 1);
+  }
+}
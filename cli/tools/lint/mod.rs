@@ -35,6 +35,7 @@ use log::debug;
 use log::info;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::fs;
 use std::io::stdin;
@@ -56,15 +57,41 @@ use crate::cache::IncrementalCache;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::graph_util::ModuleGraphCreator;
+use crate::tools::check::TypeChecker;
 use crate::tools::fmt::run_parallelized;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::specifier_from_file_path;
 use crate::util::fs::FileCollector;
 use crate::util::path::is_script_ext;
+use crate::util::sarif::SarifArtifactChange;
+use crate::util::sarif::SarifArtifactContent;
+use crate::util::sarif::SarifArtifactLocation;
+use crate::util::sarif::SarifFix;
+use crate::util::sarif::SarifInvocation;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLocation;
+use crate::util::sarif::SarifLog;
+use crate::util::sarif::SarifMessage;
+use crate::util::sarif::SarifNotification;
+use crate::util::sarif::SarifPhysicalLocation;
+use crate::util::sarif::SarifPropertyBag;
+use crate::util::sarif::SarifRegion;
+use crate::util::sarif::SarifReplacement;
+use crate::util::sarif::SarifReportingDescriptor;
+use crate::util::sarif::SarifResult;
+use crate::util::sarif::SarifRun;
+use crate::util::sarif::SarifTool;
+use crate::util::sarif::SarifToolComponent;
 use crate::util::sync::AtomicFlag;
 
+use self::baseline::LintBaseline;
+use self::plugins::LintPluginHost;
+
+pub mod baseline;
 pub mod no_slow_types;
+pub mod plugins;
+pub mod type_aware;
 
 static STDIN_FILE_NAME: &str = "$deno$stdin.ts";
 
@@ -73,6 +100,7 @@ fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new()),
   }
 }
 
@@ -118,9 +146,10 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
           let mut linter = WorkspaceLinter::new(
             factory.caches()?.clone(),
             factory.module_graph_creator().await?.clone(),
+            factory.type_checker().await?.clone(),
             cli_options.workspace.clone(),
             &cli_options.resolve_workspace_lint_options(&lint_flags)?,
-          );
+          )?;
           for paths_with_options in paths_with_options_batches {
             linter
               .lint_files(
@@ -132,7 +161,7 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
               .await?;
           }
 
-          linter.finish();
+          linter.finish()?;
 
           Ok(())
         })
@@ -154,15 +183,22 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
       let lint_options =
         cli_options.resolve_lint_options(lint_flags, &start_ctx)?;
       let lint_rules = get_config_rules_err_empty(
-        lint_options.rules,
+        &lint_options,
         start_ctx.maybe_deno_json().map(|c| c.as_ref()),
       )?;
+      let plugin_host = lint_rules.plugin_host()?;
       let file_path = cli_options.initial_cwd().join(STDIN_FILE_NAME);
-      let r = lint_stdin(&file_path, lint_rules.rules, lint_config);
+      let r = lint_stdin(
+        &file_path,
+        lint_rules.rules,
+        plugin_host.as_deref(),
+        lint_config,
+      );
       let success = handle_lint_result(
         &file_path.to_string_lossy(),
         r,
         reporter_lock.clone(),
+        None,
       );
       reporter_lock.lock().close(1);
       success
@@ -170,9 +206,10 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
       let mut linter = WorkspaceLinter::new(
         factory.caches()?.clone(),
         factory.module_graph_creator().await?.clone(),
+        factory.type_checker().await?.clone(),
         cli_options.workspace.clone(),
         &workspace_lint_options,
-      );
+      )?;
       let paths_with_options_batches =
         resolve_paths_with_options_batches(cli_options, &lint_flags)?;
       for paths_with_options in paths_with_options_batches {
@@ -185,7 +222,7 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
           )
           .await?;
       }
-      linter.finish()
+      linter.finish()?
     };
     if !success {
       std::process::exit(1);
@@ -231,31 +268,42 @@ type WorkspaceModuleGraphFuture =
 struct WorkspaceLinter {
   caches: Arc<Caches>,
   module_graph_creator: Arc<ModuleGraphCreator>,
+  type_checker: Arc<TypeChecker>,
   workspace: Arc<Workspace>,
   reporter_lock: Arc<Mutex<Box<dyn LintReporter + Send>>>,
   workspace_module_graph: Option<WorkspaceModuleGraphFuture>,
   has_error: Arc<AtomicFlag>,
   file_count: usize,
+  baseline: Option<Arc<LintBaseline>>,
 }
 
 impl WorkspaceLinter {
   pub fn new(
     caches: Arc<Caches>,
     module_graph_creator: Arc<ModuleGraphCreator>,
+    type_checker: Arc<TypeChecker>,
     workspace: Arc<Workspace>,
     workspace_options: &WorkspaceLintOptions,
-  ) -> Self {
+  ) -> Result<Self, AnyError> {
     let reporter_lock =
       Arc::new(Mutex::new(create_reporter(workspace_options.reporter_kind)));
-    Self {
+    let baseline = workspace_options
+      .baseline
+      .as_ref()
+      .map(LintBaseline::load)
+      .transpose()?
+      .map(Arc::new);
+    Ok(Self {
       caches,
       module_graph_creator,
+      type_checker,
       workspace,
       reporter_lock,
       workspace_module_graph: None,
       has_error: Default::default(),
       file_count: 0,
-    }
+      baseline,
+    })
   }
 
   pub async fn lint_files(
@@ -266,17 +314,29 @@ impl WorkspaceLinter {
     paths: Vec<PathBuf>,
   ) -> Result<(), AnyError> {
     self.file_count += paths.len();
+    if let Some(baseline) = &self.baseline {
+      // files skipped by the incremental cache have no problems left
+      baseline.mark_linted(&paths);
+    }
 
     let lint_rules = get_config_rules_err_empty(
-      lint_options.rules,
+      &lint_options,
       member_ctx.maybe_deno_json().map(|c| c.as_ref()),
     )?;
+    let plugin_host = lint_rules.plugin_host()?;
     let incremental_cache = Arc::new(IncrementalCache::new(
       self.caches.lint_incremental_cache_db(),
-      &lint_rules.incremental_cache_state(),
+      &lint_rules.incremental_cache_state(plugin_host.as_deref()),
       &paths,
     ));
 
+    let type_aware_rules = lint_rules.type_aware_rules.clone();
+    let type_aware_paths = if type_aware_rules.is_empty() {
+      Vec::new()
+    } else {
+      paths.clone()
+    };
+
     let mut futures = Vec::with_capacity(2);
     if lint_rules.no_slow_types {
       if self.workspace_module_graph.is_none() {
@@ -340,6 +400,8 @@ impl WorkspaceLinter {
       let incremental_cache = incremental_cache.clone();
       let lint_config = lint_config.clone();
       let fix = lint_options.fix;
+      let plugin_host = plugin_host.clone();
+      let baseline = self.baseline.clone();
       async move {
         run_parallelized(paths, {
           move |file_path| {
@@ -351,7 +413,14 @@ impl WorkspaceLinter {
               return Ok(());
             }
 
-            let r = lint_file(&linter, &file_path, file_text, lint_config, fix);
+            let r = lint_file(
+              &linter,
+              plugin_host.as_deref(),
+              &file_path,
+              file_text,
+              lint_config,
+              fix,
+            );
             if let Ok((file_source, file_diagnostics)) = &r {
               if file_diagnostics.is_empty() {
                 // update the incremental cache if there were no diagnostics
@@ -367,6 +436,7 @@ impl WorkspaceLinter {
               &file_path.to_string_lossy(),
               r,
               reporter_lock.clone(),
+              baseline.as_deref(),
             );
             if !success {
               has_error.raise();
@@ -382,14 +452,39 @@ impl WorkspaceLinter {
 
     deno_core::futures::future::try_join_all(futures).await?;
 
+    if !type_aware_rules.is_empty() {
+      // these depend on the other files, so the incremental cache isn't used
+      let results = type_aware::lint_files(
+        &self.module_graph_creator,
+        &self.type_checker,
+        &type_aware_paths,
+        &type_aware_rules,
+      )
+      .await?;
+      for (file_path, parsed_source, diagnostics) in results {
+        let success = handle_lint_result(
+          &file_path.to_string_lossy(),
+          Ok((parsed_source, diagnostics)),
+          self.reporter_lock.clone(),
+          self.baseline.as_deref(),
+        );
+        if !success {
+          self.has_error.raise();
+        }
+      }
+    }
+
     incremental_cache.wait_completion().await;
     Ok(())
   }
 
-  pub fn finish(self) -> bool {
+  pub fn finish(self) -> Result<bool, AnyError> {
     debug!("Found {} files", self.file_count);
     self.reporter_lock.lock().close(self.file_count);
-    !self.has_error.is_raised() // success
+    if let Some(baseline) = &self.baseline {
+      baseline.finish()?;
+    }
+    Ok(!self.has_error.is_raised()) // success
   }
 }
 
@@ -456,8 +551,23 @@ pub fn create_linter(rules: Vec<&'static dyn LintRule>) -> Linter {
     .build()
 }
 
+/// Lints a file with the rules of the linter and then the rules of the
+/// plugins, if any.
+fn lint_file_with_plugins(
+  linter: &Linter,
+  plugin_host: Option<&LintPluginHost>,
+  options: LintFileOptions,
+) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
+  let (source, mut diagnostics) = linter.lint_file(options)?;
+  if let Some(plugin_host) = plugin_host {
+    diagnostics.extend(plugin_host.lint(&source)?);
+  }
+  Ok((source, diagnostics))
+}
+
 fn lint_file(
   linter: &Linter,
+  plugin_host: Option<&LintPluginHost>,
   file_path: &Path,
   source_code: String,
   config: LintConfig,
@@ -469,6 +579,7 @@ fn lint_file(
   if fix {
     lint_file_and_fix(
       linter,
+      plugin_host,
       &specifier,
       media_type,
       source_code,
@@ -476,19 +587,22 @@ fn lint_file(
       config,
     )
   } else {
-    linter
-      .lint_file(LintFileOptions {
+    lint_file_with_plugins(
+      linter,
+      plugin_host,
+      LintFileOptions {
         specifier,
         media_type,
         source_code,
         config,
-      })
-      .map_err(AnyError::from)
+      },
+    )
   }
 }
 
 fn lint_file_and_fix(
   linter: &Linter,
+  plugin_host: Option<&LintPluginHost>,
   specifier: &ModuleSpecifier,
   media_type: MediaType,
   source_code: String,
@@ -496,12 +610,16 @@ fn lint_file_and_fix(
   config: LintConfig,
 ) -> Result<(ParsedSource, Vec<LintDiagnostic>), deno_core::anyhow::Error> {
   // initial lint
-  let (source, diagnostics) = linter.lint_file(LintFileOptions {
-    specifier: specifier.clone(),
-    media_type,
-    source_code,
-    config: config.clone(),
-  })?;
+  let (source, diagnostics) = lint_file_with_plugins(
+    linter,
+    plugin_host,
+    LintFileOptions {
+      specifier: specifier.clone(),
+      media_type,
+      source_code,
+      config: config.clone(),
+    },
+  )?;
 
   // Try applying fixes repeatedly until the file has none left or
   // a maximum number of iterations is reached. This is necessary
@@ -515,6 +633,7 @@ fn lint_file_and_fix(
       specifier,
       media_type,
       linter,
+      plugin_host,
       config.clone(),
       source.text_info_lazy(),
       &diagnostics,
@@ -554,6 +673,7 @@ fn apply_lint_fixes_and_relint(
   specifier: &ModuleSpecifier,
   media_type: MediaType,
   linter: &Linter,
+  plugin_host: Option<&LintPluginHost>,
   config: LintConfig,
   text_info: &SourceTextInfo,
   diagnostics: &[LintDiagnostic],
@@ -561,17 +681,18 @@ fn apply_lint_fixes_and_relint(
   let Some(new_text) = apply_lint_fixes(text_info, diagnostics) else {
     return Ok(None);
   };
-  linter
-    .lint_file(LintFileOptions {
+  lint_file_with_plugins(
+    linter,
+    plugin_host,
+    LintFileOptions {
       specifier: specifier.clone(),
       source_code: new_text,
       media_type,
       config,
-    })
-    .map(Some)
-    .context(
-      "An applied lint fix caused a syntax error. Please report this bug.",
-    )
+    },
+  )
+  .map(Some)
+  .context("An applied lint fix caused a syntax error. Please report this bug.")
 }
 
 fn apply_lint_fixes(
@@ -618,6 +739,7 @@ fn apply_lint_fixes(
 fn lint_stdin(
   file_path: &Path,
   lint_rules: Vec<&'static dyn LintRule>,
+  plugin_host: Option<&LintPluginHost>,
   config: LintConfig,
 ) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
   let mut source_code = String::new();
@@ -627,25 +749,34 @@ fn lint_stdin(
 
   let linter = create_linter(lint_rules);
 
-  linter
-    .lint_file(LintFileOptions {
+  lint_file_with_plugins(
+    &linter,
+    plugin_host,
+    LintFileOptions {
       specifier: specifier_from_file_path(file_path)?,
       source_code: deno_ast::strip_bom(source_code),
       media_type: MediaType::TypeScript,
       config,
-    })
-    .map_err(AnyError::from)
+    },
+  )
 }
 
 fn handle_lint_result(
   file_path: &str,
   result: Result<(ParsedSource, Vec<LintDiagnostic>), AnyError>,
   reporter_lock: Arc<Mutex<Box<dyn LintReporter + Send>>>,
+  baseline: Option<&LintBaseline>,
 ) -> bool {
   let mut reporter = reporter_lock.lock();
 
   match result {
-    Ok((_source, mut file_diagnostics)) => {
+    Ok((_source, file_diagnostics)) => {
+      let mut file_diagnostics = match baseline {
+        Some(baseline) => {
+          baseline.filter_diagnostics(Path::new(file_path), file_diagnostics)
+        }
+        None => file_diagnostics,
+      };
       file_diagnostics.sort_by(|a, b| match a.specifier.cmp(&b.specifier) {
         std::cmp::Ordering::Equal => a.range.start.cmp(&b.range.start),
         file_order => file_order,
@@ -656,6 +787,9 @@ fn handle_lint_result(
       file_diagnostics.is_empty()
     }
     Err(err) => {
+      if let Some(baseline) = baseline {
+        baseline.keep_file(Path::new(file_path));
+      }
       reporter.visit_error(file_path, &err);
       false
     }
@@ -747,6 +881,16 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
 
   fn docs_url(&self) -> Option<Cow<'_, str>> {
     match self {
+      // rules of plugins only have the docs they provide
+      LintOrCliDiagnostic::Lint(d) if plugins::is_plugin_rule_code(&d.code) => {
+        d.custom_docs_url.as_deref().map(Cow::Borrowed)
+      }
+      // the type-aware rules aren't documented on lint.deno.land
+      LintOrCliDiagnostic::Lint(d)
+        if type_aware::is_type_aware_rule_code(&d.code) =>
+      {
+        None
+      }
       LintOrCliDiagnostic::Lint(d) => d.docs_url(),
       LintOrCliDiagnostic::FastCheck(d) => d.docs_url(),
     }
@@ -959,6 +1103,177 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+/// Outputs the diagnostics as a SARIF log, with the metadata of the rules
+/// that were violated and the available fixes.
+struct SarifLintReporter {
+  results: Vec<SarifResult>,
+  rules: BTreeMap<String, SarifReportingDescriptor>,
+  errors: Vec<SarifNotification>,
+}
+
+impl SarifLintReporter {
+  fn new() -> SarifLintReporter {
+    SarifLintReporter {
+      results: Vec::new(),
+      rules: BTreeMap::new(),
+      errors: Vec::new(),
+    }
+  }
+
+  fn to_log(&self) -> SarifLog {
+    let mut results = self.results.clone();
+    // sort for a deterministic output
+    results.sort_by(|a, b| {
+      let key = |result: &SarifResult| {
+        result.locations.first().map(|location| {
+          let location = &location.physical_location;
+          (
+            location.artifact_location.uri.clone(),
+            location
+              .region
+              .as_ref()
+              .map(|region| (region.start_line, region.start_column)),
+          )
+        })
+      };
+      key(a).cmp(&key(b))
+    });
+    SarifLog::new(vec![SarifRun {
+      tool: SarifTool {
+        driver: SarifToolComponent {
+          name: "deno lint".to_string(),
+          information_uri: "https://docs.deno.com/runtime/manual/tools/linter"
+            .to_string(),
+          version: crate::version::deno().to_string(),
+          rules: self.rules.values().cloned().collect(),
+        },
+      },
+      results,
+      invocations: vec![SarifInvocation {
+        execution_successful: self.errors.is_empty(),
+        tool_execution_notifications: self.errors.clone(),
+      }],
+    }])
+  }
+}
+
+fn sarif_rule_descriptor(
+  code: &str,
+  docs_url: Option<String>,
+) -> SarifReportingDescriptor {
+  let rule = rules::get_all_rules()
+    .into_iter()
+    .find(|rule| rule.code() == code);
+  SarifReportingDescriptor {
+    id: code.to_string(),
+    short_description: rule
+      .and_then(|rule| rule.docs().trim().lines().next())
+      .filter(|line| !line.is_empty())
+      .map(SarifMessage::new),
+    help_uri: docs_url,
+    properties: rule.filter(|rule| !rule.tags().is_empty()).map(|rule| {
+      SarifPropertyBag {
+        tags: rule.tags().iter().map(|tag| tag.to_string()).collect(),
+      }
+    }),
+  }
+}
+
+fn sarif_region(text_info: &SourceTextInfo, range: SourceRange) -> SarifRegion {
+  let start = text_info.line_and_column_display(range.start);
+  let end = text_info.line_and_column_display(range.end);
+  SarifRegion {
+    start_line: start.line_number,
+    start_column: start.column_number,
+    end_line: end.line_number,
+    end_column: end.column_number,
+  }
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: LintOrCliDiagnostic) {
+    let code = d.code().to_string();
+    if !self.rules.contains_key(&code) {
+      let descriptor =
+        sarif_rule_descriptor(&code, d.docs_url().map(|url| url.to_string()));
+      self.rules.insert(code.clone(), descriptor);
+    }
+
+    let uri = d.specifier().to_string();
+    let fixes = match d {
+      LintOrCliDiagnostic::Lint(d) => d
+        .fixes
+        .iter()
+        .map(|fix| SarifFix {
+          description: SarifMessage::new(fix.description.to_string()),
+          artifact_changes: vec![SarifArtifactChange {
+            artifact_location: SarifArtifactLocation { uri: uri.clone() },
+            replacements: fix
+              .changes
+              .iter()
+              .map(|change| SarifReplacement {
+                deleted_region: sarif_region(&d.text_info, change.range),
+                inserted_content: SarifArtifactContent {
+                  text: change.new_text.to_string(),
+                },
+              })
+              .collect(),
+          }],
+        })
+        .collect(),
+      LintOrCliDiagnostic::FastCheck(_) => Vec::new(),
+    };
+    let message = match d.hint() {
+      Some(hint) => format!("{}\n\nhint: {}", d.message(), hint),
+      None => d.message().to_string(),
+    };
+    self.results.push(SarifResult {
+      rule_id: code,
+      level: match d.level() {
+        deno_ast::diagnostics::DiagnosticLevel::Error => SarifLevel::Error,
+        deno_ast::diagnostics::DiagnosticLevel::Warning => SarifLevel::Warning,
+      },
+      message: SarifMessage::new(message),
+      locations: vec![SarifLocation {
+        physical_location: SarifPhysicalLocation {
+          artifact_location: SarifArtifactLocation { uri },
+          region: d
+            .range()
+            .map(|(text_info, range)| sarif_region(text_info, range)),
+        },
+        message: None,
+      }],
+      related_locations: Vec::new(),
+      fixes,
+    });
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    let uri = ModuleSpecifier::from_file_path(file_path)
+      .map(|url| url.to_string())
+      .unwrap_or_else(|_| file_path.to_string());
+    self.errors.push(SarifNotification {
+      level: SarifLevel::Error,
+      message: SarifMessage::new(err.to_string()),
+      locations: vec![SarifLocation {
+        physical_location: SarifPhysicalLocation {
+          artifact_location: SarifArtifactLocation { uri },
+          region: None,
+        },
+        message: None,
+      }],
+    });
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    let json = serde_json::to_string_pretty(&self.to_log());
+    #[allow(clippy::print_stdout)]
+    {
+      println!("{}", json.unwrap());
+    }
+  }
+}
+
 fn sort_diagnostics(diagnostics: &mut [JsonLintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
   diagnostics.sort_by(|a, b| {
@@ -987,11 +1302,16 @@ fn sort_diagnostics(diagnostics: &mut [JsonLintDiagnostic]) {
 }
 
 fn get_config_rules_err_empty(
-  rules: LintRulesConfig,
+  lint_options: &LintOptions,
   maybe_config_file: Option<&deno_config::ConfigFile>,
 ) -> Result<ConfiguredRules, AnyError> {
-  let lint_rules = get_configured_rules(rules, maybe_config_file);
-  if lint_rules.rules.is_empty() {
+  let mut lint_rules =
+    get_configured_rules(lint_options.rules.clone(), maybe_config_file);
+  lint_rules.plugins = plugins::resolve_lint_plugins(lint_options)?;
+  if lint_rules.rules.is_empty()
+    && lint_rules.plugins.is_empty()
+    && lint_rules.type_aware_rules.is_empty()
+  {
     bail!("No rules have been configured")
   }
   Ok(lint_rules)
@@ -1002,6 +1322,12 @@ pub struct ConfiguredRules {
   pub rules: Vec<&'static dyn LintRule>,
   // cli specific rules
   pub no_slow_types: bool,
+  /// Modules with rules written in JavaScript or TypeScript, from the
+  /// "lint.plugins" configuration.
+  pub plugins: Vec<ModuleSpecifier>,
+  /// Rules that run on the TypeScript program, which are only enabled when
+  /// included explicitly.
+  pub type_aware_rules: Vec<&'static str>,
 }
 
 impl Default for ConfiguredRules {
@@ -1011,7 +1337,18 @@ impl Default for ConfiguredRules {
 }
 
 impl ConfiguredRules {
-  fn incremental_cache_state(&self) -> Vec<&str> {
+  /// Gets the host running the plugins, if any are configured.
+  pub fn plugin_host(&self) -> Result<Option<Arc<LintPluginHost>>, AnyError> {
+    if self.plugins.is_empty() {
+      return Ok(None);
+    }
+    LintPluginHost::get_or_create(&self.plugins).map(Some)
+  }
+
+  fn incremental_cache_state(
+    &self,
+    plugin_host: Option<&LintPluginHost>,
+  ) -> (Vec<&str>, Option<u64>) {
     // use a hash of the rule names in order to bust the cache
     let mut names = self.rules.iter().map(|r| r.code()).collect::<Vec<_>>();
     // ensure this is stable by sorting it
@@ -1019,7 +1356,8 @@ impl ConfiguredRules {
     if self.no_slow_types {
       names.push("no-slow-types");
     }
-    names
+    // the plugins can change without their specifiers changing
+    (names, plugin_host.map(|h| h.source_hash()))
   }
 }
 
@@ -1036,6 +1374,16 @@ pub fn get_configured_rules(
       .as_ref()
       .map(|exclude| exclude.iter().any(|i| i == NO_SLOW_TYPES_NAME))
       .unwrap_or(false);
+  let type_aware_rules = type_aware::TYPE_AWARE_RULES
+    .iter()
+    .copied()
+    .filter(|code| {
+      rules
+        .include
+        .as_ref()
+        .is_some_and(|include| include.iter().any(|i| i == code))
+    })
+    .collect();
   let rules = rules::get_filtered_rules(
     rules
       .tags
@@ -1049,13 +1397,17 @@ pub fn get_configured_rules(
     rules.include.map(|include| {
       include
         .into_iter()
-        .filter(|c| c != NO_SLOW_TYPES_NAME)
+        .filter(|c| {
+          c != NO_SLOW_TYPES_NAME && !type_aware::is_type_aware_rule_code(c)
+        })
         .collect()
     }),
   );
   ConfiguredRules {
     rules,
     no_slow_types,
+    plugins: Vec::new(),
+    type_aware_rules,
   }
 }
 
@@ -1077,6 +1429,61 @@ mod test {
   use super::*;
   use crate::args::LintRulesConfig;
 
+  #[test]
+  fn sarif_reporter() {
+    let linter = create_linter(get_recommended_rules());
+    let (_, diagnostics) = linter
+      .lint_file(LintFileOptions {
+        specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+        media_type: MediaType::TypeScript,
+        source_code: "window.value;\n".to_string(),
+        config: LintConfig {
+          default_jsx_factory: None,
+          default_jsx_fragment_factory: None,
+        },
+      })
+      .unwrap();
+    let mut reporter = SarifLintReporter::new();
+    for d in &diagnostics {
+      reporter.visit_diagnostic(LintOrCliDiagnostic::Lint(d));
+    }
+    reporter.visit_error("/invalid.ts", &generic_error("Expected ';'"));
+
+    let log = reporter.to_log();
+    assert_eq!(log.version, "2.1.0");
+    let run = &log.runs[0];
+    let rule = run
+      .tool
+      .driver
+      .rules
+      .iter()
+      .find(|rule| rule.id == "no-window")
+      .unwrap();
+    assert!(rule.help_uri.is_some());
+    assert!(rule
+      .properties
+      .as_ref()
+      .unwrap()
+      .tags
+      .contains(&"recommended".to_string()));
+
+    let result = run
+      .results
+      .iter()
+      .find(|result| result.rule_id == "no-window")
+      .unwrap();
+    let location = &result.locations[0].physical_location;
+    assert_eq!(location.artifact_location.uri, "file:///mod.ts");
+    let region = location.region.as_ref().unwrap();
+    assert_eq!((region.start_line, region.start_column), (1, 1));
+    let replacement = &result.fixes[0].artifact_changes[0].replacements[0];
+    assert_eq!(replacement.inserted_content.text, "globalThis");
+
+    let invocation = &run.invocations[0];
+    assert!(!invocation.execution_successful);
+    assert_eq!(invocation.tool_execution_notifications.len(), 1);
+  }
+
   #[test]
   fn recommended_rules_when_no_tags_in_config() {
     let rules_config = LintRulesConfig {
@@ -1099,4 +1506,27 @@ mod test {
     recommended_rule_names.sort();
     assert_eq!(rule_names, recommended_rule_names);
   }
+
+  #[test]
+  fn type_aware_rules_only_when_included() {
+    let rules = get_configured_rules(Default::default(), None);
+    assert!(rules.type_aware_rules.is_empty());
+
+    let rules_config = LintRulesConfig {
+      exclude: None,
+      include: Some(vec![
+        "no-floating-promises".to_string(),
+        "no-debugger".to_string(),
+      ]),
+      tags: Some(Vec::new()),
+    };
+    let rules = get_configured_rules(rules_config, None);
+    assert_eq!(rules.type_aware_rules, vec!["no-floating-promises"]);
+    let rule_names = rules
+      .rules
+      .into_iter()
+      .map(|r| r.code().to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(rule_names, vec!["no-debugger"]);
+  }
 }
@@ -0,0 +1,146 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Host of the lint plugins, see plugins.rs. This runs as a classic script in
+// a runtime without the Deno APIs, so it only relies on `Deno.core`.
+
+((globalThis) => {
+  const core = globalThis.Deno.core;
+
+  function format(args) {
+    return args.map((arg) =>
+      typeof arg === "string" ? arg : JSON.stringify(arg)
+    ).join(" ");
+  }
+
+  // plugins can only print to stderr, to not mix with the output of the
+  // reporters
+  globalThis.console = {
+    log: (...args) => core.print(format(args) + "\n", true),
+    info: (...args) => core.print(format(args) + "\n", true),
+    debug: (...args) => core.print(format(args) + "\n", true),
+    warn: (...args) => core.print(format(args) + "\n", true),
+    error: (...args) => core.print(format(args) + "\n", true),
+  };
+
+  /** @type {{ code: string, docsUrl: string | null, create: Function }[]} */
+  const rules = [];
+
+  function install(plugins) {
+    for (const [specifier, module] of plugins) {
+      const plugin = module.default;
+      if (
+        typeof plugin !== "object" || plugin === null ||
+        typeof plugin.name !== "string" ||
+        typeof plugin.rules !== "object" || plugin.rules === null
+      ) {
+        throw new TypeError(
+          `Lint plugin ${specifier} must default export an object with a "name" and "rules".`,
+        );
+      }
+      for (const [name, rule] of Object.entries(plugin.rules)) {
+        if (typeof rule?.create !== "function") {
+          throw new TypeError(
+            `Lint rule "${plugin.name}/${name}" in ${specifier} must have a "create" function.`,
+          );
+        }
+        rules.push({
+          code: `${plugin.name}/${name}`,
+          docsUrl: typeof rule.docs === "string" ? rule.docs : null,
+          create: rule.create,
+        });
+      }
+    }
+  }
+
+  class Node {
+    constructor(nodes, text, index) {
+      const [type, start, end] = nodes[index];
+      this.type = type;
+      this.range = [start, end];
+      this.#nodes = nodes;
+      this.#text = text;
+      this.#index = index;
+    }
+
+    #nodes;
+    #text;
+    #index;
+    #children = [];
+
+    get text() {
+      return this.#text.slice(this.range[0], this.range[1]);
+    }
+
+    get parent() {
+      const parentIndex = this.#nodes[this.#index][3];
+      return parentIndex == null ? null : this.#nodes.objects[parentIndex];
+    }
+
+    get children() {
+      return this.#children;
+    }
+  }
+
+  function toRange(value, what) {
+    const range = value?.node?.range ?? value?.range;
+    if (
+      !Array.isArray(range) || range.length !== 2 ||
+      !Number.isInteger(range[0]) || !Number.isInteger(range[1])
+    ) {
+      throw new TypeError(`A lint ${what} must have a "node" or a "range".`);
+    }
+    return [range[0], range[1]];
+  }
+
+  function toFix(fix) {
+    const fixes = Array.isArray(fix) ? fix : [fix];
+    return {
+      description: fixes[0]?.description ?? "Apply the fix",
+      changes: fixes.map((change) => ({
+        range: toRange(change, "fix"),
+        text: String(change.text ?? ""),
+      })),
+    };
+  }
+
+  function run(specifier, text, nodes) {
+    // create the node objects, linking each to its parent
+    nodes.objects = [];
+    for (let i = 0; i < nodes.length; i++) {
+      const node = new Node(nodes, text, i);
+      nodes.objects.push(node);
+      const parent = node.parent;
+      if (parent !== null) {
+        parent.children.push(node);
+      }
+    }
+
+    const diagnostics = [];
+    for (const rule of rules) {
+      const context = {
+        specifier,
+        text,
+        report(report) {
+          diagnostics.push({
+            code: rule.code,
+            message: String(report.message),
+            hint: report.hint == null ? null : String(report.hint),
+            docsUrl: rule.docsUrl,
+            range: toRange(report, "report"),
+            fixes: report.fix == null ? [] : [toFix(report.fix)],
+          });
+        },
+      };
+      const visitor = rule.create(context) ?? {};
+      for (const node of nodes.objects) {
+        const visit = visitor[node.type];
+        if (typeof visit === "function") {
+          visit(node);
+        }
+      }
+    }
+    return diagnostics;
+  }
+
+  globalThis[Symbol.for("Deno.lint.plugins")] = { install, run };
+})(globalThis);
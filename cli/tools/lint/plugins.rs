@@ -0,0 +1,624 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Lint rules written in JavaScript or TypeScript, loaded from the modules
+//! listed in the "lint.plugins" configuration of deno.json.
+//!
+//! The plugins run in a JS runtime of their own on a separate thread, which
+//! has no access to the Deno APIs. Each linted file is sent to it as a flat
+//! list of AST nodes, and the rules report diagnostics back with ranges in
+//! UTF-16 code units of the file text.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use deno_ast::view::NodeTrait;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRanged;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use deno_core::ModuleLoadResponse;
+use deno_core::ModuleLoader;
+use deno_core::ModuleSource;
+use deno_core::ModuleSourceCode;
+use deno_core::ModuleType;
+use deno_core::PollEventLoopOptions;
+use deno_core::RequestedModuleType;
+use deno_core::ResolutionKind;
+use deno_core::RuntimeOptions;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintFix;
+use deno_lint::diagnostic::LintFixChange;
+use deno_runtime::tokio_util::create_basic_runtime;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::args::LintOptions;
+use crate::cache::FastInsecureHasher;
+
+/// Gets the plugin modules of the "lint.plugins" configuration, which have to
+/// be local files.
+pub fn resolve_lint_plugins(
+  lint_options: &LintOptions,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  for plugin in &lint_options.plugins {
+    if plugin.scheme() != "file" {
+      return Err(generic_error(format!(
+        "Lint plugins must be local modules, but got \"{}\"",
+        plugin
+      )));
+    }
+  }
+  Ok(lint_options.plugins.clone())
+}
+
+/// Whether a diagnostic code is of a rule of a plugin, which are reported as
+/// "<plugin>/<rule>" unlike the built-in rules.
+pub fn is_plugin_rule_code(code: &str) -> bool {
+  code.contains('/')
+}
+
+static PLUGIN_HOSTS: Lazy<
+  Mutex<HashMap<Vec<ModuleSpecifier>, Arc<LintPluginHost>>>,
+> = Lazy::new(Default::default);
+
+/// How long the plugins may take to load or to lint a single file before
+/// their isolate is terminated.
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A JS runtime with lint plugins loaded, running on its own thread.
+pub struct LintPluginHost {
+  /// Taken once the isolate was terminated, which stops the thread.
+  sender: Mutex<Option<mpsc::Sender<PluginRequest>>>,
+  isolate_handle: v8::IsolateHandle,
+  /// The plugins and the modules they import, in the order they were loaded.
+  modules: Vec<ModuleSpecifier>,
+  source_hash: u64,
+}
+
+impl std::fmt::Debug for LintPluginHost {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LintPluginHost")
+      .field("modules", &self.modules)
+      .field("source_hash", &self.source_hash)
+      .finish()
+  }
+}
+
+impl LintPluginHost {
+  /// Gets the host of the plugins, loading them the first time they're used
+  /// and again whenever one of their modules changed.
+  pub fn get_or_create(
+    plugins: &[ModuleSpecifier],
+  ) -> Result<Arc<LintPluginHost>, AnyError> {
+    let mut hosts = PLUGIN_HOSTS.lock();
+    if let Some(host) = hosts.get(plugins) {
+      if host.is_up_to_date() {
+        return Ok(host.clone());
+      }
+      // dropping the last reference to the old host stops its thread
+      hosts.remove(plugins);
+    }
+    let host = Arc::new(Self::create(plugins.to_vec())?);
+    hosts.insert(plugins.to_vec(), host.clone());
+    Ok(host)
+  }
+
+  fn create(plugins: Vec<ModuleSpecifier>) -> Result<Self, AnyError> {
+    let (sender, receiver) = mpsc::channel::<PluginRequest>();
+    let (handle_sender, handle_receiver) = mpsc::channel();
+    let (init_sender, init_receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+      let runtime = create_basic_runtime();
+      runtime.block_on(async move {
+        let module_loader = Rc::new(PluginModuleLoader::default());
+        let mut js_runtime = JsRuntime::new(RuntimeOptions {
+          module_loader: Some(module_loader.clone()),
+          ..Default::default()
+        });
+        _ = handle_sender.send(js_runtime.v8_isolate().thread_safe_handle());
+        match load_plugins(&mut js_runtime, &plugins).await {
+          Ok(()) => {
+            let modules = module_loader.modules.lock().clone();
+            let source_hash = module_loader.source_hash.lock().finish();
+            _ = init_sender.send(Ok((modules, source_hash)));
+          }
+          Err(err) => {
+            _ = init_sender.send(Err(err));
+            return;
+          }
+        }
+        // the plugins are only run synchronously, so block on the next file
+        while let Ok(request) = receiver.recv() {
+          let result = run_plugins(&mut js_runtime, &request);
+          _ = request.response.send(result);
+        }
+      });
+    });
+    let isolate_handle = handle_receiver
+      .recv()
+      .map_err(|_| anyhow!("Lint plugin thread exited unexpectedly."))?;
+    let (modules, source_hash) =
+      match init_receiver.recv_timeout(PLUGIN_TIMEOUT) {
+        Ok(result) => result?,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+          isolate_handle.terminate_execution();
+          bail!(
+            "Lint plugins took longer than {} seconds to load.",
+            PLUGIN_TIMEOUT.as_secs()
+          );
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+          bail!("Lint plugin thread exited unexpectedly.");
+        }
+      };
+    Ok(Self {
+      sender: Mutex::new(Some(sender)),
+      isolate_handle,
+      modules,
+      source_hash,
+    })
+  }
+
+  /// A hash of the source code of the plugins, which changes the outcome of
+  /// linting.
+  pub fn source_hash(&self) -> u64 {
+    self.source_hash
+  }
+
+  /// Whether the modules of the plugins are unchanged since they were loaded
+  /// and the isolate is still running.
+  fn is_up_to_date(&self) -> bool {
+    if self.sender.lock().is_none() {
+      return false;
+    }
+    let mut hasher = FastInsecureHasher::new_without_deno_version();
+    for specifier in &self.modules {
+      let Ok(path) = specifier.to_file_path() else {
+        return false;
+      };
+      let Ok(text) = std::fs::read_to_string(path) else {
+        return false;
+      };
+      hasher.write_str(specifier.as_str()).write_str(&text);
+    }
+    hasher.finish() == self.source_hash
+  }
+
+  /// Runs the rules of the plugins on a file.
+  pub fn lint(
+    &self,
+    parsed_source: &ParsedSource,
+  ) -> Result<Vec<LintDiagnostic>, AnyError> {
+    let text_info = parsed_source.text_info_lazy();
+    let text = text_info.text_str();
+    let offsets = Utf16Offsets::new(text);
+    let file_start = text_info.range().start;
+    let nodes = parsed_source.with_view(|program| {
+      let mut nodes = Vec::new();
+      serialize_node(program.as_node(), None, &mut nodes, &|range| {
+        let range = range.as_byte_range(file_start);
+        (
+          offsets.byte_to_utf16(range.start),
+          offsets.byte_to_utf16(range.end),
+        )
+      });
+      nodes
+    });
+
+    let (response_sender, response_receiver) = mpsc::channel();
+    {
+      let sender = self.sender.lock();
+      let Some(sender) = sender.as_ref() else {
+        bail!("Lint plugins were terminated after timing out.");
+      };
+      sender
+        .send(PluginRequest {
+          specifier: parsed_source.specifier().to_string(),
+          text: text.to_string(),
+          nodes: serde_json::to_string(&nodes)?,
+          response: response_sender,
+        })
+        .map_err(|_| anyhow!("Lint plugin thread exited unexpectedly."))?;
+    }
+    let diagnostics = match response_receiver.recv_timeout(PLUGIN_TIMEOUT) {
+      Ok(result) => result?,
+      Err(mpsc::RecvTimeoutError::Timeout) => {
+        // stop the plugins, which are then loaded again on the next run
+        self.isolate_handle.terminate_execution();
+        self.sender.lock().take();
+        bail!(
+          "Lint plugins took longer than {} seconds to lint {}.",
+          PLUGIN_TIMEOUT.as_secs(),
+          parsed_source.specifier()
+        );
+      }
+      Err(mpsc::RecvTimeoutError::Disconnected) => {
+        bail!("Lint plugin thread exited unexpectedly.");
+      }
+    };
+
+    let to_range =
+      |start: usize, end: usize| -> Result<SourceRange, AnyError> {
+        match (offsets.utf16_to_byte(start), offsets.utf16_to_byte(end)) {
+          (Some(start), Some(end)) if start <= end => {
+            Ok(SourceRange::new(file_start + start, file_start + end))
+          }
+          _ => Err(generic_error(format!(
+            "Lint plugin reported an invalid range: {}-{}",
+            start, end
+          ))),
+        }
+      };
+    let diagnostics = diagnostics
+      .into_iter()
+      .map(|d| {
+        Ok(LintDiagnostic {
+          specifier: parsed_source.specifier().clone(),
+          range: to_range(d.range.0, d.range.1)?,
+          text_info: text_info.clone(),
+          message: d.message,
+          code: d.code,
+          hint: d.hint,
+          fixes: d
+            .fixes
+            .into_iter()
+            .map(|fix| {
+              Ok(LintFix {
+                description: Cow::Owned(fix.description),
+                changes: fix
+                  .changes
+                  .into_iter()
+                  .map(|change| {
+                    Ok(LintFixChange {
+                      new_text: Cow::Owned(change.text),
+                      range: to_range(change.range.0, change.range.1)?,
+                    })
+                  })
+                  .collect::<Result<_, AnyError>>()?,
+              })
+            })
+            .collect::<Result<_, AnyError>>()?,
+          custom_docs_url: d.docs_url,
+          info: Vec::new(),
+        })
+      })
+      .collect::<Result<Vec<_>, AnyError>>()?;
+    Ok(filter_ignored_diagnostics(parsed_source, diagnostics))
+  }
+}
+
+/// Removes the diagnostics ignored with `deno-lint-ignore-file` or
+/// `deno-lint-ignore` comments, the same way deno_lint does for its rules.
+pub(super) fn filter_ignored_diagnostics(
+  parsed_source: &ParsedSource,
+  mut diagnostics: Vec<LintDiagnostic>,
+) -> Vec<LintDiagnostic> {
+  fn parse_directive<'a>(
+    comment_text: &'a str,
+    directive: &str,
+  ) -> Option<Vec<&'a str>> {
+    let rest = comment_text.trim_start().strip_prefix(directive)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+      return None;
+    }
+    // anything after "--" explains why the rules are ignored
+    Some(
+      rest
+        .split_whitespace()
+        .take_while(|code| !code.starts_with("--"))
+        .collect(),
+    )
+  }
+  fn is_ignored(codes: &[&str], code: &str) -> bool {
+    codes.is_empty() || codes.contains(&code)
+  }
+
+  if let Some(leading_comments) = parsed_source.get_leading_comments() {
+    for comment in leading_comments {
+      if let Some(codes) =
+        parse_directive(&comment.text, "deno-lint-ignore-file")
+      {
+        diagnostics.retain(|d| !is_ignored(&codes, &d.code));
+      }
+    }
+  }
+
+  let text_info = parsed_source.text_info_lazy();
+  let comments = parsed_source.comments().get_vec();
+  let mut ignored_lines = HashMap::new();
+  for comment in &comments {
+    if let Some(codes) = parse_directive(&comment.text, "deno-lint-ignore") {
+      let line = text_info.line_index(comment.end());
+      ignored_lines.insert(line + 1, codes);
+    }
+  }
+  diagnostics.retain(|d| {
+    let line = text_info.line_index(d.range.start);
+    !ignored_lines
+      .get(&line)
+      .is_some_and(|codes| is_ignored(codes, &d.code))
+  });
+  diagnostics
+}
+
+struct PluginRequest {
+  specifier: String,
+  text: String,
+  nodes: String,
+  response: mpsc::Sender<Result<Vec<PluginDiagnostic>, AnyError>>,
+}
+
+/// A node of the AST as `[type, start, end, parent index]`, in the order of
+/// a depth first traversal.
+#[derive(Serialize)]
+struct SerializedNode(String, usize, usize, Option<usize>);
+
+fn serialize_node(
+  node: deno_ast::view::Node,
+  parent: Option<usize>,
+  nodes: &mut Vec<SerializedNode>,
+  to_utf16_range: &impl Fn(SourceRange) -> (usize, usize),
+) {
+  let index = nodes.len();
+  let (start, end) = to_utf16_range(node.range());
+  nodes.push(SerializedNode(
+    format!("{:?}", node.kind()),
+    start,
+    end,
+    parent,
+  ));
+  for child in node.children() {
+    serialize_node(child, Some(index), nodes, to_utf16_range);
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginDiagnostic {
+  code: String,
+  message: String,
+  hint: Option<String>,
+  docs_url: Option<String>,
+  range: (usize, usize),
+  fixes: Vec<PluginFix>,
+}
+
+#[derive(Deserialize)]
+struct PluginFix {
+  description: String,
+  changes: Vec<PluginFixChange>,
+}
+
+#[derive(Deserialize)]
+struct PluginFixChange {
+  range: (usize, usize),
+  text: String,
+}
+
+/// Maps the byte offsets of a text to offsets in UTF-16 code units, which is
+/// how JavaScript indexes strings.
+pub(super) struct Utf16Offsets {
+  // the UTF-16 offset of the character at each byte
+  by_byte: Vec<usize>,
+}
+
+impl Utf16Offsets {
+  pub(super) fn new(text: &str) -> Self {
+    let mut by_byte = Vec::with_capacity(text.len() + 1);
+    let mut utf16_offset = 0;
+    for c in text.chars() {
+      for _ in 0..c.len_utf8() {
+        by_byte.push(utf16_offset);
+      }
+      utf16_offset += c.len_utf16();
+    }
+    by_byte.push(utf16_offset);
+    Self { by_byte }
+  }
+
+  fn byte_to_utf16(&self, byte_offset: usize) -> usize {
+    self.by_byte[byte_offset.min(self.by_byte.len() - 1)]
+  }
+
+  pub(super) fn utf16_to_byte(&self, utf16_offset: usize) -> Option<usize> {
+    let byte_offset = self.by_byte.partition_point(|o| *o < utf16_offset);
+    (self.by_byte.get(byte_offset) == Some(&utf16_offset))
+      .then_some(byte_offset)
+  }
+}
+
+async fn load_plugins(
+  js_runtime: &mut JsRuntime,
+  plugins: &[ModuleSpecifier],
+) -> Result<(), AnyError> {
+  js_runtime.execute_script(
+    "ext:cli/lint_plugins.js",
+    deno_core::ascii_str_include!("plugins.js"),
+  )?;
+
+  // import the plugins from a module that hands them to the host script
+  let mut code = String::new();
+  for (i, plugin) in plugins.iter().enumerate() {
+    code.push_str(&format!(
+      "import * as plugin{} from {};\n",
+      i,
+      serde_json::to_string(plugin.as_str())?
+    ));
+  }
+  code.push_str("globalThis[Symbol.for(\"Deno.lint.plugins\")].install([");
+  for (i, plugin) in plugins.iter().enumerate() {
+    code.push_str(&format!(
+      "[{}, plugin{}],",
+      serde_json::to_string(plugin.as_str())?,
+      i
+    ));
+  }
+  code.push_str("]);\n");
+  let main_specifier =
+    ModuleSpecifier::parse("ext:cli/lint_plugins_main.js").unwrap();
+  let module_id = js_runtime
+    .load_main_es_module_from_code(&main_specifier, code)
+    .await?;
+  let evaluate = js_runtime.mod_evaluate(module_id);
+  js_runtime
+    .run_event_loop(PollEventLoopOptions::default())
+    .await?;
+  evaluate.await?;
+  Ok(())
+}
+
+fn run_plugins(
+  js_runtime: &mut JsRuntime,
+  request: &PluginRequest,
+) -> Result<Vec<PluginDiagnostic>, AnyError> {
+  let code = format!(
+    "globalThis[Symbol.for(\"Deno.lint.plugins\")].run({}, {}, {})",
+    serde_json::to_string(&request.specifier)?,
+    serde_json::to_string(&request.text)?,
+    request.nodes,
+  );
+  let value = js_runtime.execute_script("ext:cli/lint_plugins_run.js", code)?;
+  let scope = &mut js_runtime.handle_scope();
+  let value = v8::Local::new(scope, value);
+  Ok(serde_v8::from_v8(scope, value)?)
+}
+
+/// Loads the plugins and the local modules they import, transpiling
+/// TypeScript.
+struct PluginModuleLoader {
+  modules: Mutex<Vec<ModuleSpecifier>>,
+  source_hash: Mutex<FastInsecureHasher>,
+}
+
+impl Default for PluginModuleLoader {
+  fn default() -> Self {
+    Self {
+      modules: Default::default(),
+      source_hash: Mutex::new(FastInsecureHasher::new_without_deno_version()),
+    }
+  }
+}
+
+impl ModuleLoader for PluginModuleLoader {
+  fn resolve(
+    &self,
+    specifier: &str,
+    referrer: &str,
+    _kind: ResolutionKind,
+  ) -> Result<ModuleSpecifier, AnyError> {
+    let specifier = deno_core::resolve_import(specifier, referrer)?;
+    if specifier.scheme() != "file" {
+      return Err(type_error(format!(
+        "Lint plugins can only import local modules, but imported {}",
+        specifier
+      )));
+    }
+    Ok(specifier)
+  }
+
+  fn load(
+    &self,
+    specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dynamic: bool,
+    _requested_module_type: RequestedModuleType,
+  ) -> ModuleLoadResponse {
+    ModuleLoadResponse::Sync(self.load_sync(specifier))
+  }
+}
+
+impl PluginModuleLoader {
+  fn load_sync(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Result<ModuleSource, AnyError> {
+    let path = specifier
+      .to_file_path()
+      .map_err(|_| type_error(format!("Invalid file path: {}", specifier)))?;
+    let text = std::fs::read_to_string(&path)
+      .with_context(|| format!("Failed reading lint plugin {}", specifier))?;
+    self
+      .source_hash
+      .lock()
+      .write_str(specifier.as_str())
+      .write_str(&text);
+    self.modules.lock().push(specifier.clone());
+
+    let media_type = MediaType::from_specifier(specifier);
+    let (module_type, code) = match media_type {
+      MediaType::Json => (ModuleType::Json, text),
+      MediaType::JavaScript | MediaType::Mjs => (ModuleType::JavaScript, text),
+      MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Jsx
+      | MediaType::Tsx => {
+        let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+          specifier: specifier.clone(),
+          text: text.into(),
+          media_type,
+          capture_tokens: false,
+          scope_analysis: false,
+          maybe_syntax: None,
+        })?;
+        let code = parsed_source
+          .transpile(&Default::default(), &Default::default())?
+          .into_source()
+          .into_string()?
+          .text;
+        (ModuleType::JavaScript, code)
+      }
+      _ => {
+        return Err(type_error(format!(
+          "Unsupported lint plugin module type: {}",
+          specifier
+        )))
+      }
+    };
+    Ok(ModuleSource::new(
+      module_type,
+      ModuleSourceCode::String(code.into()),
+      specifier,
+      None,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_utf16_offsets() {
+    let offsets = Utf16Offsets::new("a\u{e9}\u{1F600}b");
+    // a: 1 byte, é: 2 bytes, 😀: 4 bytes and 2 UTF-16 code units, b: 1 byte
+    assert_eq!(offsets.byte_to_utf16(0), 0);
+    assert_eq!(offsets.byte_to_utf16(1), 1);
+    assert_eq!(offsets.byte_to_utf16(3), 2);
+    assert_eq!(offsets.byte_to_utf16(7), 4);
+    assert_eq!(offsets.byte_to_utf16(8), 5);
+    assert_eq!(offsets.utf16_to_byte(0), Some(0));
+    assert_eq!(offsets.utf16_to_byte(2), Some(3));
+    assert_eq!(offsets.utf16_to_byte(4), Some(7));
+    assert_eq!(offsets.utf16_to_byte(5), Some(8));
+    // in the middle of a surrogate pair
+    assert_eq!(offsets.utf16_to_byte(3), None);
+    assert_eq!(offsets.utf16_to_byte(6), None);
+  }
+}
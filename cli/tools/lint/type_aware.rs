@@ -0,0 +1,119 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Lint rules that need type information, like `no-floating-promises`. They
+//! run on the TypeScript program that `deno check` builds (see `lintSourceFile`
+//! in cli/tsc/99_main_compiler.js), so they're slower than the rules of
+//! deno_lint and only run when listed in the included rules.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParseParams;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_graph::GraphKind;
+use deno_lint::diagnostic::LintDiagnostic;
+
+use super::plugins::filter_ignored_diagnostics;
+use super::plugins::Utf16Offsets;
+use crate::graph_util::ModuleGraphCreator;
+use crate::tools::check::TypeChecker;
+use crate::tsc::TypeAwareLintDiagnostic;
+
+pub const TYPE_AWARE_RULES: &[&str] = &[
+  "await-thenable",
+  "no-floating-promises",
+  "no-misused-promises",
+];
+
+pub fn is_type_aware_rule_code(code: &str) -> bool {
+  TYPE_AWARE_RULES.contains(&code)
+}
+
+/// Runs the type-aware rules on the files, returning the diagnostics of the
+/// files that have any.
+pub async fn lint_files(
+  module_graph_creator: &ModuleGraphCreator,
+  type_checker: &TypeChecker,
+  file_paths: &[PathBuf],
+  rules: &[&'static str],
+) -> Result<Vec<(PathBuf, ParsedSource, Vec<LintDiagnostic>)>, AnyError> {
+  let specifiers = file_paths
+    .iter()
+    .filter_map(|path| {
+      let specifier = ModuleSpecifier::from_file_path(path).ok()?;
+      Some((specifier, path.clone()))
+    })
+    .collect::<HashMap<_, _>>();
+  let graph = module_graph_creator
+    .create_graph(GraphKind::TypesOnly, specifiers.keys().cloned().collect())
+    .await?;
+  module_graph_creator.graph_valid(&graph)?;
+  let diagnostics_by_specifier = type_checker
+    .lint(graph, rules.iter().map(|r| r.to_string()).collect())
+    .await
+    .context("Failed running the type-aware lint rules.")?;
+
+  let mut results = Vec::new();
+  for (specifier, diagnostics) in diagnostics_by_specifier {
+    // the program also has the dependencies of the files
+    let Some(file_path) = specifiers.get(&specifier) else {
+      continue;
+    };
+    if diagnostics.is_empty() {
+      continue;
+    }
+    let text = deno_ast::strip_bom(std::fs::read_to_string(file_path)?);
+    let media_type = MediaType::from_specifier(&specifier);
+    let Ok(parsed_source) = deno_ast::parse_program(ParseParams {
+      specifier,
+      text: text.into(),
+      media_type,
+      capture_tokens: true,
+      scope_analysis: false,
+      maybe_syntax: None,
+    }) else {
+      // the syntax error is already reported by the other rules
+      continue;
+    };
+    let diagnostics = to_lint_diagnostics(&parsed_source, diagnostics);
+    if !diagnostics.is_empty() {
+      results.push((file_path.clone(), parsed_source, diagnostics));
+    }
+  }
+  Ok(results)
+}
+
+/// Converts the diagnostics reported by tsc, dropping the ones that are
+/// ignored with comments.
+pub fn to_lint_diagnostics(
+  parsed_source: &ParsedSource,
+  diagnostics: Vec<TypeAwareLintDiagnostic>,
+) -> Vec<LintDiagnostic> {
+  let text_info = parsed_source.text_info_lazy();
+  let offsets = Utf16Offsets::new(text_info.text_str());
+  let file_start = text_info.range().start;
+  let diagnostics = diagnostics
+    .into_iter()
+    .filter_map(|d| {
+      let start = offsets.utf16_to_byte(d.start)?;
+      let end = offsets.utf16_to_byte(d.end)?;
+      Some(LintDiagnostic {
+        specifier: parsed_source.specifier().clone(),
+        range: SourceRange::new(file_start + start, file_start + end),
+        text_info: text_info.clone(),
+        message: d.message,
+        code: d.code,
+        hint: d.hint,
+        fixes: Vec::new(),
+        custom_docs_url: None,
+        info: Vec::new(),
+      })
+    })
+    .collect();
+  filter_ignored_diagnostics(parsed_source, diagnostics)
+}
@@ -0,0 +1,290 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! `deno run --learn-permissions` runs a program with all the permissions,
+//! recording the ones it uses, and prints the narrowest flags that allow
+//! them when it finishes or exits with `Deno.exit()`.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_runtime::deno_permissions::learn::learned_permissions;
+use deno_runtime::deno_permissions::learn::try_learned_permissions;
+use deno_runtime::deno_permissions::learn::LearnedPermissions;
+use deno_runtime::deno_permissions::start_learning_permissions;
+use deno_runtime::deno_permissions::Descriptor;
+use deno_runtime::deno_permissions::PermissionPath;
+use deno_runtime::deno_permissions::ReadDescriptor;
+use once_cell::sync::OnceCell;
+
+use crate::args::Flags;
+use crate::args::LearnPermissionsFormat;
+use crate::args::PermissionFlags;
+
+static LEARN_OPTIONS: OnceCell<(LearnPermissionsFormat, PathBuf)> =
+  OnceCell::new();
+
+static PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts recording the used permissions. They're printed by
+/// `print_learned_permissions` or when the process exits with `Deno.exit()`.
+pub fn start_learning(format: LearnPermissionsFormat, initial_cwd: PathBuf) {
+  if LEARN_OPTIONS.set((format, initial_cwd)).is_err() {
+    return;
+  }
+  start_learning_permissions();
+  // SAFETY: the function doesn't unwind and only uses statics
+  unsafe {
+    libc::atexit(print_learned_permissions_on_exit);
+  }
+}
+
+/// Prints the permissions used by the program once it finished.
+pub fn print_learned_permissions() {
+  print_report(&learned_permissions());
+}
+
+extern "C" fn print_learned_permissions_on_exit() {
+  // the exit can happen while another thread records a permission, which
+  // would never release the lock
+  if let Some(learned) = try_learned_permissions() {
+    print_report(&learned);
+  }
+}
+
+fn print_report(learned: &LearnedPermissions) {
+  let Some((format, initial_cwd)) = LEARN_OPTIONS.get() else {
+    return;
+  };
+  if PRINTED.swap(true, Ordering::SeqCst) {
+    return;
+  }
+  let flags = learned_permission_flags(learned, initial_cwd);
+  let text = if !flags.has_permission() {
+    "The program didn't use any permissions.".to_string()
+  } else {
+    match format {
+      LearnPermissionsFormat::Flags => format!(
+        "Permissions used by the program:\n  {}",
+        Flags {
+          permissions: flags,
+          ..Default::default()
+        }
+        .to_permission_args()
+        .join(" ")
+      ),
+      LearnPermissionsFormat::Config => format!(
+        "Permissions used by the program, as a permission set of the config file:\n{}",
+        permission_set_config(&flags)
+      ),
+    }
+  };
+  // the output of the program might still be in use, so ignore errors
+  let _ = writeln!(std::io::stderr(), "{}", text);
+}
+
+/// Converts the used permissions to the narrowest flags that allow them.
+fn learned_permission_flags(
+  learned: &LearnedPermissions,
+  initial_cwd: &Path,
+) -> PermissionFlags {
+  let list = |kind: &str, items: BTreeSet<String>| {
+    if learned.all.contains(kind) {
+      Some(Vec::new())
+    } else if items.is_empty() {
+      None
+    } else {
+      Some(items.into_iter().collect())
+    }
+  };
+  let paths = |kind: &str, paths: Vec<PathBuf>| {
+    list(
+      kind,
+      collapse_paths(paths)
+        .iter()
+        .map(|path| display_path(path, initial_cwd))
+        .collect(),
+    )
+  };
+  PermissionFlags {
+    allow_read: paths(
+      "read",
+      learned
+        .read
+        .iter()
+        .map(|d| d.0.as_path().to_path_buf())
+        .collect(),
+    ),
+    allow_write: paths(
+      "write",
+      learned
+        .write
+        .iter()
+        .map(|d| d.0.as_path().to_path_buf())
+        .collect(),
+    ),
+    allow_ffi: paths(
+      "ffi",
+      learned
+        .ffi
+        .iter()
+        .map(|d| d.0.as_path().to_path_buf())
+        .collect(),
+    ),
+    // the hosts are kept with their exact port
+    allow_net: list("net", learned.net.iter().map(|d| d.to_string()).collect()),
+    allow_env: list(
+      "env",
+      learned.env.iter().map(|d| d.as_ref().to_string()).collect(),
+    ),
+    allow_run: list("run", learned.run.iter().map(|d| d.to_string()).collect()),
+    allow_sys: list("sys", learned.sys.iter().map(|d| d.0.clone()).collect()),
+    allow_hrtime: learned.all.contains("hrtime"),
+    ..Default::default()
+  }
+}
+
+/// Replaces the paths in the same directory by the directory, and removes
+/// the paths within other paths.
+fn collapse_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+  let mut by_dir = BTreeMap::<PathBuf, Vec<PathBuf>>::new();
+  let mut collapsed = Vec::new();
+  for path in paths {
+    match path.parent() {
+      // never collapse to the root of the file system
+      Some(dir) if dir.parent().is_some() => {
+        by_dir.entry(dir.to_path_buf()).or_default().push(path);
+      }
+      _ => collapsed.push(path),
+    }
+  }
+  for (dir, paths) in by_dir {
+    if paths.len() > 1 {
+      collapsed.push(dir);
+    } else {
+      collapsed.extend(paths);
+    }
+  }
+  collapsed.sort();
+  collapsed.dedup();
+  let descriptors = collapsed
+    .into_iter()
+    .map(|path| ReadDescriptor(PermissionPath::Literal(path)))
+    .collect::<Vec<_>>();
+  descriptors
+    .iter()
+    .filter(|desc| {
+      !descriptors
+        .iter()
+        .any(|other| other != *desc && other.stronger_than(desc))
+    })
+    .map(|desc| desc.0.as_path().to_path_buf())
+    .collect()
+}
+
+/// Shows the paths in the current directory relative to it.
+fn display_path(path: &Path, initial_cwd: &Path) -> String {
+  match path.strip_prefix(initial_cwd) {
+    Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+    Ok(relative) => format!("./{}", relative.display()),
+    Err(_) => path.display().to_string(),
+  }
+}
+
+/// The permissions as the "permissions" section of a config file.
+fn permission_set_config(flags: &PermissionFlags) -> String {
+  let mut set = serde_json::Map::new();
+  let lists = [
+    ("read", &flags.allow_read),
+    ("write", &flags.allow_write),
+    ("net", &flags.allow_net),
+    ("env", &flags.allow_env),
+    ("run", &flags.allow_run),
+    ("ffi", &flags.allow_ffi),
+    ("sys", &flags.allow_sys),
+  ];
+  for (kind, list) in lists {
+    match list {
+      Some(list) if list.is_empty() => {
+        set.insert(kind.to_string(), json!(true));
+      }
+      Some(list) => {
+        set.insert(kind.to_string(), json!(list));
+      }
+      None => {}
+    }
+  }
+  if flags.allow_hrtime {
+    set.insert("hrtime".to_string(), json!(true));
+  }
+  serde_json::to_string_pretty(&json!({ "permissions": { "default": set } }))
+    .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_runtime::deno_permissions::EnvDescriptor;
+  use deno_runtime::deno_permissions::NetDescriptor;
+
+  #[test]
+  fn learned_flags() {
+    let cwd = if cfg!(windows) {
+      PathBuf::from("C:\\project")
+    } else {
+      PathBuf::from("/project")
+    };
+    let mut learned = LearnedPermissions::default();
+    for path in [
+      "data/a.json",
+      "data/b.json",
+      "data/nested/c.json",
+      "main.ts",
+    ] {
+      learned
+        .read
+        .insert(ReadDescriptor(PermissionPath::Literal(cwd.join(path))));
+    }
+    learned
+      .write
+      .insert(deno_runtime::deno_permissions::WriteDescriptor(
+        PermissionPath::Literal(cwd.join("out.txt")),
+      ));
+    learned
+      .net
+      .insert("api.example.com:443".parse::<NetDescriptor>().unwrap());
+    learned
+      .net
+      .insert("localhost:8000".parse::<NetDescriptor>().unwrap());
+    learned.env.insert(EnvDescriptor::new("PORT"));
+    learned.env.insert(EnvDescriptor::new("HOME"));
+    learned.all.insert("sys");
+
+    let flags = learned_permission_flags(&learned, &cwd);
+    assert_eq!(
+      flags.allow_read,
+      Some(vec!["./data".to_string(), "./main.ts".to_string()])
+    );
+    assert_eq!(flags.allow_write, Some(vec!["./out.txt".to_string()]));
+    assert_eq!(
+      flags.allow_net,
+      Some(vec![
+        "api.example.com:443".to_string(),
+        "localhost:8000".to_string()
+      ])
+    );
+    assert_eq!(
+      flags.allow_env,
+      Some(vec!["HOME".to_string(), "PORT".to_string()])
+    );
+    assert_eq!(flags.allow_sys, Some(vec![]));
+    assert_eq!(flags.allow_run, None);
+    assert!(!flags.allow_hrtime);
+  }
+}
@@ -17,6 +17,7 @@ use crate::util;
 use crate::util::file_watcher::WatcherRestartMode;
 
 pub mod hmr;
+pub mod learn_permissions;
 
 pub async fn run_script(
   mode: WorkerExecutionMode,
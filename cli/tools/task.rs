@@ -1,13 +1,23 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::deno_json::deno_json_deps;
+use crate::args::deno_json::TaskDefinition;
+use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::TaskFlags;
+use crate::cache::CacheDBHash;
+use crate::cache::FastInsecureHasher;
+use crate::cache::TaskCache;
+use crate::cache::TaskCacheHashes;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::npm::ManagedCliNpmResolver;
 use crate::util::fs::canonicalize_path;
+use crate::util::fs::FileCollector;
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPatternSet;
 use deno_config::workspace::TaskOrScript;
 use deno_config::workspace::Workspace;
 use deno_config::workspace::WorkspaceTasksConfig;
@@ -16,18 +26,25 @@ use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
 use deno_core::normalize_path;
+use deno_core::url::Url;
 use deno_runtime::deno_node::NodeResolver;
 use deno_semver::package::PackageNv;
 use deno_task_shell::ExecutableCommand;
 use deno_task_shell::ExecuteResult;
 use deno_task_shell::ShellCommand;
 use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellState;
 use lazy_regex::Lazy;
 use regex::Regex;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::future::Future;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -76,31 +93,130 @@ pub async fn execute_script(
 
   let npm_resolver = factory.npm_resolver().await?;
   let node_resolver = factory.node_resolver().await?;
-  let env_vars = real_env_vars();
+  let task_runner = TaskRunner {
+    cli_options,
+    npm_resolver: npm_resolver.as_ref(),
+    node_resolver,
+    env_vars: real_env_vars(),
+    task_cache: TaskCache::new(factory.caches()?.task_cache_db()),
+    cwd: task_flags.cwd.as_deref(),
+  };
+
+  if task_flags.recursive {
+    return run_task_in_members(
+      &task_runner,
+      task_name,
+      task_flags.filter.as_deref(),
+      force_use_pkg_json,
+    )
+    .await;
+  }
+
+  if tasks_config.task(task_name).is_none() {
+    log::error!("Task not found: {task_name}");
+    if log::log_enabled!(log::Level::Error) {
+      print_available_tasks(
+        &mut std::io::stderr(),
+        &cli_options.workspace,
+        &tasks_config,
+      )?;
+    }
+    return Ok(1);
+  }
+
+  task_runner.run(&tasks_config, task_name).await
+}
+
+/// Runs tasks of a tasks config, along with the tasks they depend on.
+struct TaskRunner<'a> {
+  cli_options: &'a CliOptions,
+  npm_resolver: &'a dyn CliNpmResolver,
+  node_resolver: &'a NodeResolver,
+  env_vars: HashMap<String, String>,
+  task_cache: TaskCache,
+  cwd: Option<&'a str>,
+}
 
-  match tasks_config.task(task_name) {
-    Some((dir_url, task_or_script)) => match task_or_script {
+impl TaskRunner<'_> {
+  async fn run(
+    &self,
+    tasks_config: &WorkspaceTasksConfig,
+    task_name: &str,
+  ) -> Result<i32, AnyError> {
+    let cli_options = self.cli_options;
+    let npm_resolver = self.npm_resolver;
+    let Some((dir_url, task_or_script)) = tasks_config.task(task_name) else {
+      bail!("Task not found: {}", task_name);
+    };
+    match task_or_script {
       TaskOrScript::Task(_tasks, script) => {
-        let cwd = match task_flags.cwd {
+        let task_graph =
+          TaskGraphBuilder::new(cli_options, tasks_config).build(task_name)?;
+        if task_graph.len() > 1 {
+          // ensure the npm packages are installed if a dependency is a
+          // package.json script
+          if task_graph.iter().any(|node| node.is_script)
+            && cli_options.has_node_modules_dir()
+          {
+            if let Some(npm_resolver) = npm_resolver.as_managed() {
+              npm_resolver.ensure_top_level_package_json_install().await?;
+            }
+          }
+
+          let cwd = match self.cwd {
+            Some(path) => Some(
+              canonicalize_path(&PathBuf::from(path))
+                .context("failed canonicalizing --cwd")?,
+            ),
+            None => None,
+          };
+          let custom_commands =
+            resolve_custom_commands(npm_resolver, self.node_resolver)?;
+          let local = LocalSet::new();
+          return local
+            .run_until(run_task_graph(
+              &task_graph,
+              RunTaskGraphOptions {
+                task_cache: &self.task_cache,
+                cwd: cwd.as_deref(),
+                init_cwd: cli_options.initial_cwd(),
+                env_vars: self.env_vars.clone(),
+                argv: cli_options.argv(),
+                custom_commands,
+                root_node_modules_dir: npm_resolver
+                  .root_node_modules_path()
+                  .map(|p| p.as_path()),
+              },
+            ))
+            .await;
+        }
+
+        let cwd = match self.cwd {
           Some(path) => canonicalize_path(&PathBuf::from(path))
             .context("failed canonicalizing --cwd")?,
           None => normalize_path(dir_url.to_file_path().unwrap()),
         };
 
         let custom_commands =
-          resolve_custom_commands(npm_resolver.as_ref(), node_resolver)?;
-        run_task(RunTaskOptions {
-          task_name,
-          script,
-          cwd: &cwd,
-          init_cwd: cli_options.initial_cwd(),
-          env_vars,
-          argv: cli_options.argv(),
-          custom_commands,
-          root_node_modules_dir: npm_resolver
-            .root_node_modules_path()
-            .map(|p| p.as_path()),
-        })
+          resolve_custom_commands(npm_resolver, self.node_resolver)?;
+        run_task_with_cache(
+          &self.task_cache,
+          &task_graph[0],
+          cli_options.argv(),
+          run_task(RunTaskOptions {
+            task_name,
+            script,
+            cwd: &cwd,
+            init_cwd: cli_options.initial_cwd(),
+            env_vars: self.env_vars.clone(),
+            argv: cli_options.argv(),
+            custom_commands,
+            root_node_modules_dir: npm_resolver
+              .root_node_modules_path()
+              .map(|p| p.as_path()),
+            output_prefix: None,
+          }),
+        )
         .await
       }
       TaskOrScript::Script(scripts, _script) => {
@@ -112,7 +228,7 @@ pub async fn execute_script(
           }
         }
 
-        let cwd = match task_flags.cwd {
+        let cwd = match self.cwd {
           Some(path) => canonicalize_path(&PathBuf::from(path))?,
           None => normalize_path(dir_url.to_file_path().unwrap()),
         };
@@ -122,11 +238,11 @@ pub async fn execute_script(
         // dealing with package.json here and not deno.json
         let task_names = vec![
           format!("pre{}", task_name),
-          task_name.clone(),
+          task_name.to_string(),
           format!("post{}", task_name),
         ];
         let custom_commands =
-          resolve_custom_commands(npm_resolver.as_ref(), node_resolver)?;
+          resolve_custom_commands(npm_resolver, self.node_resolver)?;
         for task_name in &task_names {
           if let Some(script) = scripts.get(task_name) {
             let exit_code = run_task(RunTaskOptions {
@@ -134,12 +250,13 @@ pub async fn execute_script(
               script,
               cwd: &cwd,
               init_cwd: cli_options.initial_cwd(),
-              env_vars: env_vars.clone(),
+              env_vars: self.env_vars.clone(),
               argv: cli_options.argv(),
               custom_commands: custom_commands.clone(),
               root_node_modules_dir: npm_resolver
                 .root_node_modules_path()
                 .map(|p| p.as_path()),
+              output_prefix: None,
             })
             .await?;
             if exit_code > 0 {
@@ -150,19 +267,570 @@ pub async fn execute_script(
 
         Ok(0)
       }
-    },
-    None => {
-      log::error!("Task not found: {task_name}");
-      if log::log_enabled!(log::Level::Error) {
-        print_available_tasks(
-          &mut std::io::stderr(),
-          &cli_options.workspace,
-          &tasks_config,
-        )?;
+    }
+  }
+}
+
+/// A workspace member that defines the task being run recursively.
+struct TaskMember {
+  /// The directory of the member, relative to the workspace root.
+  label: String,
+  /// The names of the member's deno.json and package.json.
+  names: Vec<String>,
+  /// The names of the packages that the member depends on.
+  dependency_names: HashSet<String>,
+  tasks_config: WorkspaceTasksConfig,
+}
+
+impl TaskMember {
+  fn matches_filter(&self, filter: &glob::Pattern) -> bool {
+    filter.matches(&self.label)
+      || self.names.iter().any(|name| filter.matches(name))
+  }
+}
+
+/// Runs a task in each workspace member that defines it, one member after
+/// another with the members that others depend on first. Keeps going when
+/// a member fails, except for the members that depend on it.
+async fn run_task_in_members(
+  task_runner: &TaskRunner<'_>,
+  task_name: &str,
+  filter: Option<&str>,
+  force_use_pkg_json: bool,
+) -> Result<i32, AnyError> {
+  let filter = filter
+    .map(|filter| {
+      glob::Pattern::new(filter)
+        .with_context(|| format!("Invalid --filter pattern: {}", filter))
+    })
+    .transpose()?;
+  let workspace = &task_runner.cli_options.workspace;
+  let (root_url, _) = workspace.root_folder();
+  let root_dir = normalize_path(root_url.to_file_path().unwrap());
+  let patterns = FilePatterns {
+    base: root_dir.clone(),
+    include: None,
+    exclude: PathOrPatternSet::new(Vec::new()),
+  };
+
+  let mut members = Vec::new();
+  for ctx in workspace.resolve_ctxs_from_patterns(&patterns) {
+    let tasks_config = ctx.to_tasks_config()?;
+    let tasks_config = if force_use_pkg_json {
+      tasks_config.with_only_pkg_json()
+    } else {
+      tasks_config
+    };
+    let Some(member_config) = &tasks_config.member else {
+      continue;
+    };
+    let deno_json_tasks = member_config.deno_json.as_ref();
+    let pkg_json_tasks = member_config.package_json.as_ref();
+    let Some(folder_url) = deno_json_tasks
+      .map(|config| &config.folder_url)
+      .or(pkg_json_tasks.map(|config| &config.folder_url))
+    else {
+      continue;
+    };
+    let defines_task = deno_json_tasks
+      .is_some_and(|config| config.tasks.contains_key(task_name))
+      || pkg_json_tasks
+        .is_some_and(|config| config.tasks.contains_key(task_name));
+    if *folder_url == *root_url.as_ref() || !defines_task {
+      continue;
+    }
+
+    let folder_dir = normalize_path(folder_url.to_file_path().unwrap());
+    let label = folder_dir
+      .strip_prefix(&root_dir)
+      .unwrap_or(&folder_dir)
+      .to_string_lossy()
+      .replace('\\', "/");
+    let mut names = Vec::new();
+    let mut dependency_names = HashSet::new();
+    if let Some(folder) = workspace.config_folders().get(folder_url) {
+      if let Some(deno_json) = &folder.deno_json {
+        names.extend(deno_json.json.name.clone());
+        dependency_names.extend(
+          deno_json_deps(deno_json)
+            .into_iter()
+            .map(|dep| dep.req.name.to_string()),
+        );
+      }
+      if let Some(pkg_json) = &folder.pkg_json {
+        names.extend(pkg_json.name.clone());
+        dependency_names.extend(
+          pkg_json
+            .resolve_local_package_json_deps()
+            .into_iter()
+            .map(|(alias, _)| alias.to_string()),
+        );
+      }
+    }
+    let member = TaskMember {
+      label,
+      names,
+      dependency_names,
+      tasks_config,
+    };
+    if filter
+      .as_ref()
+      .is_some_and(|filter| !member.matches_filter(filter))
+    {
+      continue;
+    }
+    members.push(member);
+  }
+
+  if members.is_empty() {
+    match filter {
+      Some(filter) => log::error!(
+        "Task not found in any workspace member matching \"{}\": {}",
+        filter,
+        task_name
+      ),
+      None => {
+        log::error!("Task not found in any workspace member: {task_name}")
+      }
+    }
+    return Ok(1);
+  }
+
+  let dependencies = members
+    .iter()
+    .enumerate()
+    .map(|(index, member)| {
+      members
+        .iter()
+        .enumerate()
+        .filter(|(other_index, other)| {
+          *other_index != index
+            && other
+              .names
+              .iter()
+              .any(|name| member.dependency_names.contains(name))
+        })
+        .map(|(other_index, _)| other_index)
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  let mut exit_code = 0;
+  // members that failed or were skipped, with the reason
+  let mut failures: Vec<(usize, String)> = Vec::new();
+  for index in sort_members_topologically(&dependencies) {
+    let member = &members[index];
+    if let Some(failed_dependency) = dependencies[index]
+      .iter()
+      .find(|dep| failures.iter().any(|(failed, _)| failed == *dep))
+    {
+      failures.push((
+        index,
+        format!(
+          "skipped, its dependency {} failed",
+          members[*failed_dependency].label
+        ),
+      ));
+      continue;
+    }
+
+    log::info!(
+      "{} {}",
+      colors::green("Member"),
+      colors::cyan(&member.label)
+    );
+    let result = task_runner.run(&member.tasks_config, task_name).await;
+    let failure = match result {
+      Ok(0) => None,
+      Ok(code) => {
+        if exit_code == 0 {
+          exit_code = code;
+        }
+        Some(format!("exit code {}", code))
+      }
+      Err(err) => {
+        log::error!("{}: {:#}", colors::red_bold("error"), err);
+        if exit_code == 0 {
+          exit_code = 1;
+        }
+        Some("errored".to_string())
+      }
+    };
+    if let Some(failure) = failure {
+      failures.push((index, failure));
+    }
+  }
+
+  if !failures.is_empty() {
+    log::error!(
+      "{} Task {} failed in {} of {} workspace members:",
+      colors::red_bold("error:"),
+      task_name,
+      failures.len(),
+      members.len(),
+    );
+    for (index, reason) in &failures {
+      log::error!("  {}: {}", members[*index].label, reason);
+    }
+  }
+  Ok(exit_code)
+}
+
+/// Orders the members so that each one comes after the members it depends
+/// on, keeping the original order otherwise. Members in a dependency cycle
+/// are put last.
+fn sort_members_topologically(dependencies: &[Vec<usize>]) -> Vec<usize> {
+  let mut sorted = Vec::with_capacity(dependencies.len());
+  let mut added = vec![false; dependencies.len()];
+  loop {
+    let next = (0..dependencies.len()).find(|index| {
+      !added[*index] && dependencies[*index].iter().all(|dep| added[*dep])
+    });
+    let Some(index) = next else {
+      break;
+    };
+    added[index] = true;
+    sorted.push(index);
+  }
+  sorted.extend((0..dependencies.len()).filter(|index| !added[*index]));
+  sorted
+}
+
+/// A task to run as part of a task graph.
+#[derive(Debug)]
+struct TaskGraphNode {
+  name: String,
+  dir_url: Url,
+  /// The scripts to run one after another, which are the "pre" and "post"
+  /// scripts along with the script itself for package.json scripts.
+  scripts: Vec<(String, String)>,
+  is_script: bool,
+  /// The definition of a deno.json task, which is empty for package.json
+  /// scripts.
+  definition: TaskDefinition,
+  /// Indexes of the tasks that have to complete before this one starts.
+  dependencies: Vec<usize>,
+}
+
+/// Resolves a task and the tasks that it depends on, transitively.
+struct TaskGraphBuilder<'a> {
+  cli_options: &'a CliOptions,
+  tasks_config: &'a WorkspaceTasksConfig,
+  nodes: Vec<TaskGraphNode>,
+  node_indexes: HashMap<String, usize>,
+  // the tasks being resolved, used to detect cycles
+  stack: Vec<String>,
+}
+
+impl<'a> TaskGraphBuilder<'a> {
+  fn new(
+    cli_options: &'a CliOptions,
+    tasks_config: &'a WorkspaceTasksConfig,
+  ) -> Self {
+    Self {
+      cli_options,
+      tasks_config,
+      nodes: Vec::new(),
+      node_indexes: HashMap::new(),
+      stack: Vec::new(),
+    }
+  }
+
+  /// Returns the tasks in an order where each task comes after its
+  /// dependencies, so the task itself is last.
+  fn build(mut self, task_name: &str) -> Result<Vec<TaskGraphNode>, AnyError> {
+    self.visit(task_name, None)?;
+    Ok(self.nodes)
+  }
+
+  fn visit(
+    &mut self,
+    task_name: &str,
+    dependent: Option<&str>,
+  ) -> Result<usize, AnyError> {
+    if let Some(index) = self.node_indexes.get(task_name) {
+      return Ok(*index);
+    }
+    if let Some(position) = self.stack.iter().position(|n| n == task_name) {
+      let mut cycle = self.stack[position..].to_vec();
+      cycle.push(task_name.to_string());
+      bail!("Task dependency cycle detected: {}", cycle.join(" -> "));
+    }
+
+    let Some((dir_url, task_or_script)) = self.tasks_config.task(task_name)
+    else {
+      match dependent {
+        Some(dependent) => bail!(
+          "Task \"{}\" depends on \"{}\", which was not found",
+          dependent,
+          task_name
+        ),
+        None => bail!("Task not found: {}", task_name),
+      }
+    };
+    let dir_url = dir_url.clone();
+    let (scripts, is_script, definition) = match task_or_script {
+      TaskOrScript::Task(_tasks, script) => (
+        vec![(task_name.to_string(), script.to_string())],
+        false,
+        self.task_definition(&dir_url, task_name, script)?,
+      ),
+      TaskOrScript::Script(scripts, _script) => {
+        let scripts = [
+          format!("pre{}", task_name),
+          task_name.to_string(),
+          format!("post{}", task_name),
+        ]
+        .into_iter()
+        .filter_map(|name| {
+          let script = scripts.get(&name)?.to_string();
+          Some((name, script))
+        })
+        .collect();
+        (scripts, true, TaskDefinition::default())
+      }
+    };
+    self.stack.push(task_name.to_string());
+    let dependencies = definition
+      .dependencies
+      .iter()
+      .map(|name| self.visit(name, Some(task_name)))
+      .collect::<Result<Vec<_>, _>>()?;
+    self.stack.pop();
+
+    let index = self.nodes.len();
+    self.nodes.push(TaskGraphNode {
+      name: task_name.to_string(),
+      dir_url,
+      scripts,
+      is_script,
+      definition,
+      dependencies,
+    });
+    self.node_indexes.insert(task_name.to_string(), index);
+    Ok(index)
+  }
+
+  /// Gets the definition of a task of the deno.json in a folder, which only
+  /// has the command unless the task is in the object form.
+  fn task_definition(
+    &self,
+    dir_url: &Url,
+    task_name: &str,
+    command: &str,
+  ) -> Result<TaskDefinition, AnyError> {
+    let maybe_config_file = self
+      .cli_options
+      .workspace
+      .config_folders()
+      .get(dir_url)
+      .and_then(|folder| folder.deno_json.as_ref());
+    let maybe_definition = match maybe_config_file {
+      Some(config_file) => self
+        .cli_options
+        .config_sections(config_file)
+        .to_task_definition(task_name)?,
+      None => None,
+    };
+    Ok(maybe_definition.unwrap_or_else(|| TaskDefinition {
+      command: command.to_string(),
+      ..Default::default()
+    }))
+  }
+}
+
+struct RunTaskGraphOptions<'a> {
+  task_cache: &'a TaskCache,
+  cwd: Option<&'a Path>,
+  init_cwd: &'a Path,
+  env_vars: HashMap<String, String>,
+  argv: &'a [String],
+  custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
+  root_node_modules_dir: Option<&'a Path>,
+}
+
+/// Runs the tasks of a graph, starting each task once its dependencies
+/// completed. Tasks that don't depend on each other run in parallel, with
+/// their output prefixed by the task name.
+async fn run_task_graph(
+  nodes: &[TaskGraphNode],
+  opts: RunTaskGraphOptions<'_>,
+) -> Result<i32, AnyError> {
+  let opts = &opts;
+  let root_index = nodes.len() - 1;
+  let max_parallel = std::thread::available_parallelism()
+    .map(|n| n.get())
+    .unwrap_or(1);
+  let mut started = vec![false; nodes.len()];
+  let mut completed = vec![false; nodes.len()];
+  let mut running = FuturesUnordered::new();
+  let mut exit_code = 0;
+  let mut error = None;
+
+  loop {
+    // don't start any more tasks once one failed, but wait for the running
+    // ones to finish
+    if exit_code == 0 && error.is_none() {
+      for (index, node) in nodes.iter().enumerate() {
+        if running.len() >= max_parallel {
+          break;
+        }
+        if started[index] || !node.dependencies.iter().all(|i| completed[*i]) {
+          continue;
+        }
+        started[index] = true;
+        running.push(async move {
+          (index, run_graph_node(node, index == root_index, opts).await)
+        });
+      }
+    }
+
+    let Some((index, result)) = running.next().await else {
+      break;
+    };
+    completed[index] = true;
+    match result {
+      Ok(node_exit_code) => {
+        if node_exit_code != 0 && exit_code == 0 {
+          exit_code = node_exit_code;
+        }
+      }
+      Err(err) => {
+        if error.is_none() {
+          error = Some(err);
+        }
+      }
+    }
+  }
+
+  match error {
+    Some(err) => Err(err),
+    None => Ok(exit_code),
+  }
+}
+
+async fn run_graph_node(
+  node: &TaskGraphNode,
+  is_root: bool,
+  opts: &RunTaskGraphOptions<'_>,
+) -> Result<i32, AnyError> {
+  let cwd = match opts.cwd {
+    Some(cwd) => cwd.to_path_buf(),
+    None => normalize_path(node.dir_url.to_file_path().unwrap()),
+  };
+  // the arguments are only for the task that was asked for
+  let argv = if is_root { opts.argv } else { &[] };
+  run_task_with_cache(opts.task_cache, node, argv, async {
+    for (task_name, script) in &node.scripts {
+      let exit_code = execute_task(RunTaskOptions {
+        task_name,
+        script,
+        cwd: &cwd,
+        init_cwd: opts.init_cwd,
+        env_vars: opts.env_vars.clone(),
+        argv,
+        custom_commands: opts.custom_commands.clone(),
+        root_node_modules_dir: opts.root_node_modules_dir,
+        output_prefix: Some(&node.name),
+      })
+      .await?;
+      if exit_code > 0 {
+        return Ok(exit_code);
       }
-      Ok(1)
+    }
+    Ok::<_, AnyError>(0)
+  })
+  .await
+}
+
+/// Runs a task, unless it declares "inputs" and they and its "outputs"
+/// didn't change since its last successful run.
+async fn run_task_with_cache(
+  task_cache: &TaskCache,
+  node: &TaskGraphNode,
+  argv: &[String],
+  run: impl Future<Output = Result<i32, AnyError>>,
+) -> Result<i32, AnyError> {
+  if node.definition.inputs.is_empty() {
+    return run.await;
+  }
+
+  let folder = node.dir_url.to_string();
+  let dir = normalize_path(node.dir_url.to_file_path().unwrap());
+  let inputs_hash = {
+    let mut hasher = FastInsecureHasher::new_deno_versioned();
+    // a change to the task itself needs to run it again
+    for (task_name, script) in &node.scripts {
+      hasher.write_str(task_name).write_str(script);
+    }
+    for arg in argv {
+      hasher.write_str(arg);
+    }
+    hasher.write_u64(hash_files(&dir, &node.definition.inputs)?);
+    CacheDBHash::new(hasher.finish())
+  };
+  let outputs_hash = || -> Result<CacheDBHash, AnyError> {
+    Ok(CacheDBHash::new(hash_files(
+      &dir,
+      &node.definition.outputs,
+    )?))
+  };
+
+  if let Some(hashes) = task_cache.get_hashes(&folder, &node.name) {
+    if hashes.inputs == inputs_hash && hashes.outputs == outputs_hash()? {
+      log::info!(
+        "{} {} {}",
+        colors::green("Task"),
+        colors::cyan(&node.name),
+        colors::gray("skipped, inputs and outputs are unchanged"),
+      );
+      return Ok(0);
     }
   }
+
+  let exit_code = run.await?;
+  if exit_code == 0 {
+    task_cache.set_hashes(
+      &folder,
+      &node.name,
+      TaskCacheHashes {
+        inputs: inputs_hash,
+        outputs: outputs_hash()?,
+      },
+    );
+  } else {
+    task_cache.remove_hashes(&folder, &node.name);
+  }
+  Ok(exit_code)
+}
+
+/// Hashes the paths and contents of the files matching the globs.
+fn hash_files(dir: &Path, globs: &[String]) -> Result<u64, AnyError> {
+  let mut hasher = FastInsecureHasher::new_without_deno_version();
+  if globs.is_empty() {
+    return Ok(hasher.finish());
+  }
+  let file_patterns = FilePatterns {
+    base: dir.to_path_buf(),
+    include: Some(PathOrPatternSet::from_include_relative_path_or_patterns(
+      dir, globs,
+    )?),
+    exclude: PathOrPatternSet::new(Vec::new()),
+  };
+  let mut paths = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .collect_file_patterns(file_patterns)?;
+  paths.sort();
+  for path in paths {
+    let bytes = std::fs::read(&path)
+      .with_context(|| format!("Failed reading {}", path.display()))?;
+    let relative_path = path.strip_prefix(dir).unwrap_or(&path);
+    hasher
+      .write_str(&relative_path.to_string_lossy())
+      .write_u64(bytes.len() as u64)
+      .write(&bytes);
+  }
+  Ok(hasher.finish())
 }
 
 struct RunTaskOptions<'a> {
@@ -174,23 +842,111 @@ struct RunTaskOptions<'a> {
   argv: &'a [String],
   custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
   root_node_modules_dir: Option<&'a Path>,
+  /// Prefixes each line of output, for tasks that run in parallel.
+  output_prefix: Option<&'a str>,
 }
 
 async fn run_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
+  let local = LocalSet::new();
+  local.run_until(execute_task(opts)).await
+}
+
+/// Runs a task script. Needs to run in a `LocalSet`.
+async fn execute_task(opts: RunTaskOptions<'_>) -> Result<i32, AnyError> {
   let script = get_script_with_args(opts.script, opts.argv);
   output_task(opts.task_name, &script);
   let seq_list = deno_task_shell::parser::parse(&script)
     .with_context(|| format!("Error parsing script '{}'.", opts.task_name))?;
   let env_vars =
     prepare_env_vars(opts.env_vars, opts.init_cwd, opts.root_node_modules_dir);
-  let local = LocalSet::new();
-  let future = deno_task_shell::execute(
+  let Some(output_prefix) = opts.output_prefix else {
+    return Ok(
+      deno_task_shell::execute(
+        seq_list,
+        env_vars,
+        opts.cwd,
+        opts.custom_commands,
+      )
+      .await,
+    );
+  };
+
+  let prefix = format!("{} ", colors::cyan(format!("[{}]", output_prefix)));
+  let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+  let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+  let stdout_handle = tokio::task::spawn_blocking({
+    let prefix = prefix.clone();
+    move || {
+      let mut writer = PrefixedWriter::new(prefix, std::io::stdout());
+      stdout_reader.pipe_to(&mut writer)?;
+      writer.flush()
+    }
+  });
+  let stderr_handle = tokio::task::spawn_blocking(move || {
+    let mut writer = PrefixedWriter::new(prefix, std::io::stderr());
+    stderr_reader.pipe_to(&mut writer)?;
+    writer.flush()
+  });
+  let state = ShellState::new(env_vars, opts.cwd, opts.custom_commands);
+  let exit_code = deno_task_shell::execute_with_pipes(
     seq_list,
-    env_vars,
-    opts.cwd,
-    opts.custom_commands,
-  );
-  Ok(local.run_until(future).await)
+    state,
+    ShellPipeReader::stdin(),
+    stdout_writer,
+    stderr_writer,
+  )
+  .await;
+  stdout_handle.await??;
+  stderr_handle.await??;
+  Ok(exit_code)
+}
+
+/// Writes the output of a task line by line with a prefix, so that the
+/// output of tasks that run in parallel can be told apart.
+struct PrefixedWriter<W: Write> {
+  prefix: String,
+  inner: W,
+  line: Vec<u8>,
+}
+
+impl<W: Write> PrefixedWriter<W> {
+  fn new(prefix: String, inner: W) -> Self {
+    Self {
+      prefix,
+      inner,
+      line: Vec::new(),
+    }
+  }
+
+  fn write_line(&mut self) -> std::io::Result<()> {
+    let mut output = Vec::with_capacity(self.prefix.len() + self.line.len());
+    output.extend_from_slice(self.prefix.as_bytes());
+    output.append(&mut self.line);
+    if output.last() != Some(&b'\n') {
+      output.push(b'\n');
+    }
+    // write the whole line at once, so it doesn't interleave with others
+    self.inner.write_all(&output)
+  }
+}
+
+impl<W: Write> Write for PrefixedWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    for line in buf.split_inclusive(|b| *b == b'\n') {
+      self.line.extend_from_slice(line);
+      if line.ends_with(b"\n") {
+        self.write_line()?;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    if !self.line.is_empty() {
+      self.write_line()?;
+    }
+    self.inner.flush()
+  }
 }
 
 fn get_script_with_args(script: &str, argv: &[String]) -> String {
@@ -309,7 +1065,7 @@ fn print_available_tasks(
                 (
                   is_root,
                   false,
-                  (k, Cow::Owned(deno_config::Task::Definition(v.clone()))),
+                  (k, Cow::Owned(deno_config::Task::Definition(v.into()))),
                 )
               })
             })
@@ -622,6 +1378,25 @@ fn resolve_managed_npm_commands(
 mod test {
   use super::*;
 
+  #[test]
+  fn test_sort_members_topologically() {
+    assert_eq!(sort_members_topologically(&[]), Vec::<usize>::new());
+    assert_eq!(
+      sort_members_topologically(&[vec![], vec![], vec![]]),
+      vec![0, 1, 2]
+    );
+    // 0 depends on 2, which depends on 1
+    assert_eq!(
+      sort_members_topologically(&[vec![2], vec![], vec![1]]),
+      vec![1, 2, 0]
+    );
+    // 1 and 2 depend on each other
+    assert_eq!(
+      sort_members_topologically(&[vec![], vec![2], vec![1], vec![0]]),
+      vec![0, 3, 1, 2]
+    );
+  }
+
   #[test]
   fn test_prepend_to_path() {
     let mut env_vars = HashMap::new();
@@ -650,6 +1425,19 @@ mod test {
     );
   }
 
+  #[test]
+  fn test_prefixed_writer() {
+    let mut output = Vec::new();
+    let mut writer = PrefixedWriter::new("[a] ".to_string(), &mut output);
+    writer.write_all(b"one\ntw").unwrap();
+    writer.write_all(b"o\n\nthree").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      "[a] one\n[a] two\n[a] \n[a] three\n"
+    );
+  }
+
   #[test]
   fn test_resolve_execution_path_from_npx_shim() {
     // example shim on unix
@@ -0,0 +1,105 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! The durations of the test modules in a file that is shared by the CI
+//! machines, usually checked in, to balance the modules across `--shard`s.
+//! The modules are keyed by their path relative to the current directory, so
+//! that every machine computes the same split.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+
+use crate::util::path::relative_path;
+
+/// The key of a test module, which is its path relative to the current
+/// directory with `/` separators, or its URL when it isn't a local file.
+pub fn test_module_key(
+  specifier: &ModuleSpecifier,
+  initial_cwd: &Path,
+) -> String {
+  specifier
+    .to_file_path()
+    .ok()
+    .and_then(|path| relative_path(initial_cwd, &path))
+    .map(|path| path.to_string_lossy().replace('\\', "/"))
+    .unwrap_or_else(|| specifier.to_string())
+}
+
+#[derive(Clone)]
+pub struct TestDurations {
+  path: PathBuf,
+  initial_cwd: PathBuf,
+  /// The durations in milliseconds by test module key.
+  durations: Arc<Mutex<BTreeMap<String, u64>>>,
+}
+
+impl TestDurations {
+  /// Reads the durations file, which doesn't have to exist yet.
+  pub fn load(path: PathBuf, initial_cwd: &Path) -> Result<Self, AnyError> {
+    let durations = if path.exists() {
+      let text = std::fs::read_to_string(&path).with_context(|| {
+        format!("Failed reading test durations {}", path.display())
+      })?;
+      serde_json::from_str(&text).with_context(|| {
+        format!("Failed parsing test durations {}", path.display())
+      })?
+    } else {
+      BTreeMap::new()
+    };
+    Ok(Self {
+      path,
+      initial_cwd: initial_cwd.to_path_buf(),
+      durations: Arc::new(Mutex::new(durations)),
+    })
+  }
+
+  pub fn get(&self, key: &str) -> Option<u64> {
+    self.durations.lock().get(key).copied()
+  }
+
+  pub fn record(&self, specifier: &ModuleSpecifier, duration: u64) {
+    let key = test_module_key(specifier, &self.initial_cwd);
+    self.durations.lock().insert(key, duration);
+  }
+
+  pub fn save(&self) -> Result<(), AnyError> {
+    let mut text = serde_json::to_string_pretty(&*self.durations.lock())?;
+    text.push('\n');
+    std::fs::write(&self.path, text).with_context(|| {
+      format!("Failed writing test durations {}", self.path.display())
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_durations_file() {
+    let temp_dir = test_util::TempDir::new();
+    let cwd = temp_dir.path().to_path_buf();
+    let path = cwd.join("durations.json");
+    let specifier =
+      ModuleSpecifier::from_file_path(cwd.join("tests/a_test.ts")).unwrap();
+    assert_eq!(
+      test_module_key(&specifier, cwd.as_path()),
+      "tests/a_test.ts"
+    );
+
+    let durations = TestDurations::load(path.clone(), cwd.as_path()).unwrap();
+    assert_eq!(durations.get("tests/a_test.ts"), None);
+    durations.record(&specifier, 150);
+    durations.save().unwrap();
+
+    let durations = TestDurations::load(path, cwd.as_path()).unwrap();
+    assert_eq!(durations.get("tests/a_test.ts"), Some(150));
+  }
+}
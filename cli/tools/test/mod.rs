@@ -4,6 +4,9 @@ use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::TestFlags;
 use crate::args::TestReporterConfig;
+use crate::args::TestShard;
+use crate::cache::CacheDBHash;
+use crate::cache::TestFailuresCache;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
@@ -90,22 +93,30 @@ use std::time::Instant;
 use tokio::signal;
 
 mod channel;
+mod durations;
 pub mod fmt;
 pub mod reporters;
+mod snapshot;
 
 pub use channel::create_single_test_event_channel;
 pub use channel::create_test_event_channel;
 pub use channel::TestEventReceiver;
 pub use channel::TestEventSender;
 pub use channel::TestEventWorkerSender;
+use durations::TestDurations;
 use fmt::format_sanitizer_diff;
 pub use fmt::format_test_error;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
+use reporters::FailedTestsReporter;
+use reporters::JsonTestReporter;
 use reporters::JunitTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
 use reporters::TestReporter;
+pub use snapshot::TestSnapshotMode;
+pub use snapshot::TestSnapshotState;
+pub use snapshot::TestSnapshotSummary;
 
 /// How many times we're allowed to spin the event loop before considering something a leak.
 const MAX_SANITIZER_LOOP_SPINS: usize = 16;
@@ -266,6 +277,10 @@ pub struct TestDescription {
   pub location: TestLocation,
   pub sanitize_ops: bool,
   pub sanitize_resources: bool,
+  /// Overrides `--retries` for this test.
+  pub retry: Option<usize>,
+  /// Overrides `--timeout` for this test, in milliseconds. Zero disables it.
+  pub timeout: Option<u64>,
 }
 
 /// May represent a failure of a test or test step.
@@ -297,6 +312,7 @@ pub enum TestFailure {
   FailedSteps(usize),
   IncompleteSteps,
   Leaked(Vec<String>, Vec<String>), // Details, trailer notes
+  TimedOut(TestTimeout),
   // The rest are for steps only.
   Incomplete,
   OverlapsWithSanitizers(IndexSet<String>), // Long names of overlapped tests
@@ -330,6 +346,7 @@ impl std::fmt::Display for TestFailure {
         }
         Ok(())
       }
+      TestFailure::TimedOut(timeout) => write!(f, "{timeout}"),
       TestFailure::OverlapsWithSanitizers(long_names) => {
         write!(f, "Started test step while another test step with sanitizers was running:")?;
         for long_name in long_names {
@@ -359,6 +376,7 @@ impl TestFailure {
       }
       TestFailure::Incomplete => "Didn't complete before parent".to_string(),
       TestFailure::Leaked(_, _) => "Leaks detected".to_string(),
+      TestFailure::TimedOut(timeout) => timeout.overview(),
       TestFailure::OverlapsWithSanitizers(_) => {
         "Started test step while another test step with sanitizers was running"
           .to_string()
@@ -377,6 +395,7 @@ impl TestFailure {
   fn format_label(&self) -> String {
     match self {
       TestFailure::Incomplete => colors::gray("INCOMPLETE").to_string(),
+      TestFailure::TimedOut(_) => colors::red("TIMED OUT").to_string(),
       _ => colors::red("FAILED").to_string(),
     }
   }
@@ -410,6 +429,71 @@ pub enum TestResult {
   Ignored,
   Failed(TestFailure),
   Cancelled,
+  TimedOut(TestTimeout),
+}
+
+/// A test that didn't finish within its timeout.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestTimeout {
+  /// The timeout in milliseconds.
+  pub timeout: u64,
+  /// Ops, resources and timers started by the test that were still pending.
+  pub pending: Vec<String>,
+  pub trailer_notes: Vec<String>,
+}
+
+impl std::fmt::Display for TestTimeout {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Test timed out after {}ms.", self.timeout)?;
+    if !self.pending.is_empty() {
+      write!(f, " Still pending:")?;
+      for pending in &self.pending {
+        write!(f, "\n  - {}", pending)?;
+      }
+      for trailer in &self.trailer_notes {
+        write!(f, "\n{}", trailer)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl TestTimeout {
+  pub fn overview(&self) -> String {
+    format!("Timed out after {}ms", self.timeout)
+  }
+}
+
+/// Terminates the execution of the isolate once a test runs past its timeout,
+/// since a test that blocks synchronously never yields back to the event loop
+/// for `tokio::time::timeout` to interrupt it.
+struct TestTimeoutWatchdog {
+  cancel_tx: std::sync::mpsc::Sender<()>,
+  thread: std::thread::JoinHandle<bool>,
+}
+
+impl TestTimeoutWatchdog {
+  fn start(isolate_handle: v8::IsolateHandle, timeout: Duration) -> Self {
+    let (cancel_tx, cancel_rx) = std::sync::mpsc::channel::<()>();
+    let thread =
+      std::thread::spawn(move || match cancel_rx.recv_timeout(timeout) {
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+          isolate_handle.terminate_execution();
+          true
+        }
+        _ => false,
+      });
+    Self { cancel_tx, thread }
+  }
+
+  /// Stops the watchdog and returns whether it terminated the execution, in
+  /// which case the termination has to be cancelled to keep using the isolate.
+  fn stop(self) -> bool {
+    let Self { cancel_tx, thread } = self;
+    drop(cancel_tx);
+    thread.join().unwrap_or(false)
+  }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -461,6 +545,10 @@ pub enum TestEvent {
   StepRegister(TestStepDescription),
   StepWait(usize),
   StepResult(usize, TestStepResult, u64),
+  /// A test failed and is going to be run again. Holds the number of the
+  /// failed attempt, starting at 1.
+  Retry(usize, TestFailure, usize, u64),
+  Snapshots(TestSnapshotSummary),
   /// Indicates that this worker has completed running tests.
   Completed,
   /// Indicates that the user has cancelled the test run with Ctrl+C and
@@ -479,6 +567,8 @@ impl TestEvent {
       self,
       TestEvent::Plan(..)
         | TestEvent::Result(..)
+        | TestEvent::Retry(..)
+        | TestEvent::Snapshots(..)
         | TestEvent::StepWait(..)
         | TestEvent::StepResult(..)
         | TestEvent::UncaughtError(..)
@@ -488,6 +578,16 @@ impl TestEvent {
   }
 }
 
+/// Which shard of the collected test modules a run covers.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct TestShardSummary {
+  /// One-based index of the shard that ran.
+  pub index: usize,
+  pub count: usize,
+  /// Number of test modules that were left to the other shards.
+  pub skipped: usize,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TestSummary {
   pub total: usize,
@@ -501,6 +601,10 @@ pub struct TestSummary {
   pub measured: usize,
   pub failures: Vec<(TestFailureDescription, TestFailure)>,
   pub uncaught_errors: Vec<(String, Box<JsError>)>,
+  pub shard: Option<TestShardSummary>,
+  pub snapshots: Vec<TestSnapshotSummary>,
+  /// Tests that passed after being retried, with the number of retries.
+  pub flaky: Vec<(TestFailureDescription, usize)>,
 }
 
 #[derive(Debug, Clone)]
@@ -513,6 +617,10 @@ struct TestSpecifiersOptions {
   specifier: TestSpecifierOptions,
   reporter: TestReporterConfig,
   junit_path: Option<String>,
+  json_path: Option<String>,
+  shard: Option<TestShardSummary>,
+  /// With `--only-failed`, the names of the tests to run in each module.
+  failed_tests: Option<HashMap<ModuleSpecifier, Vec<String>>>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -520,6 +628,13 @@ pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
   pub trace_leaks: bool,
+  /// How many times a failing test is re-run, unless the test sets `retry`.
+  pub retries: usize,
+  /// Milliseconds after which a test is stopped, unless the test sets
+  /// `timeout`.
+  pub timeout: Option<u64>,
+  /// Enables `t.assertSnapshot()` for the test module.
+  pub snapshot_mode: Option<TestSnapshotMode>,
 }
 
 impl TestSummary {
@@ -536,15 +651,42 @@ impl TestSummary {
       measured: 0,
       failures: Vec::new(),
       uncaught_errors: Vec::new(),
+      shard: None,
+      snapshots: Vec::new(),
+      flaky: Vec::new(),
     }
   }
 
   fn has_failed(&self) -> bool {
     self.failed > 0 || !self.failures.is_empty()
   }
+
+  /// Forgets the step results of a failed attempt of a test that is going to
+  /// be retried.
+  fn discard_step_results(
+    &mut self,
+    steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    for (desc, result) in steps {
+      match result {
+        TestStepResult::Ok => {
+          self.passed_steps -= 1;
+        }
+        TestStepResult::Ignored => {
+          self.ignored_steps -= 1;
+        }
+        TestStepResult::Failed(_) => {
+          self.failed_steps -= 1;
+          self.failures.retain(|(failure, _)| failure.id != desc.id);
+        }
+      }
+    }
+  }
 }
 
-fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
+fn get_test_reporter(
+  options: &TestSpecifiersOptions,
+) -> Result<Box<dyn TestReporter>, AnyError> {
   let parallel = options.concurrent_jobs.get() > 1;
   let reporter: Box<dyn TestReporter> = match &options.reporter {
     TestReporterConfig::Dot => {
@@ -564,17 +706,30 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
       options.cwd.clone(),
       options.concurrent_jobs > NonZeroUsize::new(1).unwrap(),
     )),
+    TestReporterConfig::Json => {
+      Box::new(JsonTestReporter::new(options.cwd.clone(), "-".to_string())?)
+    }
   };
 
+  let mut reporters = vec![reporter];
   if let Some(junit_path) = &options.junit_path {
-    let junit = Box::new(JunitTestReporter::new(
+    reporters.push(Box::new(JunitTestReporter::new(
       options.cwd.clone(),
       junit_path.to_string(),
-    ));
-    return Box::new(CompoundTestReporter::new(vec![reporter, junit]));
+    )));
+  }
+  if let Some(json_path) = &options.json_path {
+    reporters.push(Box::new(JsonTestReporter::new(
+      options.cwd.clone(),
+      json_path.to_string(),
+    )?));
+  }
+
+  if reporters.len() > 1 {
+    return Ok(Box::new(CompoundTestReporter::new(reporters)));
   }
 
-  reporter
+  Ok(reporters.remove(0))
 }
 
 async fn configure_main_worker(
@@ -774,6 +929,11 @@ pub async fn run_tests_for_worker(
 
   let tests: Arc<TestDescriptions> = tests.into();
   send_test_event(&state_rc, TestEvent::Register(tests.clone()))?;
+  if let Some(snapshot_mode) = options.snapshot_mode {
+    state_rc
+      .borrow_mut()
+      .put(TestSnapshotState::new(specifier, snapshot_mode));
+  }
   let res = run_tests_for_worker_inner(
     worker,
     specifier,
@@ -782,12 +942,65 @@ pub async fn run_tests_for_worker(
     options,
     fail_fast_tracker,
   )
-  .await;
+  .await
+  .and_then(|()| finish_test_snapshots(&state_rc, specifier));
 
   _ = send_test_event(&state_rc, TestEvent::Completed);
   res
 }
 
+fn finish_test_snapshots(
+  op_state: &RefCell<OpState>,
+  specifier: &ModuleSpecifier,
+) -> Result<(), AnyError> {
+  let Some(snapshots) = op_state.borrow_mut().try_take::<TestSnapshotState>()
+  else {
+    return Ok(());
+  };
+  let summary = snapshots.finish(specifier.to_string())?;
+  if !summary.is_empty() {
+    send_test_event(op_state, TestEvent::Snapshots(summary))?;
+  }
+  Ok(())
+}
+
+fn start_test_snapshots(op_state: &RefCell<OpState>) {
+  if let Some(snapshots) =
+    op_state.borrow_mut().try_borrow_mut::<TestSnapshotState>()
+  {
+    snapshots.start_test();
+  }
+}
+
+/// Forgets the snapshots asserted by a failed attempt of a test that is going
+/// to be retried.
+fn discard_test_snapshots(op_state: &RefCell<OpState>) {
+  if let Some(snapshots) =
+    op_state.borrow_mut().try_borrow_mut::<TestSnapshotState>()
+  {
+    snapshots.discard_test();
+  }
+}
+
+/// Registers a test of the module with its snapshots.
+fn add_test_snapshots(op_state: &RefCell<OpState>, name: &str) {
+  if let Some(snapshots) =
+    op_state.borrow_mut().try_borrow_mut::<TestSnapshotState>()
+  {
+    snapshots.add_test(name);
+  }
+}
+
+/// Keeps the snapshots of a test that didn't run to completion from being
+/// reported as obsolete.
+fn skip_test_snapshots(op_state: &RefCell<OpState>, name: &str) {
+  if let Some(snapshots) =
+    op_state.borrow_mut().try_borrow_mut::<TestSnapshotState>()
+  {
+    snapshots.skip_test(name);
+  }
+}
+
 async fn run_tests_for_worker_inner(
   worker: &mut MainWorker,
   specifier: &ModuleSpecifier,
@@ -825,6 +1038,17 @@ async fn run_tests_for_worker_inner(
     tests_to_run.shuffle(&mut SmallRng::seed_from_u64(seed));
   }
 
+  let ids_to_run = tests_to_run
+    .iter()
+    .map(|(d, _)| d.id)
+    .collect::<HashSet<_>>();
+  for (id, d) in &tests.tests {
+    add_test_snapshots(&state_rc, &d.name);
+    if !ids_to_run.contains(id) {
+      skip_test_snapshots(&state_rc, &d.name);
+    }
+  }
+
   send_test_event(
     &state_rc,
     TestEvent::Plan(TestPlan {
@@ -836,6 +1060,7 @@ async fn run_tests_for_worker_inner(
   )?;
 
   let mut had_uncaught_error = false;
+  let mut had_timeout = false;
   let stats = worker.js_runtime.runtime_activity_stats_factory();
   let ops = worker.js_runtime.op_names();
 
@@ -872,8 +1097,13 @@ async fn run_tests_for_worker_inner(
       .or_insert(1);
   }
 
-  for (desc, function) in tests_to_run.into_iter() {
+  let mut tests_to_run = tests_to_run.into_iter();
+  while let Some((desc, function)) = tests_to_run.next() {
     if fail_fast_tracker.should_stop() {
+      skip_test_snapshots(&state_rc, &desc.name);
+      for (desc, _) in tests_to_run.by_ref() {
+        skip_test_snapshots(&state_rc, &desc.name);
+      }
       break;
     }
 
@@ -889,13 +1119,17 @@ async fn run_tests_for_worker_inner(
       .try_take::<deno_runtime::deno_fetch::reqwest::Client>();
 
     if desc.ignore {
+      skip_test_snapshots(&state_rc, &desc.name);
       send_test_event(
         &state_rc,
         TestEvent::Result(desc.id, TestResult::Ignored, 0),
       )?;
       continue;
     }
-    if had_uncaught_error {
+    // A test that timed out may still be running, so the tests after it can't
+    // be run reliably.
+    if had_uncaught_error || had_timeout {
+      skip_test_snapshots(&state_rc, &desc.name);
       send_test_event(
         &state_rc,
         TestEvent::Result(desc.id, TestResult::Cancelled, 0),
@@ -904,128 +1138,177 @@ async fn run_tests_for_worker_inner(
     }
     send_test_event(&state_rc, TestEvent::Wait(desc.id))?;
 
-    // Poll event loop once, to allow all ops that are already resolved, but haven't
-    // responded to settle.
-    // TODO(mmastrac): we should provide an API to poll the event loop until no further
-    // progress is made.
-    poll_event_loop(worker).await?;
-
-    // We always capture stats, regardless of sanitization state
-    let before = stats.clone().capture(&filter);
-
-    let earlier = Instant::now();
-    let call = worker.js_runtime.call(&function);
-
-    let slow_state_rc = state_rc.clone();
-    let slow_test_id = desc.id;
-    let slow_test_warning = spawn(async move {
-      // The slow test warning should pop up every DENO_SLOW_TEST_TIMEOUT*(2**n) seconds,
-      // with a duration that is doubling each time. So for a warning time of 60s,
-      // we should get a warning at 60s, 120s, 240s, etc.
-      let base_timeout = env::var("DENO_SLOW_TEST_TIMEOUT").unwrap_or_default();
-      let base_timeout = base_timeout.parse().unwrap_or(60).max(1);
-      let mut multiplier = 1;
-      let mut elapsed = 0;
-      loop {
-        tokio::time::sleep(Duration::from_secs(
-          base_timeout * (multiplier - elapsed),
-        ))
-        .await;
-        if send_test_event(
-          &slow_state_rc,
-          TestEvent::Slow(
-            slow_test_id,
-            Duration::from_secs(base_timeout * multiplier).as_millis() as _,
-          ),
+    let retries = desc.retry.unwrap_or(options.retries);
+    let timeout = desc.timeout.or(options.timeout).filter(|t| *t > 0);
+    let mut attempt = 0;
+    loop {
+      attempt += 1;
+      start_test_snapshots(&state_rc);
+
+      // Poll event loop once, to allow all ops that are already resolved, but haven't
+      // responded to settle.
+      // TODO(mmastrac): we should provide an API to poll the event loop until no further
+      // progress is made.
+      poll_event_loop(worker).await?;
+
+      // We always capture stats, regardless of sanitization state
+      let before = stats.clone().capture(&filter);
+
+      let earlier = Instant::now();
+      let watchdog = timeout.map(|timeout| {
+        TestTimeoutWatchdog::start(
+          worker.js_runtime.v8_isolate().thread_safe_handle(),
+          Duration::from_millis(timeout),
         )
-        .is_err()
-        {
-          break;
+      });
+      let call = worker.js_runtime.call(&function);
+
+      let slow_state_rc = state_rc.clone();
+      let slow_test_id = desc.id;
+      let slow_test_warning = spawn(async move {
+        // The slow test warning should pop up every DENO_SLOW_TEST_TIMEOUT*(2**n) seconds,
+        // with a duration that is doubling each time. So for a warning time of 60s,
+        // we should get a warning at 60s, 120s, 240s, etc.
+        let base_timeout =
+          env::var("DENO_SLOW_TEST_TIMEOUT").unwrap_or_default();
+        let base_timeout = base_timeout.parse().unwrap_or(60).max(1);
+        let mut multiplier = 1;
+        let mut elapsed = 0;
+        loop {
+          tokio::time::sleep(Duration::from_secs(
+            base_timeout * (multiplier - elapsed),
+          ))
+          .await;
+          if send_test_event(
+            &slow_state_rc,
+            TestEvent::Slow(
+              slow_test_id,
+              Duration::from_secs(base_timeout * multiplier).as_millis() as _,
+            ),
+          )
+          .is_err()
+          {
+            break;
+          }
+          multiplier *= 2;
+          elapsed += 1;
         }
-        multiplier *= 2;
-        elapsed += 1;
-      }
-    });
-
-    let result = worker
-      .js_runtime
-      .with_event_loop_promise(call, PollEventLoopOptions::default())
-      .await;
-    slow_test_warning.abort();
-    let result = match result {
-      Ok(r) => r,
-      Err(error) => {
-        if error.is::<JsError>() {
+      });
+
+      let result = worker
+        .js_runtime
+        .with_event_loop_promise(call, PollEventLoopOptions::default());
+      let result = match timeout {
+        Some(timeout) => {
+          tokio::time::timeout(Duration::from_millis(timeout), result)
+            .await
+            .map_err(|_| timeout)
+        }
+        None => Ok(result.await),
+      };
+      let result = match (watchdog.map(|w| w.stop()), timeout) {
+        (Some(true), Some(timeout)) => {
+          worker.js_runtime.v8_isolate().cancel_terminate_execution();
+          Err(timeout)
+        }
+        _ => result,
+      };
+      slow_test_warning.abort();
+      let result = match result {
+        Ok(result) => result,
+        Err(timeout) => {
+          let after = stats.clone().capture(&filter);
+          let mut diff = RuntimeActivityStats::diff(&before, &after);
+          // Only report what the test started and left running.
+          diff.disappeared.clear();
+          let (pending, trailer_notes) = format_sanitizer_diff(diff);
+          fail_fast_tracker.add_failure();
+          skip_test_snapshots(&state_rc, &desc.name);
           send_test_event(
             &state_rc,
-            TestEvent::UncaughtError(
-              specifier.to_string(),
-              Box::new(error.downcast::<JsError>().unwrap()),
+            TestEvent::Result(
+              desc.id,
+              TestResult::TimedOut(TestTimeout {
+                timeout,
+                pending,
+                trailer_notes,
+              }),
+              earlier.elapsed().as_millis() as u64,
             ),
           )?;
-          fail_fast_tracker.add_failure();
+          had_timeout = true;
+          break;
+        }
+      };
+      let result = match result {
+        Ok(r) => r,
+        Err(error) => {
+          if error.is::<JsError>() {
+            send_test_event(
+              &state_rc,
+              TestEvent::UncaughtError(
+                specifier.to_string(),
+                Box::new(error.downcast::<JsError>().unwrap()),
+              ),
+            )?;
+            fail_fast_tracker.add_failure();
+            skip_test_snapshots(&state_rc, &desc.name);
+            send_test_event(
+              &state_rc,
+              TestEvent::Result(desc.id, TestResult::Cancelled, 0),
+            )?;
+            had_uncaught_error = true;
+            break;
+          } else {
+            return Err(error);
+          }
+        }
+      };
+
+      // Check the result before we check for leaks
+      let result = {
+        let scope = &mut worker.js_runtime.handle_scope();
+        let result = v8::Local::new(scope, result);
+        serde_v8::from_v8::<TestResult>(scope, result)?
+      };
+      let result = if matches!(result, TestResult::Failed(_)) {
+        result
+      } else {
+        // Await activity stabilization
+        let diff = wait_for_activity_to_stabilize(
+          worker,
+          &stats,
+          &filter,
+          &top_level,
+          before,
+          desc.sanitize_ops,
+          desc.sanitize_resources,
+        )
+        .await?;
+        match diff.map(format_sanitizer_diff) {
+          Some((formatted, trailer_notes)) if !formatted.is_empty() => {
+            TestResult::Failed(TestFailure::Leaked(formatted, trailer_notes))
+          }
+          _ => result,
+        }
+      };
+
+      let elapsed = earlier.elapsed().as_millis() as u64;
+      if let TestResult::Failed(failure) = &result {
+        if attempt <= retries && !fail_fast_tracker.should_stop() {
+          discard_test_snapshots(&state_rc);
           send_test_event(
             &state_rc,
-            TestEvent::Result(desc.id, TestResult::Cancelled, 0),
+            TestEvent::Retry(desc.id, failure.clone(), attempt, elapsed),
           )?;
-          had_uncaught_error = true;
           continue;
-        } else {
-          return Err(error);
         }
-      }
-    };
-
-    // Check the result before we check for leaks
-    let result = {
-      let scope = &mut worker.js_runtime.handle_scope();
-      let result = v8::Local::new(scope, result);
-      serde_v8::from_v8::<TestResult>(scope, result)?
-    };
-    if matches!(result, TestResult::Failed(_)) {
-      fail_fast_tracker.add_failure();
-      let elapsed = earlier.elapsed().as_millis();
-      send_test_event(
-        &state_rc,
-        TestEvent::Result(desc.id, result, elapsed as u64),
-      )?;
-      continue;
-    }
-
-    // Await activity stabilization
-    if let Some(diff) = wait_for_activity_to_stabilize(
-      worker,
-      &stats,
-      &filter,
-      &top_level,
-      before,
-      desc.sanitize_ops,
-      desc.sanitize_resources,
-    )
-    .await?
-    {
-      let (formatted, trailer_notes) = format_sanitizer_diff(diff);
-      if !formatted.is_empty() {
-        let failure = TestFailure::Leaked(formatted, trailer_notes);
         fail_fast_tracker.add_failure();
-        let elapsed = earlier.elapsed().as_millis();
-        send_test_event(
-          &state_rc,
-          TestEvent::Result(
-            desc.id,
-            TestResult::Failed(failure),
-            elapsed as u64,
-          ),
-        )?;
-        continue;
+        skip_test_snapshots(&state_rc, &desc.name);
       }
+      send_test_event(&state_rc, TestEvent::Result(desc.id, result, elapsed))?;
+      break;
     }
-
-    let elapsed = earlier.elapsed().as_millis();
-    send_test_event(
-      &state_rc,
-      TestEvent::Result(desc.id, result, elapsed as u64),
-    )?;
   }
   Ok(())
 }
@@ -1389,10 +1672,119 @@ pub async fn check_specifiers(
 
 static HAS_TEST_RUN_SIGINT_HANDLER: AtomicBool = AtomicBool::new(false);
 
+/// Deterministically splits the specifiers into `shard.count` shards and
+/// keeps only the ones belonging to `shard.index`, returning them along with
+/// the number of specifiers that were skipped.
+///
+/// The split only depends on the paths of the specifiers relative to
+/// `initial_cwd`, so every CI machine computes the same one. Specifiers are
+/// assigned longest first to the least loaded shard using the durations of
+/// the shared durations file. Specifiers without a known duration are
+/// weighted with the average of the known ones, so without durations this
+/// degrades to a round-robin split of the sorted specifiers.
+fn shard_specifiers(
+  specifiers_with_mode: Vec<(ModuleSpecifier, TestMode)>,
+  shard: &TestShard,
+  initial_cwd: &Path,
+  get_duration: impl Fn(&str) -> Option<u64>,
+) -> (Vec<(ModuleSpecifier, TestMode)>, usize) {
+  let mut sorted = specifiers_with_mode
+    .iter()
+    .map(|(specifier, _)| {
+      let key = durations::test_module_key(specifier, initial_cwd);
+      let duration = get_duration(&key);
+      (key, specifier, duration)
+    })
+    .collect::<Vec<_>>();
+  sorted.sort_by(|a, b| a.0.cmp(&b.0));
+  let known_durations = sorted
+    .iter()
+    .filter_map(|(_, _, duration)| *duration)
+    .collect::<Vec<_>>();
+  let default_duration = if known_durations.is_empty() {
+    1
+  } else {
+    (known_durations.iter().sum::<u64>() / known_durations.len() as u64).max(1)
+  };
+  let mut weighted = sorted
+    .into_iter()
+    .map(|(_, specifier, duration)| {
+      (specifier, duration.unwrap_or(default_duration).max(1))
+    })
+    .collect::<Vec<_>>();
+  // stable sort, so equal durations keep the specifier order
+  weighted.sort_by(|a, b| b.1.cmp(&a.1));
+
+  let mut loads = vec![0u64; shard.count];
+  let mut selected = HashSet::new();
+  for (specifier, duration) in weighted {
+    let (index, _) = loads
+      .iter()
+      .enumerate()
+      .min_by_key(|(_, load)| **load)
+      .unwrap();
+    loads[index] += duration;
+    if index + 1 == shard.index {
+      selected.insert(specifier.clone());
+    }
+  }
+
+  let total = specifiers_with_mode.len();
+  let specifiers_with_mode = specifiers_with_mode
+    .into_iter()
+    .filter(|(specifier, _)| selected.contains(specifier))
+    .collect::<Vec<_>>();
+  let skipped = total - specifiers_with_mode.len();
+  (specifiers_with_mode, skipped)
+}
+
+/// Hashes the source of a local test module, to tell whether it changed since
+/// its tests were recorded as failed.
+fn test_module_source_hash(specifier: &ModuleSpecifier) -> Option<CacheDBHash> {
+  let path = specifier.to_file_path().ok()?;
+  let source = std::fs::read_to_string(path).ok()?;
+  Some(CacheDBHash::from_source(source))
+}
+
+/// Resolves the tests that failed in the previous run back to the test
+/// modules they are in, for `--only-failed`. Returns `None` when a test
+/// module with failures changed since, in which case all tests should run.
+fn resolve_failed_tests(
+  specifiers_with_mode: &[(ModuleSpecifier, TestMode)],
+  failures_cache: &TestFailuresCache,
+) -> Option<HashMap<ModuleSpecifier, Vec<String>>> {
+  let specifiers = specifiers_with_mode
+    .iter()
+    .filter(|(_, mode)| !matches!(mode, TestMode::Documentation))
+    .map(|(specifier, _)| specifier)
+    .collect::<HashSet<_>>();
+  let mut failed_tests: HashMap<ModuleSpecifier, Vec<String>> = HashMap::new();
+  for failure in failures_cache.get_failures() {
+    if !specifiers.contains(&failure.specifier) {
+      continue;
+    }
+    if test_module_source_hash(&failure.specifier) != Some(failure.source_hash)
+    {
+      log::info!(
+        "{} Test modules changed since the previous run, running all tests.",
+        colors::yellow("Warning"),
+      );
+      return None;
+    }
+    failed_tests
+      .entry(failure.specifier)
+      .or_default()
+      .push(failure.name);
+  }
+  Some(failed_tests)
+}
+
 /// Test a collection of specifiers with test modes concurrently.
 async fn test_specifiers(
   worker_factory: Arc<CliMainWorkerFactory>,
   permissions: &Permissions,
+  durations: Option<TestDurations>,
+  failures_cache: TestFailuresCache,
   specifiers: Vec<ModuleSpecifier>,
   options: TestSpecifiersOptions,
 ) -> Result<(), AnyError> {
@@ -1415,24 +1807,43 @@ async fn test_specifiers(
     cancel_sender.send(TestEvent::Sigint).ok();
   });
   HAS_TEST_RUN_SIGINT_HANDLER.store(true, Ordering::Relaxed);
-  let reporter = get_test_reporter(&options);
+  let mut reporter: Box<dyn TestReporter> =
+    Box::new(CompoundTestReporter::new(vec![
+      get_test_reporter(&options)?,
+      Box::new(FailedTestsReporter::new(failures_cache)),
+    ]));
+  if let Some(shard) = &options.shard {
+    reporter.report_shard(shard);
+  }
   let fail_fast_tracker = FailFastTracker::new(options.fail_fast);
 
   let join_handles = specifiers.into_iter().map(move |specifier| {
     let worker_factory = worker_factory.clone();
     let permissions = permissions.clone();
+    let durations = durations.clone();
     let worker_sender = test_event_sender_factory.worker();
     let fail_fast_tracker = fail_fast_tracker.clone();
-    let specifier_options = options.specifier.clone();
+    let mut specifier_options = options.specifier.clone();
+    if let Some(failed_tests) = &options.failed_tests {
+      specifier_options.filter.include = failed_tests.get(&specifier).cloned();
+    }
     spawn_blocking(move || {
-      create_and_run_current_thread(test_specifier(
+      let started = Instant::now();
+      let result = create_and_run_current_thread(test_specifier(
         worker_factory,
         permissions,
-        specifier,
+        specifier.clone(),
         worker_sender,
-        fail_fast_tracker,
+        fail_fast_tracker.clone(),
         specifier_options,
-      ))
+      ));
+      // don't record modules that were skipped due to `--fail-fast`
+      if result.is_ok() && !fail_fast_tracker.should_stop() {
+        if let Some(durations) = &durations {
+          durations.record(&specifier, started.elapsed().as_millis() as u64);
+        }
+      }
+      result
     })
   });
 
@@ -1462,6 +1873,7 @@ pub async fn report_tests(
   let mut test_steps = IndexMap::new();
   let mut tests_started = HashSet::new();
   let mut tests_with_result = HashSet::new();
+  let mut step_results = HashMap::new();
   let mut start_time = None;
   let mut had_plan = false;
   let mut used_only = false;
@@ -1500,7 +1912,9 @@ pub async fn report_tests(
       TestEvent::Result(id, result, elapsed) => {
         if tests_with_result.insert(id) {
           match result {
-            TestResult::Failed(_) | TestResult::Cancelled => {
+            TestResult::Failed(_)
+            | TestResult::Cancelled
+            | TestResult::TimedOut(_) => {
               failed = true;
             }
             _ => (),
@@ -1530,8 +1944,28 @@ pub async fn report_tests(
             &tests,
             &test_steps,
           );
+          step_results.insert(id, result);
         }
       }
+      TestEvent::Retry(id, failure, attempt, elapsed) => {
+        let discarded_steps = test_steps
+          .values()
+          .filter(|desc| desc.root_id == id)
+          .filter_map(|desc| {
+            Some((desc.clone(), step_results.remove(&desc.id)?))
+          })
+          .collect::<Vec<_>>();
+        reporter.report_retry(
+          tests.get(&id).unwrap(),
+          &failure,
+          attempt,
+          elapsed,
+          &discarded_steps,
+        );
+      }
+      TestEvent::Snapshots(summary) => {
+        reporter.report_snapshots(&summary);
+      }
       TestEvent::ForceEndReport => {
         break;
       }
@@ -1747,6 +2181,19 @@ pub async fn run_tests(
     Permissions::from_options(&cli_options.permissions_options()?)?;
   let log_level = cli_options.log_level();
 
+  let durations = workspace_test_options
+    .shard_durations
+    .as_ref()
+    .map(|path| {
+      TestDurations::load(
+        cli_options.initial_cwd().join(path),
+        cli_options.initial_cwd(),
+      )
+    })
+    .transpose()?;
+  let failures_cache =
+    TestFailuresCache::new(factory.caches()?.test_failures_db());
+
   let members_with_test_options =
     cli_options.resolve_test_options_for_members(&test_flags)?;
   let specifiers_with_mode = fetch_specifiers_with_test_mode(
@@ -1761,6 +2208,41 @@ pub async fn run_tests(
     return Err(generic_error("No test modules found"));
   }
 
+  let failed_tests = if workspace_test_options.only_failed {
+    resolve_failed_tests(&specifiers_with_mode, &failures_cache)
+  } else {
+    None
+  };
+  let specifiers_with_mode = match &failed_tests {
+    Some(failed_tests) if failed_tests.is_empty() => {
+      log::info!("No tests failed in the previous run.");
+      return Ok(());
+    }
+    Some(failed_tests) => specifiers_with_mode
+      .into_iter()
+      .filter(|(specifier, _)| failed_tests.contains_key(specifier))
+      .collect(),
+    None => specifiers_with_mode,
+  };
+
+  let (specifiers_with_mode, shard) =
+    if let Some(shard) = &workspace_test_options.shard {
+      let (specifiers_with_mode, skipped) = shard_specifiers(
+        specifiers_with_mode,
+        shard,
+        cli_options.initial_cwd(),
+        |key| durations.as_ref().and_then(|durations| durations.get(key)),
+      );
+      let shard = TestShardSummary {
+        index: shard.index,
+        count: shard.count,
+        skipped,
+      };
+      (specifiers_with_mode, Some(shard))
+    } else {
+      (specifiers_with_mode, None)
+    };
+
   let main_graph_container = factory.main_module_graph_container().await?;
 
   check_specifiers(
@@ -1777,9 +2259,13 @@ pub async fn run_tests(
   let worker_factory =
     Arc::new(factory.create_cli_main_worker_factory().await?);
 
-  test_specifiers(
+  // the durations are only recorded by a run over all the modules
+  let recorded_durations = durations.filter(|_| shard.is_none());
+  let result = test_specifiers(
     worker_factory,
     &permissions,
+    recorded_durations.clone(),
+    failures_cache,
     specifiers_with_mode
       .into_iter()
       .filter_map(|(s, m)| match m {
@@ -1799,19 +2285,30 @@ pub async fn run_tests(
       concurrent_jobs: workspace_test_options.concurrent_jobs,
       fail_fast: workspace_test_options.fail_fast,
       log_level,
-      filter: workspace_test_options.filter.is_some(),
+      filter: workspace_test_options.filter.is_some() || failed_tests.is_some(),
       reporter: workspace_test_options.reporter,
       junit_path: workspace_test_options.junit_path,
+      json_path: workspace_test_options.json_path,
+      shard,
+      failed_tests,
       specifier: TestSpecifierOptions {
         filter: TestFilter::from_flag(&workspace_test_options.filter),
         shuffle: workspace_test_options.shuffle,
         trace_leaks: workspace_test_options.trace_leaks,
+        retries: workspace_test_options.retries,
+        timeout: workspace_test_options.timeout,
+        snapshot_mode: Some(TestSnapshotMode::resolve(
+          workspace_test_options.update_snapshots,
+        )),
       },
     },
   )
-  .await?;
+  .await;
 
-  Ok(())
+  if let Some(durations) = recorded_durations {
+    durations.save()?;
+  }
+  result
 }
 
 pub async fn run_tests_with_watch(
@@ -1944,6 +2441,8 @@ pub async fn run_tests_with_watch(
         test_specifiers(
           worker_factory,
           &permissions,
+          None,
+          TestFailuresCache::new(factory.caches()?.test_failures_db()),
           specifiers_with_mode
             .into_iter()
             .filter_map(|(s, m)| match m {
@@ -1966,10 +2465,18 @@ pub async fn run_tests_with_watch(
             filter: workspace_test_options.filter.is_some(),
             reporter: workspace_test_options.reporter,
             junit_path: workspace_test_options.junit_path,
+            json_path: workspace_test_options.json_path,
+            shard: None,
+            failed_tests: None,
             specifier: TestSpecifierOptions {
               filter: TestFilter::from_flag(&workspace_test_options.filter),
               shuffle: workspace_test_options.shuffle,
               trace_leaks: workspace_test_options.trace_leaks,
+              retries: workspace_test_options.retries,
+              timeout: workspace_test_options.timeout,
+              snapshot_mode: Some(TestSnapshotMode::resolve(
+                workspace_test_options.update_snapshots,
+              )),
             },
           },
         )
@@ -2055,6 +2562,79 @@ mod inner_test {
     assert!(!is_supported_test_ext(Path::new("foo.JsON")));
   }
 
+  #[test]
+  fn test_shard_specifiers() {
+    let cwd = if cfg!(windows) {
+      Path::new("C:\\project")
+    } else {
+      Path::new("/project")
+    };
+    let specifiers_with_mode = ["e", "c", "a", "d", "b"]
+      .iter()
+      .map(|name| {
+        (
+          ModuleSpecifier::from_file_path(
+            cwd.join("tests").join(format!("{name}_test.ts")),
+          )
+          .unwrap(),
+          TestMode::Executable,
+        )
+      })
+      .collect::<Vec<_>>();
+    let names = |specifiers_with_mode: &[(ModuleSpecifier, TestMode)]| {
+      specifiers_with_mode
+        .iter()
+        .map(|(s, _)| s.path().rsplit('/').next().unwrap().to_string())
+        .collect::<Vec<_>>()
+    };
+
+    // without durations the split is round-robin over the sorted specifiers
+    let (shard, skipped) = shard_specifiers(
+      specifiers_with_mode.clone(),
+      &TestShard { index: 1, count: 2 },
+      cwd,
+      |_| None,
+    );
+    assert_eq!(names(&shard), vec!["a_test.ts", "c_test.ts", "e_test.ts"]);
+    assert_eq!(skipped, 2);
+    let (shard, skipped) = shard_specifiers(
+      specifiers_with_mode.clone(),
+      &TestShard { index: 2, count: 2 },
+      cwd,
+      |_| None,
+    );
+    assert_eq!(names(&shard), vec!["b_test.ts", "d_test.ts"]);
+    assert_eq!(skipped, 3);
+
+    // with durations the slowest module gets a shard of its own
+    let get_duration = |key: &str| {
+      if key == "tests/c_test.ts" {
+        Some(1000)
+      } else {
+        Some(10)
+      }
+    };
+    let (shard, skipped) = shard_specifiers(
+      specifiers_with_mode.clone(),
+      &TestShard { index: 1, count: 2 },
+      cwd,
+      get_duration,
+    );
+    assert_eq!(names(&shard), vec!["c_test.ts"]);
+    assert_eq!(skipped, 4);
+    let (shard, skipped) = shard_specifiers(
+      specifiers_with_mode,
+      &TestShard { index: 2, count: 2 },
+      cwd,
+      get_duration,
+    );
+    assert_eq!(
+      names(&shard),
+      vec!["a_test.ts", "b_test.ts", "d_test.ts", "e_test.ts"]
+    );
+    assert_eq!(skipped, 1);
+  }
+
   #[test]
   fn test_is_supported_test_path() {
     assert!(is_supported_test_path(Path::new(
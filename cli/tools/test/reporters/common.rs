@@ -68,6 +68,14 @@ pub fn format_test_step_for_summary(
   )
 }
 
+pub(super) fn format_shard_skipped(skipped: usize) -> String {
+  if skipped == 1 {
+    "1 module".to_string()
+  } else {
+    format!("{skipped} modules")
+  }
+}
+
 pub(super) fn report_sigint(
   writer: &mut dyn std::io::Write,
   cwd: &Url,
@@ -100,6 +108,93 @@ pub(super) fn report_sigint(
   writeln!(writer).unwrap();
 }
 
+pub(super) fn format_retries(retries: usize) -> String {
+  if retries == 1 {
+    "1 retry".to_string()
+  } else {
+    format!("{retries} retries")
+  }
+}
+
+fn report_flaky_summary(
+  writer: &mut dyn std::io::Write,
+  cwd: &Url,
+  flaky: &[(TestFailureDescription, usize)],
+) {
+  if flaky.is_empty() {
+    return;
+  }
+
+  writeln!(writer, "\n{}\n", colors::yellow_bold("FLAKY")).unwrap();
+  for (description, retries) in flaky {
+    writeln!(
+      writer,
+      "{} {}",
+      format_test_for_summary(cwd, description),
+      colors::gray(format!("(passed after {})", format_retries(*retries)))
+    )
+    .unwrap();
+  }
+}
+
+fn report_snapshots_summary(
+  writer: &mut dyn std::io::Write,
+  cwd: &Url,
+  snapshots: &[TestSnapshotSummary],
+) {
+  let written = snapshots.iter().map(|s| s.written).sum::<usize>();
+  let updated = snapshots.iter().map(|s| s.updated).sum::<usize>();
+  let removed = snapshots.iter().map(|s| s.removed).sum::<usize>();
+  let obsolete = snapshots
+    .iter()
+    .flat_map(|s| s.obsolete.iter().map(move |name| (&s.origin, name)))
+    .collect::<Vec<_>>();
+  if written == 0 && updated == 0 && removed == 0 && obsolete.is_empty() {
+    return;
+  }
+
+  writeln!(writer, "\n{}\n", colors::intense_blue("SNAPSHOTS")).unwrap();
+  let mut counts = vec![];
+  if written > 0 {
+    counts.push(format!("{written} written"));
+  }
+  if updated > 0 {
+    counts.push(format!("{updated} updated"));
+  }
+  if removed > 0 {
+    counts.push(format!("{removed} removed"));
+  }
+  if !counts.is_empty() {
+    writeln!(writer, "{}", counts.join(" | ")).unwrap();
+  }
+  if !obsolete.is_empty() {
+    writeln!(
+      writer,
+      "{} obsolete snapshot{} found:",
+      obsolete.len(),
+      if obsolete.len() == 1 { "" } else { "s" }
+    )
+    .unwrap();
+    for (origin, name) in obsolete {
+      writeln!(
+        writer,
+        "{} {}",
+        name,
+        colors::gray(format!(
+          "=> {}",
+          to_relative_path_or_remote_url(cwd, origin)
+        ))
+      )
+      .unwrap();
+    }
+    writeln!(
+      writer,
+      "Run `deno test --update-snapshots` to remove obsolete snapshots."
+    )
+    .unwrap();
+  }
+}
+
 pub(super) fn report_summary(
   writer: &mut dyn std::io::Write,
   cwd: &Url,
@@ -168,6 +263,9 @@ pub(super) fn report_summary(
     }
   }
 
+  report_flaky_summary(writer, cwd, &summary.flaky);
+  report_snapshots_summary(writer, cwd, &summary.snapshots);
+
   let status = if summary.has_failed() {
     colors::red("FAILED").to_string()
   } else {
@@ -206,6 +304,10 @@ pub(super) fn report_summary(
     .unwrap()
   }
 
+  if !summary.flaky.is_empty() {
+    write!(summary_result, " | {} flaky", summary.flaky.len()).unwrap();
+  }
+
   if summary.measured > 0 {
     write!(summary_result, " | {} measured", summary.measured,).unwrap();
   }
@@ -214,6 +316,17 @@ pub(super) fn report_summary(
     write!(summary_result, " | {} filtered out", summary.filtered_out).unwrap()
   };
 
+  if let Some(shard) = &summary.shard {
+    write!(
+      summary_result,
+      " | shard {}/{} ({} skipped)",
+      shard.index,
+      shard.count,
+      format_shard_skipped(shard.skipped)
+    )
+    .unwrap()
+  };
+
   writeln!(
     writer,
     "\n{} | {} {}",
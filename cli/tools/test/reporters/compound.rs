@@ -25,6 +25,12 @@ impl TestReporter for CompoundTestReporter {
     }
   }
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_shard(shard);
+    }
+  }
+
   fn report_wait(&mut self, description: &TestDescription) {
     for reporter in &mut self.test_reporters {
       reporter.report_wait(description);
@@ -54,6 +60,25 @@ impl TestReporter for CompoundTestReporter {
     }
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    attempt: usize,
+    elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_retry(
+        description,
+        failure,
+        attempt,
+        elapsed,
+        discarded_steps,
+      );
+    }
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     for reporter in &mut self.test_reporters {
       reporter.report_uncaught_error(origin, error.clone());
@@ -85,6 +110,12 @@ impl TestReporter for CompoundTestReporter {
     }
   }
 
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_snapshots(summary);
+    }
+  }
+
   fn report_summary(
     &mut self,
     elapsed: &Duration,
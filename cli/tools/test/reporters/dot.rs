@@ -8,6 +8,8 @@ pub struct DotTestReporter {
   n: usize,
   width: usize,
   cwd: Url,
+  /// Number of failed attempts of the tests that were retried.
+  retries: HashMap<usize, usize>,
   summary: TestSummary,
 }
 
@@ -25,6 +27,7 @@ impl DotTestReporter {
       n: 0,
       width: console_width,
       cwd,
+      retries: Default::default(),
       summary: TestSummary::new(),
     }
   }
@@ -59,6 +62,7 @@ impl DotTestReporter {
       TestResult::Ignored => fmt_ignored(),
       TestResult::Failed(_failure) => fmt_failed(),
       TestResult::Cancelled => fmt_cancelled(),
+      TestResult::TimedOut(_) => fmt_failed(),
     };
 
     self.print_status(status);
@@ -90,6 +94,10 @@ impl TestReporter for DotTestReporter {
     self.summary.filtered_out += plan.filtered_out;
   }
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.summary.shard = Some(shard.clone());
+  }
+
   fn report_wait(&mut self, _description: &TestDescription) {
     // flush for faster feedback when line buffered
     std::io::stdout().flush().unwrap();
@@ -104,9 +112,13 @@ impl TestReporter for DotTestReporter {
     result: &TestResult,
     _elapsed: u64,
   ) {
+    let retries = self.retries.remove(&description.id);
     match &result {
       TestResult::Ok => {
         self.summary.passed += 1;
+        if let Some(retries) = retries {
+          self.summary.flaky.push((description.into(), retries));
+        }
       }
       TestResult::Ignored => {
         self.summary.ignored += 1;
@@ -121,11 +133,30 @@ impl TestReporter for DotTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::TimedOut(timeout) => {
+        self.summary.failed += 1;
+        self
+          .summary
+          .failures
+          .push((description.into(), TestFailure::TimedOut(timeout.clone())));
+      }
     }
 
     self.print_test_result(result);
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    _failure: &TestFailure,
+    attempt: usize,
+    _elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    self.retries.insert(description.id, attempt);
+    self.summary.discard_step_results(discarded_steps);
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     self.summary.failed += 1;
     self
@@ -179,6 +210,10 @@ impl TestReporter for DotTestReporter {
     self.print_test_step_result(result);
   }
 
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.summary.snapshots.push(summary.clone());
+  }
+
   fn report_summary(
     &mut self,
     elapsed: &Duration,
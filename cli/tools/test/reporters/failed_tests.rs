@@ -0,0 +1,162 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use super::*;
+
+/// Records which tests failed, so that `deno test --only-failed` can run them
+/// again. The record is updated each time a test module completes.
+pub struct FailedTestsReporter {
+  cache: TestFailuresCache,
+  // The names of the tests of each module, keyed by its origin
+  registered: HashMap<String, HashSet<String>>,
+  // Whether each test failed, keyed by its origin and name
+  results: IndexMap<(String, String), bool>,
+}
+
+impl FailedTestsReporter {
+  pub fn new(cache: TestFailuresCache) -> Self {
+    Self {
+      cache,
+      registered: HashMap::new(),
+      results: IndexMap::new(),
+    }
+  }
+
+  /// Replaces the records of each module that ran, so that the records of
+  /// tests that were renamed or deleted don't linger. Tests that still exist
+  /// but didn't run, e.g. because they were filtered out, keep their record.
+  fn write_results(&mut self) {
+    let mut results_by_origin: IndexMap<String, Vec<(String, bool)>> =
+      IndexMap::new();
+    for ((origin, name), failed) in self.results.drain(..) {
+      results_by_origin
+        .entry(origin)
+        .or_default()
+        .push((name, failed));
+    }
+    if results_by_origin.is_empty() {
+      return;
+    }
+    let previous_failures = self.cache.get_failures();
+    for (origin, results) in results_by_origin {
+      let Ok(specifier) = ModuleSpecifier::parse(&origin) else {
+        continue;
+      };
+      let registered = self.registered.get(&origin);
+      let kept_failures = previous_failures
+        .iter()
+        .filter(|failure| {
+          failure.specifier == specifier
+            && registered.is_some_and(|names| names.contains(&failure.name))
+            && !results.iter().any(|(name, _)| *name == failure.name)
+        })
+        .collect::<Vec<_>>();
+      self.cache.clear_module(&specifier);
+      for failure in kept_failures {
+        self
+          .cache
+          .set_failed(&specifier, &failure.name, failure.source_hash);
+      }
+      if !results.iter().any(|(_, failed)| *failed) {
+        continue;
+      }
+      // only local test modules can be checked for changes
+      let Some(source_hash) = test_module_source_hash(&specifier) else {
+        continue;
+      };
+      for (name, failed) in results {
+        if failed {
+          self.cache.set_failed(&specifier, &name, source_hash);
+        }
+      }
+    }
+  }
+}
+
+impl TestReporter for FailedTestsReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    self
+      .registered
+      .entry(description.origin.clone())
+      .or_default()
+      .insert(description.name.clone());
+  }
+  fn report_plan(&mut self, _plan: &TestPlan) {}
+  fn report_shard(&mut self, _shard: &TestShardSummary) {}
+  fn report_wait(&mut self, _description: &TestDescription) {}
+  fn report_slow(&mut self, _description: &TestDescription, _elapsed: u64) {}
+  fn report_output(&mut self, _output: &[u8]) {}
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    _elapsed: u64,
+  ) {
+    let failed = match result {
+      TestResult::Ok | TestResult::Ignored => false,
+      TestResult::Failed(_)
+      | TestResult::Cancelled
+      | TestResult::TimedOut(_) => true,
+    };
+    self.results.insert(
+      (description.origin.clone(), description.name.clone()),
+      failed,
+    );
+  }
+
+  fn report_retry(
+    &mut self,
+    _description: &TestDescription,
+    _failure: &TestFailure,
+    _attempt: usize,
+    _elapsed: u64,
+    _discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+  }
+
+  fn report_uncaught_error(&mut self, _origin: &str, _error: Box<JsError>) {}
+  fn report_step_register(&mut self, _description: &TestStepDescription) {}
+  fn report_step_wait(&mut self, _description: &TestStepDescription) {}
+
+  fn report_step_result(
+    &mut self,
+    _desc: &TestStepDescription,
+    _result: &TestStepResult,
+    _elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+  }
+
+  fn report_snapshots(&mut self, _summary: &TestSnapshotSummary) {}
+
+  fn report_summary(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+  }
+
+  fn report_sigint(
+    &mut self,
+    _tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+  }
+
+  fn report_completed(&mut self) {
+    self.write_results();
+  }
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    self.write_results();
+    Ok(())
+  }
+}
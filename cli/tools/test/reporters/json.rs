@@ -0,0 +1,344 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::PathBuf;
+
+use deno_core::serde_json::json;
+use deno_core::serde_json::Value;
+use deno_core::serde_json::{self};
+
+use super::fmt::to_relative_path_or_remote_url;
+use super::*;
+
+/// A test reporter that streams one JSON object per line for each test event,
+/// meant to be consumed by other tools.
+pub struct JsonTestReporter {
+  cwd: Url,
+  output_path: String,
+  writer: Box<dyn Write>,
+  summary: TestSummary,
+  retries: HashMap<usize, usize>,
+  // The first error that happened while writing, reported on flush
+  error: Option<std::io::Error>,
+}
+
+impl JsonTestReporter {
+  pub fn new(cwd: Url, output_path: String) -> Result<Self, AnyError> {
+    let writer: Box<dyn Write> = if output_path == "-" {
+      Box::new(std::io::stdout())
+    } else {
+      Box::new(
+        crate::util::fs::create_file(&PathBuf::from(&output_path))
+          .context("Failed to open JSON report file.")?,
+      )
+    };
+    Ok(Self {
+      cwd,
+      output_path,
+      writer,
+      summary: TestSummary::new(),
+      retries: HashMap::new(),
+      error: None,
+    })
+  }
+
+  fn write_event(&mut self, event: Value) {
+    if self.error.is_some() {
+      return;
+    }
+    let mut line =
+      serde_json::to_string(&event).expect("failed to serialize test event");
+    line.push('\n');
+    // write each event at once, so that it can be read while tests run
+    if let Err(err) = self.writer.write_all(line.as_bytes()) {
+      self.error = Some(err);
+    }
+  }
+
+  fn location(&self, location: &TestLocation) -> Value {
+    json!({
+      "file": to_relative_path_or_remote_url(&self.cwd, &location.file_name),
+      "line": location.line_number,
+      "column": location.column_number,
+    })
+  }
+
+  fn failure(failure: &TestFailure) -> Value {
+    let kind = match failure {
+      TestFailure::JsError(_) => "jsError",
+      TestFailure::FailedSteps(_) => "failedSteps",
+      TestFailure::IncompleteSteps => "incompleteSteps",
+      TestFailure::Leaked(_, _) => "leaked",
+      TestFailure::TimedOut(_) => "timedOut",
+      TestFailure::Incomplete => "incomplete",
+      TestFailure::OverlapsWithSanitizers(_) => "overlapsWithSanitizers",
+      TestFailure::HasSanitizersAndOverlaps(_) => "hasSanitizersAndOverlaps",
+    };
+    let mut value = json!({
+      "kind": kind,
+      "message": failure.overview(),
+      "detail": failure.detail(),
+    });
+    if let TestFailure::JsError(js_error) = failure {
+      value["error"] = json!(js_error);
+    }
+    value
+  }
+}
+
+impl TestReporter for JsonTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    self.write_event(json!({
+      "type": "register",
+      "id": description.id,
+      "name": description.name,
+      "origin": to_relative_path_or_remote_url(&self.cwd, &description.origin),
+      "location": self.location(&description.location),
+      "ignore": description.ignore,
+      "only": description.only,
+    }));
+  }
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    self.summary.total += plan.total;
+    self.summary.filtered_out += plan.filtered_out;
+    self.write_event(json!({
+      "type": "plan",
+      "origin": to_relative_path_or_remote_url(&self.cwd, &plan.origin),
+      "total": plan.total,
+      "filteredOut": plan.filtered_out,
+      "usedOnly": plan.used_only,
+    }));
+  }
+
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.summary.shard = Some(shard.clone());
+    self.write_event(json!({
+      "type": "shard",
+      "index": shard.index,
+      "count": shard.count,
+      "skipped": shard.skipped,
+    }));
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    self.write_event(json!({
+      "type": "wait",
+      "id": description.id,
+    }));
+  }
+
+  fn report_slow(&mut self, description: &TestDescription, elapsed: u64) {
+    self.write_event(json!({
+      "type": "slow",
+      "id": description.id,
+      "elapsed": elapsed,
+    }));
+  }
+
+  fn report_output(&mut self, output: &[u8]) {
+    self.write_event(json!({
+      "type": "output",
+      "output": String::from_utf8_lossy(output),
+    }));
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let retries = self.retries.remove(&description.id);
+    let (status, failure) = match result {
+      TestResult::Ok => {
+        self.summary.passed += 1;
+        if let Some(retries) = retries {
+          self.summary.flaky.push((description.into(), retries));
+        }
+        ("ok", None)
+      }
+      TestResult::Ignored => {
+        self.summary.ignored += 1;
+        ("ignored", None)
+      }
+      TestResult::Failed(failure) => {
+        self.summary.failed += 1;
+        ("failed", Some(Self::failure(failure)))
+      }
+      TestResult::Cancelled => {
+        self.summary.failed += 1;
+        ("cancelled", None)
+      }
+      TestResult::TimedOut(timeout) => {
+        self.summary.failed += 1;
+        (
+          "timedOut",
+          Some(Self::failure(&TestFailure::TimedOut(timeout.clone()))),
+        )
+      }
+    };
+    self.write_event(json!({
+      "type": "result",
+      "id": description.id,
+      "name": description.name,
+      "origin": to_relative_path_or_remote_url(&self.cwd, &description.origin),
+      "location": self.location(&description.location),
+      "status": status,
+      "duration": elapsed,
+      "retries": retries.unwrap_or(0),
+      "failure": failure,
+    }));
+  }
+
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    attempt: usize,
+    elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    self.retries.insert(description.id, attempt);
+    self.summary.discard_step_results(discarded_steps);
+    self.write_event(json!({
+      "type": "retry",
+      "id": description.id,
+      "name": description.name,
+      "attempt": attempt,
+      "duration": elapsed,
+      "failure": Self::failure(failure),
+    }));
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.summary.failed += 1;
+    self.write_event(json!({
+      "type": "uncaughtError",
+      "origin": to_relative_path_or_remote_url(&self.cwd, origin),
+      "message": error.exception_message,
+      "detail": format_test_error(&error),
+      "error": error,
+    }));
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    self.write_event(json!({
+      "type": "stepRegister",
+      "id": description.id,
+      "name": description.name,
+      "origin": to_relative_path_or_remote_url(&self.cwd, &description.origin),
+      "location": self.location(&description.location),
+      "level": description.level,
+      "parentId": description.parent_id,
+      "rootId": description.root_id,
+    }));
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    self.write_event(json!({
+      "type": "stepWait",
+      "id": description.id,
+    }));
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let (status, failure) = match result {
+      TestStepResult::Ok => {
+        self.summary.passed_steps += 1;
+        ("ok", None)
+      }
+      TestStepResult::Ignored => {
+        self.summary.ignored_steps += 1;
+        ("ignored", None)
+      }
+      TestStepResult::Failed(failure) => {
+        self.summary.failed_steps += 1;
+        ("failed", Some(Self::failure(failure)))
+      }
+    };
+    self.write_event(json!({
+      "type": "stepResult",
+      "id": desc.id,
+      "name": desc.name,
+      "rootId": desc.root_id,
+      "status": status,
+      "duration": elapsed,
+      "failure": failure,
+    }));
+  }
+
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.write_event(json!({
+      "type": "snapshots",
+      "origin": to_relative_path_or_remote_url(&self.cwd, &summary.origin),
+      "written": summary.written,
+      "updated": summary.updated,
+      "removed": summary.removed,
+      "obsolete": summary.obsolete,
+    }));
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self.write_event(json!({
+      "type": "summary",
+      "ok": !self.summary.has_failed(),
+      "total": self.summary.total,
+      "passed": self.summary.passed,
+      "failed": self.summary.failed,
+      "ignored": self.summary.ignored,
+      "filteredOut": self.summary.filtered_out,
+      "passedSteps": self.summary.passed_steps,
+      "failedSteps": self.summary.failed_steps,
+      "ignoredSteps": self.summary.ignored_steps,
+      "flaky": self.summary.flaky.len(),
+      "duration": elapsed.as_millis() as u64,
+    }));
+  }
+
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let mut pending = tests_pending.iter().collect::<Vec<_>>();
+    pending.sort();
+    self.write_event(json!({
+      "type": "sigint",
+      "pending": pending,
+    }));
+  }
+
+  fn report_completed(&mut self) {}
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    let result = match self.error.take() {
+      Some(err) => Err(err),
+      None => self.writer.flush(),
+    };
+    result.with_context(|| {
+      if self.output_path == "-" {
+        "Failed to write JSON report to stdout".to_string()
+      } else {
+        format!("Failed to write JSON report to {}", self.output_path)
+      }
+    })
+  }
+}
@@ -15,6 +15,9 @@ pub struct JunitTestReporter {
   // from child to parent to build the full test name that reflects the test
   // hierarchy.
   test_name_tree: TestNameTree,
+  // Failed attempts of the tests that were retried, by the Test ID
+  reruns: HashMap<usize, Vec<quick_junit::TestRerun>>,
+  shard: Option<TestShardSummary>,
 }
 
 impl JunitTestReporter {
@@ -24,6 +27,8 @@ impl JunitTestReporter {
       output_path,
       cases: IndexMap::new(),
       test_name_tree: TestNameTree::new(),
+      reruns: HashMap::new(),
+      shard: None,
     }
   }
 
@@ -45,6 +50,15 @@ impl JunitTestReporter {
         description: None,
         reruns: vec![],
       },
+      TestResult::TimedOut(timeout) => {
+        quick_junit::TestCaseStatus::NonSuccess {
+          kind: quick_junit::NonSuccessKind::Failure,
+          message: Some(timeout.overview()),
+          ty: None,
+          description: Some(timeout.to_string()),
+          reruns: vec![],
+        }
+      }
     }
   }
 
@@ -92,6 +106,10 @@ impl TestReporter for JunitTestReporter {
 
   fn report_plan(&mut self, _plan: &TestPlan) {}
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.shard = Some(shard.clone());
+  }
+
   fn report_slow(&mut self, _description: &TestDescription, _elapsed: u64) {}
   fn report_wait(&mut self, _description: &TestDescription) {}
 
@@ -113,6 +131,37 @@ impl TestReporter for JunitTestReporter {
     if let Some(case) = self.cases.get_mut(&description.id) {
       case.status = Self::convert_status(result);
       case.set_time(Duration::from_millis(elapsed));
+      let reruns = self.reruns.remove(&description.id).unwrap_or_default();
+      match &mut case.status {
+        quick_junit::TestCaseStatus::Success { flaky_runs } => {
+          *flaky_runs = reruns;
+        }
+        quick_junit::TestCaseStatus::NonSuccess { reruns: runs, .. } => {
+          *runs = reruns;
+        }
+        quick_junit::TestCaseStatus::Skipped { .. } => {}
+      }
+    }
+  }
+
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    _attempt: usize,
+    elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    let mut rerun =
+      quick_junit::TestRerun::new(quick_junit::NonSuccessKind::Failure);
+    rerun
+      .set_message(failure.overview())
+      .set_description(failure.detail())
+      .set_time(Duration::from_millis(elapsed));
+    self.reruns.entry(description.id).or_default().push(rerun);
+    // Only the steps of the last attempt are reported.
+    for (step, _) in discarded_steps {
+      self.cases.shift_remove(&step.id);
     }
   }
 
@@ -158,6 +207,8 @@ impl TestReporter for JunitTestReporter {
     }
   }
 
+  fn report_snapshots(&mut self, _summary: &TestSnapshotSummary) {}
+
   fn report_summary(
     &mut self,
     _elapsed: &Duration,
@@ -214,6 +265,15 @@ impl TestReporter for JunitTestReporter {
         });
     }
 
+    if let Some(shard) = &self.shard {
+      for suite in suites.values_mut() {
+        suite.add_property(quick_junit::Property::new(
+          "shard",
+          format!("{}/{}", shard.index, shard.count),
+        ));
+      }
+    }
+
     let mut report = quick_junit::Report::new("deno test");
     report
       .set_time(*elapsed)
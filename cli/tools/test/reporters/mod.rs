@@ -5,12 +5,16 @@ use super::*;
 mod common;
 mod compound;
 mod dot;
+mod failed_tests;
+mod json;
 mod junit;
 mod pretty;
 mod tap;
 
 pub use compound::CompoundTestReporter;
 pub use dot::DotTestReporter;
+pub use failed_tests::FailedTestsReporter;
+pub use json::JsonTestReporter;
 pub use junit::JunitTestReporter;
 pub use pretty::PrettyTestReporter;
 pub use tap::TapTestReporter;
@@ -18,6 +22,7 @@ pub use tap::TapTestReporter;
 pub trait TestReporter {
   fn report_register(&mut self, description: &TestDescription);
   fn report_plan(&mut self, plan: &TestPlan);
+  fn report_shard(&mut self, shard: &TestShardSummary);
   fn report_wait(&mut self, description: &TestDescription);
   fn report_slow(&mut self, description: &TestDescription, elapsed: u64);
   fn report_output(&mut self, output: &[u8]);
@@ -27,6 +32,14 @@ pub trait TestReporter {
     result: &TestResult,
     elapsed: u64,
   );
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    attempt: usize,
+    elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  );
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>);
   fn report_step_register(&mut self, description: &TestStepDescription);
   fn report_step_wait(&mut self, description: &TestStepDescription);
@@ -38,6 +51,7 @@ pub trait TestReporter {
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
   );
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary);
   fn report_summary(
     &mut self,
     elapsed: &Duration,
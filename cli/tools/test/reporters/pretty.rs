@@ -18,6 +18,8 @@ pub struct PrettyTestReporter {
   ended_tests: bool,
   child_results_buffer:
     HashMap<usize, IndexMap<usize, (TestStepDescription, TestStepResult, u64)>>,
+  /// Number of failed attempts of the tests that were retried.
+  retries: HashMap<usize, usize>,
   summary: TestSummary,
   writer: Box<dyn std::io::Write>,
 }
@@ -43,6 +45,7 @@ impl PrettyTestReporter {
       started_tests: false,
       ended_tests: false,
       child_results_buffer: Default::default(),
+      retries: Default::default(),
       summary: TestSummary::new(),
       writer: Box::new(std::io::stdout()),
     }
@@ -190,6 +193,10 @@ impl TestReporter for PrettyTestReporter {
     self.in_new_line = true;
   }
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    self.summary.shard = Some(shard.clone());
+  }
+
   fn report_wait(&mut self, description: &TestDescription) {
     if !self.parallel {
       self.force_report_wait(description);
@@ -246,9 +253,13 @@ impl TestReporter for PrettyTestReporter {
     result: &TestResult,
     elapsed: u64,
   ) {
+    let retries = self.retries.remove(&description.id);
     match &result {
       TestResult::Ok => {
         self.summary.passed += 1;
+        if let Some(retries) = retries {
+          self.summary.flaky.push((description.into(), retries));
+        }
       }
       TestResult::Ignored => {
         self.summary.ignored += 1;
@@ -263,6 +274,13 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Cancelled => {
         self.summary.failed += 1;
       }
+      TestResult::TimedOut(timeout) => {
+        self.summary.failed += 1;
+        self
+          .summary
+          .failures
+          .push((description.into(), TestFailure::TimedOut(timeout.clone())));
+      }
     }
 
     if self.parallel {
@@ -275,10 +293,14 @@ impl TestReporter for PrettyTestReporter {
     }
 
     let status = match result {
+      TestResult::Ok if retries.is_some() => {
+        format!("{} {}", colors::green("ok"), colors::yellow("(flaky)"))
+      }
       TestResult::Ok => colors::green("ok").to_string(),
       TestResult::Ignored => colors::yellow("ignored").to_string(),
       TestResult::Failed(failure) => failure.format_label(),
       TestResult::Cancelled => colors::gray("cancelled").to_string(),
+      TestResult::TimedOut(_) => colors::red("TIMED OUT").to_string(),
     };
     write!(&mut self.writer, " {}", status).unwrap();
     if let TestResult::Failed(failure) = result {
@@ -296,6 +318,43 @@ impl TestReporter for PrettyTestReporter {
     self.scope_test_id = None;
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    attempt: usize,
+    elapsed: u64,
+    discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    self.retries.insert(description.id, attempt);
+    self.summary.discard_step_results(discarded_steps);
+    for (step, _) in discarded_steps {
+      self.child_results_buffer.remove(&step.id);
+    }
+    self.child_results_buffer.remove(&description.id);
+
+    self.write_output_end();
+    if self.in_new_line || self.scope_test_id != Some(description.id) {
+      self.force_report_wait(description);
+    }
+    write!(&mut self.writer, " {}", failure.format_label()).unwrap();
+    if let Some(inline_summary) = failure.format_inline_summary() {
+      write!(&mut self.writer, " ({})", inline_summary).unwrap();
+    }
+    writeln!(
+      &mut self.writer,
+      " {} {}",
+      colors::gray(format!("({})", display::human_elapsed(elapsed.into()))),
+      colors::yellow("retrying")
+    )
+    .unwrap();
+    self.in_new_line = true;
+    self.scope_test_id = None;
+    if !self.parallel {
+      self.force_report_wait(description);
+    }
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     self.summary.failed += 1;
     self
@@ -388,6 +447,10 @@ impl TestReporter for PrettyTestReporter {
     }
   }
 
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    self.summary.snapshots.push(summary.clone());
+  }
+
   fn report_summary(
     &mut self,
     elapsed: &Duration,
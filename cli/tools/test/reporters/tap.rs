@@ -20,6 +20,7 @@ pub struct TapTestReporter {
   n: usize,
   step_n: usize,
   step_results: HashMap<usize, Vec<(TestStepDescription, TestStepResult)>>,
+  retries: HashMap<usize, usize>,
 }
 
 #[allow(clippy::print_stdout)]
@@ -33,6 +34,7 @@ impl TapTestReporter {
       n: 0,
       step_n: 0,
       step_results: HashMap::new(),
+      retries: HashMap::new(),
     }
   }
 
@@ -135,6 +137,19 @@ impl TestReporter for TapTestReporter {
     }
   }
 
+  fn report_shard(&mut self, shard: &TestShardSummary) {
+    if !self.header {
+      println!("{}", VERSION_HEADER);
+      self.header = true;
+    }
+    println!(
+      "# shard {}/{} ({} skipped)",
+      shard.index,
+      shard.count,
+      common::format_shard_skipped(shard.skipped)
+    );
+  }
+
   fn report_wait(&mut self, _description: &TestDescription) {
     // flush for faster feedback when line buffered
     std::io::stdout().flush().unwrap();
@@ -166,20 +181,57 @@ impl TestReporter for TapTestReporter {
       TestResult::Ignored => ("ok", " # SKIP"),
       TestResult::Failed(_failure) => ("not ok", ""),
       TestResult::Cancelled => ("not ok", ""),
+      TestResult::TimedOut(_) => ("not ok", ""),
     };
     self.n += 1;
     Self::print_line(0, status, self.n, &description.name, directive);
 
-    if let TestResult::Failed(failure) = result {
+    let failure = match result {
+      TestResult::Failed(failure) => Some(failure.clone()),
+      TestResult::TimedOut(timeout) => {
+        Some(TestFailure::TimedOut(timeout.clone()))
+      }
+      _ => None,
+    };
+    if let Some(failure) = failure {
       Self::print_diagnostic(
         0,
-        failure,
+        &failure,
         DiagnosticLocation {
           file: to_relative_path_or_remote_url(&self.cwd, &description.origin),
           line: description.location.line_number,
         },
       );
     }
+
+    if let Some(retries) = self.retries.remove(&description.id) {
+      if matches!(result, TestResult::Ok) {
+        println!(
+          "# flaky: {} passed after {}",
+          Self::escape_description(&description.name),
+          common::format_retries(retries)
+        );
+      }
+    }
+  }
+
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    _failure: &TestFailure,
+    attempt: usize,
+    _elapsed: u64,
+    _discarded_steps: &[(TestStepDescription, TestStepResult)],
+  ) {
+    self.retries.insert(description.id, attempt);
+    // The subtests of the failed attempt are superseded by the next one.
+    self.step_results.remove(&description.id);
+    self.step_n = 0;
+    println!(
+      "# retrying: {} failed on attempt {}",
+      Self::escape_description(&description.name),
+      attempt
+    );
   }
 
   fn report_uncaught_error(&mut self, _origin: &str, _errorr: Box<JsError>) {}
@@ -214,6 +266,16 @@ impl TestReporter for TapTestReporter {
     self.print_step_result(desc, result);
   }
 
+  fn report_snapshots(&mut self, summary: &TestSnapshotSummary) {
+    for name in &summary.obsolete {
+      println!(
+        "# obsolete snapshot: {} => {}",
+        to_relative_path_or_remote_url(&self.cwd, &summary.origin),
+        name
+      );
+    }
+  }
+
   fn report_summary(
     &mut self,
     _elapsed: &Duration,
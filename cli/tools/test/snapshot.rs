@@ -0,0 +1,515 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use deno_core::ModuleSpecifier;
+use serde::Deserialize;
+
+use crate::util::diff::diff;
+
+const SNAPSHOT_FILE_HEADER: &str = "// Deno Snapshot v1\n";
+
+/// How snapshot assertions behave when a snapshot differs or is missing.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum TestSnapshotMode {
+  /// Compare against the existing snapshots and write the missing ones.
+  #[default]
+  Assert,
+  /// Compare against the existing snapshots and fail when one is missing.
+  Ci,
+  /// Overwrite the existing snapshots and remove the obsolete ones.
+  Update,
+}
+
+impl TestSnapshotMode {
+  pub fn resolve(update_snapshots: bool) -> Self {
+    if update_snapshots {
+      return Self::Update;
+    }
+    match std::env::var("CI") {
+      Ok(value) if !matches!(value.as_str(), "" | "0" | "false") => Self::Ci,
+      _ => Self::Assert,
+    }
+  }
+}
+
+/// The outcome of the snapshot assertions of a single test module.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+pub struct TestSnapshotSummary {
+  pub origin: String,
+  pub written: usize,
+  pub updated: usize,
+  pub removed: usize,
+  /// Names of snapshots that were not asserted by any test that ran, but are
+  /// still in the snapshot file.
+  pub obsolete: Vec<String>,
+}
+
+impl TestSnapshotSummary {
+  pub fn is_empty(&self) -> bool {
+    self.written == 0
+      && self.updated == 0
+      && self.removed == 0
+      && self.obsolete.is_empty()
+  }
+}
+
+/// Tracks the snapshot assertions made by the tests of a test module, which
+/// are stored in `__snapshots__/<file name>.snap` next to the module.
+pub struct TestSnapshotState {
+  path: Option<PathBuf>,
+  mode: TestSnapshotMode,
+  existing: Option<BTreeMap<String, String>>,
+  asserted: BTreeMap<String, String>,
+  /// Names of the snapshots asserted by the test that is currently running.
+  current_test: Vec<String>,
+  tests: Vec<String>,
+  skipped_tests: Vec<String>,
+}
+
+impl TestSnapshotState {
+  pub fn new(specifier: &ModuleSpecifier, mode: TestSnapshotMode) -> Self {
+    let path = specifier.to_file_path().ok().and_then(|path| {
+      let file_name = path.file_name()?.to_string_lossy().to_string();
+      let dir = path.parent()?;
+      Some(dir.join("__snapshots__").join(format!("{file_name}.snap")))
+    });
+    Self {
+      path,
+      mode,
+      existing: None,
+      asserted: Default::default(),
+      current_test: Default::default(),
+      tests: Default::default(),
+      skipped_tests: Default::default(),
+    }
+  }
+
+  fn existing(&mut self) -> Result<&mut BTreeMap<String, String>, AnyError> {
+    if self.existing.is_none() {
+      let existing = match &self.path {
+        Some(path) => match fs::read_to_string(path) {
+          Ok(text) => parse_snapshot_file(&text).with_context(|| {
+            format!("Failed to parse snapshot file '{}'", path.display())
+          })?,
+          Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            BTreeMap::new()
+          }
+          Err(err) => {
+            return Err(err).with_context(|| {
+              format!("Failed to read snapshot file '{}'", path.display())
+            })
+          }
+        },
+        None => BTreeMap::new(),
+      };
+      self.existing = Some(existing);
+    }
+    Ok(self.existing.as_mut().unwrap())
+  }
+
+  /// Asserts that `actual` matches the stored snapshot called `name`.
+  pub fn assert(
+    &mut self,
+    name: String,
+    actual: String,
+  ) -> Result<(), AnyError> {
+    if self.path.is_none() {
+      return Err(generic_error(
+        "Snapshot assertions are only supported in local test modules",
+      ));
+    }
+    if self.asserted.contains_key(&name) {
+      return Err(generic_error(format!(
+        "Snapshot '{name}' was asserted more than once"
+      )));
+    }
+    let mode = self.mode;
+    let expected = self.existing()?.get(&name).cloned();
+    self.asserted.insert(name.clone(), actual.clone());
+    self.current_test.push(name.clone());
+    match expected {
+      Some(expected) if expected == actual => Ok(()),
+      Some(_) if mode == TestSnapshotMode::Update => Ok(()),
+      Some(expected) => Err(generic_error(format!(
+        "Snapshot '{}' does not match:\n\n{}\nRun `deno test --update-snapshots` to update it.",
+        name,
+        diff(&expected, &actual),
+      ))),
+      None if mode == TestSnapshotMode::Ci => Err(generic_error(format!(
+        "Missing snapshot '{name}'. Run `deno test` outside of CI to write it."
+      ))),
+      None => Ok(()),
+    }
+  }
+
+  /// Called before each run of a test.
+  pub fn start_test(&mut self) {
+    self.current_test.clear();
+  }
+
+  /// Forgets the snapshots asserted by the current run of a test, so that it
+  /// can assert them again when it's retried.
+  pub fn discard_test(&mut self) {
+    for name in self.current_test.drain(..) {
+      self.asserted.remove(&name);
+    }
+  }
+
+  /// Registers a test of the module, so that the snapshots named after it can
+  /// be told apart from the ones with a custom name.
+  pub fn add_test(&mut self, name: &str) {
+    self.tests.push(name.to_string());
+  }
+
+  /// Marks a test that did not run to completion, so its snapshots are not
+  /// considered obsolete.
+  pub fn skip_test(&mut self, name: &str) {
+    self.skipped_tests.push(name.to_string());
+  }
+
+  /// Whether a snapshot may belong to a test that didn't run to completion.
+  /// A snapshot with a custom name can't be traced back to its test, so it's
+  /// kept whenever a test was skipped.
+  fn is_skipped(&self, snapshot_name: &str) -> bool {
+    if self.skipped_tests.is_empty() {
+      return false;
+    }
+    let belongs_to =
+      |test_name: &String| is_snapshot_of_test(snapshot_name, test_name);
+    self.skipped_tests.iter().any(belongs_to)
+      || !self.tests.iter().any(belongs_to)
+  }
+
+  /// Writes the snapshot file if it changed and returns what happened to the
+  /// snapshots of the module.
+  pub fn finish(
+    mut self,
+    origin: String,
+  ) -> Result<TestSnapshotSummary, AnyError> {
+    let mut summary = TestSnapshotSummary {
+      origin,
+      ..Default::default()
+    };
+    let Some(path) = self.path.clone() else {
+      return Ok(summary);
+    };
+    let mode = self.mode;
+    let existing = std::mem::take(self.existing()?);
+    let obsolete = existing
+      .keys()
+      .filter(|name| !self.asserted.contains_key(*name))
+      .filter(|name| !self.is_skipped(name))
+      .cloned()
+      .collect::<HashSet<_>>();
+
+    let mut snapshots = existing.clone();
+    match mode {
+      TestSnapshotMode::Ci => {}
+      TestSnapshotMode::Assert => {
+        for (name, actual) in &self.asserted {
+          snapshots
+            .entry(name.clone())
+            .or_insert_with(|| actual.clone());
+        }
+      }
+      TestSnapshotMode::Update => {
+        snapshots.extend(self.asserted.clone());
+        snapshots.retain(|name, _| !obsolete.contains(name));
+        summary.removed = obsolete.len();
+      }
+    }
+    if mode != TestSnapshotMode::Update {
+      summary.obsolete = obsolete.into_iter().collect();
+      summary.obsolete.sort();
+    }
+    if mode != TestSnapshotMode::Ci {
+      for (name, actual) in &self.asserted {
+        match existing.get(name) {
+          None => summary.written += 1,
+          Some(expected)
+            if expected != actual && mode == TestSnapshotMode::Update =>
+          {
+            summary.updated += 1
+          }
+          Some(_) => {}
+        }
+      }
+    }
+
+    if snapshots != existing {
+      if snapshots.is_empty() {
+        fs::remove_file(&path).with_context(|| {
+          format!("Failed to remove snapshot file '{}'", path.display())
+        })?;
+      } else {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, serialize_snapshot_file(&snapshots)).with_context(
+          || format!("Failed to write snapshot file '{}'", path.display()),
+        )?;
+      }
+    }
+
+    Ok(summary)
+  }
+}
+
+/// Snapshots are named by their test name followed by a space and their
+/// counter, or by ` ... ` and the names of the steps for the snapshots of its
+/// steps. Unlike a plain prefix, this doesn't match e.g. the snapshots of a
+/// test `foo bar` for the test `foo`.
+fn is_snapshot_of_test(snapshot_name: &str, test_name: &str) -> bool {
+  let Some(rest) = snapshot_name.strip_prefix(test_name) else {
+    return false;
+  };
+  if rest.starts_with(" ... ") {
+    return true;
+  }
+  rest
+    .strip_prefix(' ')
+    .map(|counter| {
+      !counter.is_empty() && counter.chars().all(|c| c.is_ascii_digit())
+    })
+    .unwrap_or(false)
+}
+
+fn escape_template(text: &str) -> String {
+  text
+    .replace('\\', "\\\\")
+    .replace('`', "\\`")
+    .replace("${", "\\${")
+}
+
+fn serialize_snapshot_file(snapshots: &BTreeMap<String, String>) -> String {
+  let mut text = SNAPSHOT_FILE_HEADER.to_string();
+  for (name, value) in snapshots {
+    text.push_str(&format!(
+      "\nexports[`{}`] = `\n{}\n`;\n",
+      escape_template(name),
+      escape_template(value)
+    ));
+  }
+  text
+}
+
+/// Reads a template literal body up to the closing backtick, returning the
+/// unescaped text and the remaining input.
+fn read_template(input: &str) -> Result<(String, &str), AnyError> {
+  let mut text = String::new();
+  let mut chars = input.char_indices();
+  while let Some((index, c)) = chars.next() {
+    match c {
+      '`' => return Ok((text, &input[index + 1..])),
+      '\\' => match chars.next() {
+        Some((_, c)) => text.push(c),
+        None => break,
+      },
+      c => text.push(c),
+    }
+  }
+  Err(generic_error("Unterminated template literal"))
+}
+
+fn parse_snapshot_file(
+  text: &str,
+) -> Result<BTreeMap<String, String>, AnyError> {
+  let text = text.replace("\r\n", "\n");
+  let mut snapshots = BTreeMap::new();
+  let mut rest = text.strip_prefix(SNAPSHOT_FILE_HEADER).unwrap_or(&text);
+  loop {
+    rest = rest.trim_start();
+    if rest.is_empty() {
+      return Ok(snapshots);
+    }
+    let Some(after_prefix) = rest.strip_prefix("exports[`") else {
+      return Err(generic_error("Expected a snapshot entry"));
+    };
+    let (name, after_name) = read_template(after_prefix)?;
+    let Some(after_assign) = after_name.strip_prefix("] = `") else {
+      return Err(generic_error(format!(
+        "Expected a value for snapshot '{name}'"
+      )));
+    };
+    let (value, after_value) = read_template(after_assign)?;
+    let Some(after_entry) = after_value.strip_prefix(';') else {
+      return Err(generic_error(format!(
+        "Expected ';' after snapshot '{name}'"
+      )));
+    };
+    let value = value
+      .strip_prefix('\n')
+      .and_then(|value| value.strip_suffix('\n'))
+      .unwrap_or(&value)
+      .to_string();
+    snapshots.insert(name, value);
+    rest = after_entry;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn snapshot_file_roundtrip() {
+    let mut snapshots = BTreeMap::new();
+    snapshots.insert("simple 1".to_string(), "{ a: 1 }".to_string());
+    snapshots.insert(
+      "nested ... step 1".to_string(),
+      "[\n  \"multi\",\n  \"line\",\n]".to_string(),
+    );
+    snapshots
+      .insert("escapes `1`".to_string(), "`${value}` \\ \\n".to_string());
+    let text = serialize_snapshot_file(&snapshots);
+    assert!(text.starts_with(SNAPSHOT_FILE_HEADER));
+    assert!(text.contains(
+      "exports[`escapes \\`1\\``] = `\n\\`\\${value}\\` \\\\ \\\\n\n`;"
+    ));
+    assert_eq!(parse_snapshot_file(&text).unwrap(), snapshots);
+    assert_eq!(
+      parse_snapshot_file(&text.replace('\n', "\r\n")).unwrap(),
+      snapshots
+    );
+  }
+
+  #[test]
+  fn snapshot_file_invalid() {
+    assert!(parse_snapshot_file("exports[`a`] = `1").is_err());
+    assert!(parse_snapshot_file("exports[`a`] = 1;").is_err());
+    assert!(parse_snapshot_file("const a = 1;").is_err());
+    assert!(parse_snapshot_file(SNAPSHOT_FILE_HEADER)
+      .unwrap()
+      .is_empty());
+  }
+
+  #[test]
+  fn snapshot_state_modes() {
+    let temp_dir = test_util::TempDir::new();
+    let specifier =
+      ModuleSpecifier::from_file_path(temp_dir.path().join("a_test.ts"))
+        .unwrap();
+    let snap_path = temp_dir.path().join("__snapshots__/a_test.ts.snap");
+
+    // missing snapshots are written
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Assert);
+    state.assert("a 1".to_string(), "1".to_string()).unwrap();
+    state.assert("b 1".to_string(), "2".to_string()).unwrap();
+    assert!(state.assert("b 1".to_string(), "2".to_string()).is_err());
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert_eq!(summary.written, 2);
+    assert!(snap_path.exists());
+
+    // mismatches fail, and unasserted snapshots are obsolete unless skipped
+    let mut state = TestSnapshotState::new(&specifier, TestSnapshotMode::Ci);
+    assert!(state.assert("a 1".to_string(), "3".to_string()).is_err());
+    assert!(state.assert("c 1".to_string(), "3".to_string()).is_err());
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert_eq!(summary.obsolete, vec!["b 1".to_string()]);
+    let mut state = TestSnapshotState::new(&specifier, TestSnapshotMode::Ci);
+    state.skip_test("b");
+    state.assert("a 1".to_string(), "1".to_string()).unwrap();
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert!(summary.is_empty());
+
+    // updating overwrites and removes obsolete snapshots
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Update);
+    state.assert("a 1".to_string(), "3".to_string()).unwrap();
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert_eq!(summary.updated, 1);
+    assert_eq!(summary.removed, 1);
+    let snapshots =
+      parse_snapshot_file(&fs::read_to_string(&snap_path).unwrap()).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots.get("a 1").unwrap(), "3");
+  }
+
+  #[test]
+  fn snapshot_state_skipped_test() {
+    let specifier = ModuleSpecifier::parse("file:///a_test.ts").unwrap();
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Assert);
+    state.add_test("foo");
+    state.add_test("foo bar");
+    state.add_test("foobar");
+    assert!(!state.is_skipped("foo 1"));
+    assert!(!state.is_skipped("custom 1"));
+
+    state.skip_test("foo");
+    assert!(state.is_skipped("foo 1"));
+    assert!(state.is_skipped("foo 12"));
+    assert!(state.is_skipped("foo ... step 1"));
+    assert!(state.is_skipped("foo ... step ... nested 1"));
+    assert!(!state.is_skipped("foo bar 1"));
+    assert!(!state.is_skipped("foo bar ... step 1"));
+    assert!(!state.is_skipped("foo "));
+    assert!(!state.is_skipped("foobar 1"));
+    // custom names can belong to the skipped test
+    assert!(state.is_skipped("custom 1"));
+  }
+
+  #[test]
+  fn snapshot_state_update_filtered() {
+    let temp_dir = test_util::TempDir::new();
+    let specifier =
+      ModuleSpecifier::from_file_path(temp_dir.path().join("a_test.ts"))
+        .unwrap();
+    let snap_path = temp_dir.path().join("__snapshots__/a_test.ts.snap");
+
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Assert);
+    for name in ["a 1", "a 2", "b 1", "b ... step 1", "custom 1"] {
+      state.assert(name.to_string(), "1".to_string()).unwrap();
+    }
+    state.finish(specifier.to_string()).unwrap();
+
+    // only the obsolete snapshots of the test that ran are removed when the
+    // others are filtered out
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Update);
+    state.add_test("a");
+    state.add_test("b");
+    state.skip_test("b");
+    state.assert("a 1".to_string(), "1".to_string()).unwrap();
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert_eq!(summary.removed, 1);
+    let snapshots =
+      parse_snapshot_file(&fs::read_to_string(&snap_path).unwrap()).unwrap();
+    assert_eq!(
+      snapshots.keys().collect::<Vec<_>>(),
+      vec!["a 1", "b 1", "b ... step 1", "custom 1"]
+    );
+  }
+
+  #[test]
+  fn snapshot_state_retry() {
+    let temp_dir = test_util::TempDir::new();
+    let specifier =
+      ModuleSpecifier::from_file_path(temp_dir.path().join("a_test.ts"))
+        .unwrap();
+
+    let mut state =
+      TestSnapshotState::new(&specifier, TestSnapshotMode::Assert);
+    state.start_test();
+    state.assert("a 1".to_string(), "1".to_string()).unwrap();
+    // the failed attempt is forgotten, so the retry can assert again
+    state.discard_test();
+    state.start_test();
+    state.assert("a 1".to_string(), "2".to_string()).unwrap();
+    let summary = state.finish(specifier.to_string()).unwrap();
+    assert_eq!(summary.written, 1);
+    let snapshots = parse_snapshot_file(
+      &fs::read_to_string(temp_dir.path().join("__snapshots__/a_test.ts.snap"))
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(snapshots.get("a 1").unwrap(), "2");
+  }
+}
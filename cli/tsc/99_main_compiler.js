@@ -931,6 +931,7 @@ delete Object.prototype.__proto__;
    * @property {boolean} debug
    * @property {string[]} rootNames
    * @property {boolean} localOnly
+   * @property {string[]} lintRules
    */
 
   /**
@@ -982,10 +983,246 @@ delete Object.prototype.__proto__;
     return options;
   }
 
+  /**
+   * @param {ts.Type} type
+   * @returns {readonly ts.Type[]}
+   */
+  function unionTypeParts(type) {
+    return type.isUnion() ? type.types : [type];
+  }
+
+  /**
+   * Whether a type has a callable `then` property, like a Promise.
+   * @param {ts.TypeChecker} checker
+   * @param {ts.Type} type
+   * @param {ts.Node} node
+   */
+  function isThenableType(checker, type, node) {
+    const then = type.getProperty("then");
+    if (then == null) {
+      return false;
+    }
+    const thenType = checker.getTypeOfSymbolAtLocation(then, node);
+    return thenType.getCallSignatures().length > 0;
+  }
+
+  /**
+   * @param {ts.Type} type
+   */
+  function isUnknownType(type) {
+    return (type.flags &
+      (ts.TypeFlags.AnyOrUnknown | ts.TypeFlags.Instantiable)) !== 0;
+  }
+
+  /**
+   * @param {ts.Expression} node
+   * @returns {ts.Expression}
+   */
+  function skipParentheses(node) {
+    while (ts.isParenthesizedExpression(node)) {
+      node = node.expression;
+    }
+    return node;
+  }
+
+  /**
+   * Whether an expression statement creates a Promise that is neither
+   * awaited, handled nor explicitly ignored with `void`.
+   * @param {ts.TypeChecker} checker
+   * @param {ts.Expression} node
+   * @returns {boolean}
+   */
+  function isFloatingPromise(checker, node) {
+    node = skipParentheses(node);
+    if (ts.isVoidExpression(node)) {
+      return false;
+    }
+    if (
+      ts.isBinaryExpression(node) &&
+      node.operatorToken.kind >= ts.SyntaxKind.FirstAssignment &&
+      node.operatorToken.kind <= ts.SyntaxKind.LastAssignment
+    ) {
+      return false;
+    }
+    if (
+      ts.isCallExpression(node) &&
+      ts.isPropertyAccessExpression(node.expression)
+    ) {
+      const name = node.expression.name.text;
+      if (name === "catch" && node.arguments.length > 0) {
+        return false;
+      }
+      if (name === "then" && node.arguments.length > 1) {
+        return false;
+      }
+      if (name === "finally") {
+        return isFloatingPromise(checker, node.expression.expression);
+      }
+    }
+    return unionTypeParts(checker.getTypeAtLocation(node))
+      .some((t) => isThenableType(checker, t, node));
+  }
+
+  /**
+   * Whether an expression used as a condition is always a Promise, which is
+   * always truthy.
+   * @param {ts.TypeChecker} checker
+   * @param {ts.Expression} node
+   */
+  function isPromiseCondition(checker, node) {
+    node = skipParentheses(node);
+    return unionTypeParts(checker.getTypeAtLocation(node))
+      .every((t) => isThenableType(checker, t, node));
+  }
+
+  /**
+   * Whether a function that returns a Promise is passed where a function
+   * returning `void` is expected, so the Promise would be ignored.
+   * @param {ts.TypeChecker} checker
+   * @param {ts.Expression} node
+   */
+  function isPromiseReturnedForVoid(checker, node) {
+    const returnsThenable = checker.getTypeAtLocation(node)
+      .getCallSignatures()
+      .some((s) =>
+        unionTypeParts(checker.getReturnTypeOfSignature(s))
+          .some((t) => isThenableType(checker, t, node))
+      );
+    if (!returnsThenable) {
+      return false;
+    }
+    const contextualType = checker.getContextualType(node);
+    if (contextualType == null) {
+      return false;
+    }
+    const expectedSignatures = unionTypeParts(contextualType)
+      .flatMap((t) => t.getCallSignatures());
+    return expectedSignatures.length > 0 &&
+      expectedSignatures.every((s) =>
+        (checker.getReturnTypeOfSignature(s).flags & ts.TypeFlags.Void) !== 0
+      );
+  }
+
+  /**
+   * @typedef {object} LintDiagnostic
+   * @property {string} code
+   * @property {string} message
+   * @property {string | null} hint
+   * @property {number} start
+   * @property {number} end
+   */
+
+  /**
+   * Runs the lint rules that need type information on a file. These are
+   * enabled with the `lint.rules.include` configuration, see
+   * cli/tools/lint/type_aware.rs.
+   * @param {ts.Program} program
+   * @param {ts.SourceFile} sourceFile
+   * @param {string[]} rules
+   * @returns {LintDiagnostic[]}
+   */
+  function lintSourceFile(program, sourceFile, rules) {
+    const checker = program.getTypeChecker();
+    const enabled = new Set(rules);
+    /** @type {LintDiagnostic[]} */
+    const diagnostics = [];
+    /**
+     * @param {string} code
+     * @param {ts.Node} node
+     * @param {string} message
+     * @param {string} hint
+     */
+    function report(code, node, message, hint) {
+      diagnostics.push({
+        code,
+        message,
+        hint,
+        start: node.getStart(sourceFile),
+        end: node.getEnd(),
+      });
+    }
+    /** @param {ts.Expression | undefined} condition */
+    function checkCondition(condition) {
+      if (condition != null && isPromiseCondition(checker, condition)) {
+        report(
+          "no-misused-promises",
+          condition,
+          "Expected a non-Promise value in a condition.",
+          "A Promise is always truthy. Did you forget to `await` it?",
+        );
+      }
+    }
+    /** @param {ts.Node} node */
+    function visit(node) {
+      if (enabled.has("no-floating-promises")) {
+        if (
+          ts.isExpressionStatement(node) &&
+          isFloatingPromise(checker, node.expression)
+        ) {
+          report(
+            "no-floating-promises",
+            node.expression,
+            "Promises must be awaited, returned or have their rejections handled.",
+            "Add `await`, handle the rejection with `.catch()`, or mark the Promise as intentionally ignored with `void`.",
+          );
+        }
+      }
+      if (enabled.has("no-misused-promises")) {
+        if (
+          ts.isIfStatement(node) || ts.isWhileStatement(node) ||
+          ts.isDoStatement(node)
+        ) {
+          checkCondition(node.expression);
+        } else if (ts.isForStatement(node)) {
+          checkCondition(node.condition);
+        } else if (ts.isConditionalExpression(node)) {
+          checkCondition(node.condition);
+        } else if (
+          ts.isPrefixUnaryExpression(node) &&
+          node.operator === ts.SyntaxKind.ExclamationToken
+        ) {
+          checkCondition(node.operand);
+        } else if (ts.isCallExpression(node) || ts.isNewExpression(node)) {
+          for (const argument of node.arguments ?? []) {
+            if (isPromiseReturnedForVoid(checker, argument)) {
+              report(
+                "no-misused-promises",
+                argument,
+                "Promise-returning function provided where a void return was expected.",
+                "The returned Promise is ignored, so its rejections are unhandled. Handle them inside the function instead.",
+              );
+            }
+          }
+        }
+      }
+      if (enabled.has("await-thenable") && ts.isAwaitExpression(node)) {
+        const operand = skipParentheses(node.expression);
+        const types = unionTypeParts(checker.getTypeAtLocation(operand));
+        if (
+          !types.some((t) =>
+            isUnknownType(t) || isThenableType(checker, t, operand)
+          )
+        ) {
+          report(
+            "await-thenable",
+            node,
+            "Unexpected `await` of a non-Promise value.",
+            "Remove the `await`.",
+          );
+        }
+      }
+      ts.forEachChild(node, visit);
+    }
+    visit(sourceFile);
+    return diagnostics;
+  }
+
   /** The API that is called by Rust when executing a request.
    * @param {Request} request
    */
-  function exec({ config, debug: debugFlag, rootNames, localOnly }) {
+  function exec(
+    { config, debug: debugFlag, rootNames, localOnly, lintRules },
+  ) {
     setLogDebug(debugFlag, "TS");
     performanceStart();
 
@@ -1024,6 +1261,32 @@ delete Object.prototype.__proto__;
         })
       : undefined;
 
+    if (lintRules.length > 0) {
+      // lint the files instead of type checking them
+      const lintProgram = program.getProgram();
+      /** @type {Record<string, LintDiagnostic[]>} */
+      const lintDiagnostics = {};
+      for (const sourceFile of checkFiles ?? lintProgram.getSourceFiles()) {
+        if (!sourceFile.isDeclarationFile) {
+          lintDiagnostics[sourceFile.fileName] = lintSourceFile(
+            lintProgram,
+            sourceFile,
+            lintRules,
+          );
+        }
+      }
+
+      performanceProgram({ program });
+
+      ops.op_respond({
+        diagnostics: [],
+        lintDiagnostics,
+        stats: performanceEnd(),
+      });
+      debug("<<< exec stop");
+      return;
+    }
+
     if (checkFiles != null) {
       // When calling program.getSemanticDiagnostics(...) with a source file, we
       // need to call this code first in order to get it to invalidate cached
@@ -1259,6 +1522,38 @@ delete Object.prototype.__proto__;
       case "$getAssets": {
         return respond(id, getAssets());
       }
+      case "$getLintDiagnostics": {
+        const projectVersion = args[2];
+        // see `$getDiagnostics`
+        if (projectVersionCache && projectVersion !== projectVersionCache) {
+          return respond(id, {});
+        }
+        try {
+          const program = ls.getProgram();
+          /** @type {Record<string, LintDiagnostic[]>} */
+          const diagnosticMap = {};
+          for (const specifier of args[0]) {
+            const sourceFile = program?.getSourceFile(specifier);
+            if (sourceFile != null) {
+              diagnosticMap[specifier] = lintSourceFile(
+                program,
+                sourceFile,
+                args[1],
+              );
+            }
+          }
+          return respond(id, diagnosticMap);
+        } catch (e) {
+          if (!isCancellationError(e)) {
+            return respond(
+              id,
+              {},
+              formatErrorWithArgs(e, [id, method, args, scope, maybeChange]),
+            );
+          }
+          return respond(id, {});
+        }
+      }
       case "$getDiagnostics": {
         const projectVersion = args[1];
         // there's a possibility that we receive a change notification
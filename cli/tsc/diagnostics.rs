@@ -9,9 +9,23 @@ use deno_core::serde::Deserializer;
 use deno_core::serde::Serialize;
 use deno_core::serde::Serializer;
 use deno_core::sourcemap::SourceMap;
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt;
 
+use crate::util::sarif::SarifArtifactLocation;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLocation;
+use crate::util::sarif::SarifLog;
+use crate::util::sarif::SarifMessage;
+use crate::util::sarif::SarifPhysicalLocation;
+use crate::util::sarif::SarifRegion;
+use crate::util::sarif::SarifReportingDescriptor;
+use crate::util::sarif::SarifResult;
+use crate::util::sarif::SarifRun;
+use crate::util::sarif::SarifTool;
+use crate::util::sarif::SarifToolComponent;
+
 const MAX_SOURCE_LINE_LENGTH: usize = 150;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -143,6 +157,72 @@ impl Diagnostic {
     self.code != TS6133
   }
 
+  /// The id of the rule of this diagnostic in a SARIF log.
+  fn sarif_rule_id(&self) -> String {
+    if self.code >= 900001 {
+      "deno".to_string()
+    } else {
+      format!("TS{}", self.code)
+    }
+  }
+
+  fn sarif_message(&self) -> String {
+    match &self.message_chain {
+      Some(message_chain) => message_chain.format_message(0),
+      None => self.message_text.clone().unwrap_or_default(),
+    }
+  }
+
+  fn sarif_location(&self) -> Option<SarifLocation> {
+    let file_name = self.file_name.as_ref()?;
+    let region = match (&self.original_source_start, &self.start, &self.end) {
+      // the end is only known in the fast check module
+      (Some(start), _, _) => Some((start, start)),
+      (None, Some(start), Some(end)) => Some((start, end)),
+      _ => None,
+    };
+    Some(SarifLocation {
+      physical_location: SarifPhysicalLocation {
+        artifact_location: SarifArtifactLocation {
+          uri: file_name.clone(),
+        },
+        region: region.map(|(start, end)| SarifRegion {
+          start_line: start.line as usize + 1,
+          start_column: start.character as usize + 1,
+          end_line: end.line as usize + 1,
+          end_column: end.character as usize + 1,
+        }),
+      },
+      message: None,
+    })
+  }
+
+  fn to_sarif_result(&self) -> SarifResult {
+    SarifResult {
+      rule_id: self.sarif_rule_id(),
+      level: match self.category {
+        DiagnosticCategory::Error => SarifLevel::Error,
+        DiagnosticCategory::Warning => SarifLevel::Warning,
+        DiagnosticCategory::Suggestion | DiagnosticCategory::Message => {
+          SarifLevel::Note
+        }
+      },
+      message: SarifMessage::new(self.sarif_message()),
+      locations: self.sarif_location().into_iter().collect(),
+      related_locations: self
+        .related_information
+        .iter()
+        .flatten()
+        .filter_map(|related| {
+          let mut location = related.sarif_location()?;
+          location.message = Some(SarifMessage::new(related.sarif_message()));
+          Some(location)
+        })
+        .collect(),
+      fixes: Vec::new(),
+    }
+  }
+
   fn fmt_category_and_code(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let category = match self.category {
       DiagnosticCategory::Error => "ERROR",
@@ -294,6 +374,37 @@ impl Diagnostics {
     self.0.is_empty()
   }
 
+  /// Converts the diagnostics to a SARIF log.
+  pub fn to_sarif_log(&self) -> SarifLog {
+    let rule_ids = self
+      .0
+      .iter()
+      .map(|d| d.sarif_rule_id())
+      .collect::<BTreeSet<_>>();
+    SarifLog::new(vec![SarifRun {
+      tool: SarifTool {
+        driver: SarifToolComponent {
+          name: "deno check".to_string(),
+          information_uri:
+            "https://docs.deno.com/runtime/manual/advanced/typescript/overview"
+              .to_string(),
+          version: crate::version::deno().to_string(),
+          rules: rule_ids
+            .into_iter()
+            .map(|id| SarifReportingDescriptor {
+              id,
+              short_description: None,
+              help_uri: None,
+              properties: None,
+            })
+            .collect(),
+        },
+      },
+      results: self.0.iter().map(|d| d.to_sarif_result()).collect(),
+      invocations: Vec::new(),
+    }])
+  }
+
   /// Modifies all the diagnostics to have their display positions
   /// modified to point at the original source.
   pub fn apply_fast_check_source_maps(&mut self, graph: &ModuleGraph) {
@@ -583,4 +694,79 @@ mod tests {
     let actual = diagnostics.to_string();
     assert_eq!(strip_ansi_codes(&actual), "TS2552 [ERROR]: Cannot find name \'foo_Bar\'. Did you mean \'foo_bar\'?\nfoo_Bar();\n~~~~~~~\n    at test.ts:8:1\n\n    \'foo_bar\' is declared here.\n    function foo_bar() {\n             ~~~~~~~\n        at test.ts:4:10");
   }
+
+  #[test]
+  fn test_diagnostics_to_sarif() {
+    let value = json!([
+      {
+        "start": {
+          "line": 7,
+          "character": 0
+        },
+        "end": {
+          "line": 7,
+          "character": 7
+        },
+        "fileName": "file:///test.ts",
+        "messageText": "Cannot find name 'foo_Bar'. Did you mean 'foo_bar'?",
+        "sourceLine": "foo_Bar();",
+        "relatedInformation": [
+          {
+            "start": {
+              "line": 3,
+              "character": 9
+            },
+            "end": {
+              "line": 3,
+              "character": 16
+            },
+            "fileName": "file:///test.ts",
+            "messageText": "'foo_bar' is declared here.",
+            "sourceLine": "function foo_bar() {",
+            "category": 3,
+            "code": 2728
+          }
+        ],
+        "category": 1,
+        "code": 2552
+      }
+    ]);
+    let diagnostics: Diagnostics = serde_json::from_value(value).unwrap();
+    let actual = serde_json::to_value(diagnostics.to_sarif_log()).unwrap();
+    let run = &actual["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"], json!([{ "id": "TS2552" }]));
+    assert_eq!(
+      run["results"],
+      json!([{
+        "ruleId": "TS2552",
+        "level": "error",
+        "message": {
+          "text": "Cannot find name 'foo_Bar'. Did you mean 'foo_bar'?"
+        },
+        "locations": [{
+          "physicalLocation": {
+            "artifactLocation": { "uri": "file:///test.ts" },
+            "region": {
+              "startLine": 8,
+              "startColumn": 1,
+              "endLine": 8,
+              "endColumn": 8
+            }
+          }
+        }],
+        "relatedLocations": [{
+          "physicalLocation": {
+            "artifactLocation": { "uri": "file:///test.ts" },
+            "region": {
+              "startLine": 4,
+              "startColumn": 10,
+              "endLine": 4,
+              "endColumn": 17
+            }
+          },
+          "message": { "text": "'foo_bar' is declared here." }
+        }]
+      }])
+    );
+  }
 }
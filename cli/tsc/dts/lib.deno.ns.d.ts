@@ -762,6 +762,32 @@ declare namespace Deno {
      * ```
      */
     step(fn: (t: TestContext) => void | Promise<void>): Promise<boolean>;
+
+    /** Assert that `actual` matches the snapshot stored in
+     * `__snapshots__/<test file name>.snap` next to the test module.
+     *
+     * Missing snapshots are written, unless the `CI` environment variable is
+     * set in which case the assertion fails. Run `deno test --update-snapshots`
+     * to update snapshots that changed and remove obsolete ones.
+     *
+     * ```ts
+     * Deno.test("greeting", (t) => {
+     *   t.assertSnapshot({ greeting: "hello", name: "world" });
+     * });
+     * ```
+     */
+    assertSnapshot(actual: unknown, options?: TestSnapshotOptions): void;
+  }
+
+  /** @category Testing */
+  export interface TestSnapshotOptions {
+    /** The name of the snapshot, defaults to the full name of the current
+     * test or step. A counter is appended to tell apart multiple snapshots
+     * with the same name. */
+    name?: string;
+    /** Converts the value to the string that is stored in the snapshot file.
+     * Defaults to `Deno.inspect()` with sorted keys and no depth limit. */
+    serializer?: (actual: unknown) => string;
   }
 
   /** @category Testing */
@@ -840,6 +866,19 @@ declare namespace Deno {
      *
      * @default {"inherit"} */
     permissions?: PermissionOptions;
+    /** How many times to re-run the test if it fails. A test that passes
+     * after being retried is reported as flaky.
+     *
+     * Overrides the `--retries` flag of `deno test` for this test.
+     *
+     * @default {0} */
+    retry?: number;
+    /** Fail the test if it doesn't finish within this many milliseconds. The
+     * report lists the async operations, resources and timers that were still
+     * pending. Set this to `0` to disable the timeout.
+     *
+     * Overrides the `--timeout` flag of `deno test` for this test. */
+    timeout?: number;
   }
 
   /** Register a test which will be run when `deno test` is used on the command
@@ -318,6 +318,9 @@ pub struct Request {
   /// program.
   pub root_names: Vec<(ModuleSpecifier, MediaType)>,
   pub check_mode: TypeCheckMode,
+  /// The type-aware lint rules to run on the roots. When not empty, the roots
+  /// are linted instead of type checked.
+  pub lint_rules: Vec<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -328,6 +331,20 @@ pub struct Response {
   pub maybe_tsbuildinfo: Option<String>,
   /// Statistics from the check.
   pub stats: Stats,
+  /// The diagnostics of the type-aware lint rules by module, if any were
+  /// requested.
+  pub lint_diagnostics: HashMap<ModuleSpecifier, Vec<TypeAwareLintDiagnostic>>,
+}
+
+/// A problem reported by a type-aware lint rule, with its range in UTF-16
+/// code units of the file text.
+#[derive(Debug, Clone, Deserialize, Eq, PartialEq)]
+pub struct TypeAwareLintDiagnostic {
+  pub code: String,
+  pub message: String,
+  pub hint: Option<String>,
+  pub start: usize,
+  pub end: usize,
 }
 
 // TODO(bartlomieju): we have similar struct in `tsc.rs` - maybe at least change
@@ -820,6 +837,8 @@ fn op_is_node_file(state: &mut OpState, #[string] path: &str) -> bool {
 #[derive(Debug, Deserialize, Eq, PartialEq)]
 struct RespondArgs {
   pub diagnostics: Diagnostics,
+  #[serde(default, rename = "lintDiagnostics")]
+  pub lint_diagnostics: HashMap<String, Vec<TypeAwareLintDiagnostic>>,
   pub stats: Stats,
 }
 
@@ -900,6 +919,7 @@ pub fn exec(request: Request) -> Result<Response, AnyError> {
     "debug": request.debug,
     "rootNames": root_names,
     "localOnly": request.check_mode == TypeCheckMode::Local,
+    "lintRules": request.lint_rules,
   });
   let exec_source = format!("globalThis.exec({request_value})");
 
@@ -923,11 +943,23 @@ pub fn exec(request: Request) -> Result<Response, AnyError> {
     let diagnostics = response.diagnostics;
     let maybe_tsbuildinfo = state.maybe_tsbuildinfo;
     let stats = response.stats;
+    let lint_diagnostics = response
+      .lint_diagnostics
+      .into_iter()
+      .filter_map(|(file_name, diagnostics)| {
+        let specifier = match state.root_map.get(&file_name) {
+          Some(specifier) => specifier.clone(),
+          None => ModuleSpecifier::parse(&file_name).ok()?,
+        };
+        Some((specifier, diagnostics))
+      })
+      .collect();
 
     Ok(Response {
       diagnostics,
       maybe_tsbuildinfo,
       stats,
+      lint_diagnostics,
     })
   } else {
     Err(anyhow!("The response for the exec request was not set."))
@@ -1041,6 +1073,7 @@ mod tests {
       maybe_tsbuildinfo: None,
       root_names: vec![(specifier.clone(), MediaType::TypeScript)],
       check_mode: TypeCheckMode::All,
+      lint_rules: Vec::new(),
     };
     exec(request)
   }
@@ -1250,6 +1283,7 @@ mod tests {
           file_name: None,
           related_information: None,
         }]),
+        lint_diagnostics: HashMap::new(),
         stats: Stats(vec![("a".to_string(), 12)])
       })
     );
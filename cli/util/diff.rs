@@ -3,7 +3,9 @@
 use crate::colors;
 use dissimilar::diff as difference;
 use dissimilar::Chunk;
+use std::collections::HashMap;
 use std::fmt::Write as _;
+use std::ops::Range;
 
 /// Print diff of the same file_path, before and after formatting.
 ///
@@ -169,6 +171,213 @@ fn fmt_rem_text_highlight(x: &str) -> String {
   colors::white_on_red(x).to_string()
 }
 
+/// A change between two texts, as the 0-indexed ranges of the lines it
+/// replaces in the original text and of the lines it inserts from the edited
+/// text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineChange {
+  pub orig: Range<usize>,
+  pub edit: Range<usize>,
+}
+
+/// Diffs two texts line by line. The lines include their line break, so
+/// `str::split_inclusive('\n')` gives the lines the ranges refer to.
+///
+/// Unlike `diff`, this doesn't merge changes that are close to each other.
+pub fn diff_lines(orig_text: &str, edit_text: &str) -> Vec<LineChange> {
+  let orig_lines = orig_text.split_inclusive('\n').collect::<Vec<_>>();
+  let edit_lines = edit_text.split_inclusive('\n').collect::<Vec<_>>();
+  let mut matches = Vec::new();
+  match_lines(&orig_lines, 0, &edit_lines, 0, &mut matches);
+
+  // the changes are the gaps between the matching lines
+  let mut changes = Vec::new();
+  let mut orig_line = 0;
+  let mut edit_line = 0;
+  let end = (orig_lines.len(), edit_lines.len());
+  for (orig_match, edit_match) in matches.into_iter().chain([end]) {
+    if orig_line < orig_match || edit_line < edit_match {
+      changes.push(LineChange {
+        orig: orig_line..orig_match,
+        edit: edit_line..edit_match,
+      });
+    }
+    orig_line = orig_match + 1;
+    edit_line = edit_match + 1;
+  }
+  changes
+}
+
+/// Pushes the indexes of the matching lines in order, using the patience
+/// diff algorithm: the lines that are unique in both texts are matched
+/// first, and then the lines between them.
+fn match_lines(
+  orig: &[&str],
+  orig_offset: usize,
+  edit: &[&str],
+  edit_offset: usize,
+  matches: &mut Vec<(usize, usize)>,
+) {
+  let prefix_len = orig.iter().zip(edit).take_while(|(a, b)| a == b).count();
+  let suffix_len = orig[prefix_len..]
+    .iter()
+    .rev()
+    .zip(edit[prefix_len..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+  matches.extend((0..prefix_len).map(|i| (orig_offset + i, edit_offset + i)));
+
+  let orig_mid = &orig[prefix_len..orig.len() - suffix_len];
+  let edit_mid = &edit[prefix_len..edit.len() - suffix_len];
+  let orig_mid_offset = orig_offset + prefix_len;
+  let edit_mid_offset = edit_offset + prefix_len;
+  if !orig_mid.is_empty() && !edit_mid.is_empty() {
+    let anchors = unique_line_anchors(orig_mid, edit_mid);
+    if anchors.is_empty() {
+      match_lines_lcs(
+        orig_mid,
+        orig_mid_offset,
+        edit_mid,
+        edit_mid_offset,
+        matches,
+      );
+    } else {
+      let mut orig_start = 0;
+      let mut edit_start = 0;
+      for (orig_index, edit_index) in anchors {
+        match_lines(
+          &orig_mid[orig_start..orig_index],
+          orig_mid_offset + orig_start,
+          &edit_mid[edit_start..edit_index],
+          edit_mid_offset + edit_start,
+          matches,
+        );
+        matches
+          .push((orig_mid_offset + orig_index, edit_mid_offset + edit_index));
+        orig_start = orig_index + 1;
+        edit_start = edit_index + 1;
+      }
+      match_lines(
+        &orig_mid[orig_start..],
+        orig_mid_offset + orig_start,
+        &edit_mid[edit_start..],
+        edit_mid_offset + edit_start,
+        matches,
+      );
+    }
+  }
+
+  let orig_suffix_offset = orig_offset + orig.len() - suffix_len;
+  let edit_suffix_offset = edit_offset + edit.len() - suffix_len;
+  matches.extend(
+    (0..suffix_len).map(|i| (orig_suffix_offset + i, edit_suffix_offset + i)),
+  );
+}
+
+/// Gets the longest sequence of lines that are unique in both texts and in
+/// the same order in both.
+fn unique_line_anchors(orig: &[&str], edit: &[&str]) -> Vec<(usize, usize)> {
+  #[derive(Default)]
+  struct LineCounts {
+    orig: usize,
+    edit: usize,
+    orig_index: usize,
+  }
+
+  let mut counts = HashMap::<&str, LineCounts>::new();
+  for (i, line) in orig.iter().enumerate() {
+    let counts = counts.entry(*line).or_default();
+    counts.orig += 1;
+    counts.orig_index = i;
+  }
+  for line in edit {
+    if let Some(counts) = counts.get_mut(line) {
+      counts.edit += 1;
+    }
+  }
+  // ordered by the edit index
+  let unique = edit
+    .iter()
+    .enumerate()
+    .filter_map(|(edit_index, line)| {
+      let counts = &counts[line];
+      (counts.orig == 1 && counts.edit == 1)
+        .then_some((counts.orig_index, edit_index))
+    })
+    .collect::<Vec<_>>();
+  // longest increasing subsequence of the orig indexes with patience sorting
+  let mut pile_tops = Vec::<usize>::new();
+  let mut predecessors = vec![None; unique.len()];
+  for (i, (orig_index, _)) in unique.iter().enumerate() {
+    let pile = pile_tops.partition_point(|top| unique[*top].0 < *orig_index);
+    if pile > 0 {
+      predecessors[i] = Some(pile_tops[pile - 1]);
+    }
+    if pile == pile_tops.len() {
+      pile_tops.push(i);
+    } else {
+      pile_tops[pile] = i;
+    }
+  }
+  let mut anchors = Vec::new();
+  let mut current = pile_tops.last().copied();
+  while let Some(i) = current {
+    anchors.push(unique[i]);
+    current = predecessors[i];
+  }
+  anchors.reverse();
+  anchors
+}
+
+/// Matches the lines with the longest common subsequence. Large texts
+/// without matching unique lines are considered to be all changed instead.
+fn match_lines_lcs(
+  orig: &[&str],
+  orig_offset: usize,
+  edit: &[&str],
+  edit_offset: usize,
+  matches: &mut Vec<(usize, usize)>,
+) {
+  const MAX_TABLE_SIZE: usize = 1 << 20;
+  if (orig.len() + 1) * (edit.len() + 1) > MAX_TABLE_SIZE {
+    return;
+  }
+  // the length of the longest common subsequence of the lines after each
+  // index
+  let width = edit.len() + 1;
+  let mut table = vec![0u32; (orig.len() + 1) * width];
+  for i in (0..orig.len()).rev() {
+    for j in (0..edit.len()).rev() {
+      table[i * width + j] = if orig[i] == edit[j] {
+        table[(i + 1) * width + j + 1] + 1
+      } else {
+        table[(i + 1) * width + j].max(table[i * width + j + 1])
+      };
+    }
+  }
+  let mut i = 0;
+  let mut j = 0;
+  while i < orig.len() && j < edit.len() {
+    if orig[i] == edit[j] {
+      matches.push((orig_offset + i, edit_offset + j));
+      i += 1;
+      j += 1;
+      continue;
+    }
+    let skip_orig = table[(i + 1) * width + j];
+    let skip_edit = table[i * width + j + 1];
+    // when both are as good, keep the lines of both texts at similar
+    // positions, as the changed lines usually replace each other
+    if skip_orig > skip_edit
+      || (skip_orig == skip_edit && i * edit.len() <= j * orig.len())
+    {
+      i += 1;
+    } else {
+      j += 1;
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -219,6 +428,48 @@ mod tests {
     run_test("test\n", "test\r\n", " | Text differed by line endings.\n");
   }
 
+  #[test]
+  fn test_diff_lines() {
+    assert_eq!(diff_lines("a\nb\nc\n", "a\nb\nc\n"), vec![]);
+    assert_eq!(
+      diff_lines("a\nb\nc\nd\n", "a\nB\nc\nd\ne\n"),
+      vec![
+        LineChange {
+          orig: 1..2,
+          edit: 1..2,
+        },
+        LineChange {
+          orig: 4..4,
+          edit: 4..5,
+        },
+      ]
+    );
+    assert_eq!(
+      diff_lines("a\n\nb\n\nc\n", "A\n\nB\n\nC\n"),
+      vec![
+        LineChange {
+          orig: 0..1,
+          edit: 0..1,
+        },
+        LineChange {
+          orig: 2..3,
+          edit: 2..3,
+        },
+        LineChange {
+          orig: 4..5,
+          edit: 4..5,
+        },
+      ]
+    );
+    assert_eq!(
+      diff_lines("a\nb\nc", "a\nc\n"),
+      vec![LineChange {
+        orig: 1..3,
+        edit: 1..2,
+      }]
+    );
+  }
+
   fn run_test(diff_text1: &str, diff_text2: &str, expected_output: &str) {
     assert_eq!(
       test_util::strip_ansi_codes(&diff(diff_text1, diff_text2,)),
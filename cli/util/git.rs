@@ -0,0 +1,198 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+use crate::util::fs::canonicalize_path;
+
+/// The files of a git repository that changed against a ref, including the
+/// uncommitted and untracked files.
+#[derive(Debug, Default)]
+pub struct GitChangedFiles {
+  /// The changed lines of each file by its canonicalized path, or `None`
+  /// when the whole file is new.
+  files: HashMap<PathBuf, Option<Vec<Range<usize>>>>,
+}
+
+impl GitChangedFiles {
+  pub fn resolve(cwd: &Path, git_ref: &str) -> Result<Self, AnyError> {
+    if git_ref.starts_with('-') {
+      bail!("Invalid git ref: {}", git_ref);
+    }
+    let root = run_git(cwd, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root.trim_end_matches(['\n', '\r']));
+    let root = canonicalize_path(&root).unwrap_or(root);
+
+    let diff = run_git(
+      &root,
+      &[
+        "-c",
+        "core.quotePath=false",
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-renames",
+        "--unified=0",
+        "--diff-filter=d",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        git_ref,
+        "--",
+      ],
+    )?;
+    let mut files = parse_diff_changed_lines(&diff)
+      .into_iter()
+      .map(|(path, lines)| (root.join(path), Some(lines)))
+      .collect::<HashMap<_, _>>();
+
+    let untracked =
+      run_git(&root, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+    for path in untracked.split('\0').filter(|p| !p.is_empty()) {
+      files.insert(root.join(path), None);
+    }
+    Ok(Self { files })
+  }
+
+  pub fn contains(&self, file_path: &Path) -> bool {
+    self.files.contains_key(&canonical_path(file_path))
+  }
+
+  /// The 0-indexed ranges of the lines that changed in a file, or `None`
+  /// when the whole file changed.
+  pub fn changed_lines(&self, file_path: &Path) -> Option<&[Range<usize>]> {
+    self
+      .files
+      .get(&canonical_path(file_path))
+      .and_then(|lines| lines.as_deref())
+  }
+}
+
+fn canonical_path(file_path: &Path) -> PathBuf {
+  canonicalize_path(file_path).unwrap_or_else(|_| file_path.to_path_buf())
+}
+
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let bin_name = if cfg!(windows) { "git.exe" } else { "git" };
+  let output = Command::new(bin_name)
+    .current_dir(cwd)
+    .args(args)
+    .output()
+    .context("Failed to run git. Is it installed?")?;
+  if !output.status.success() {
+    bail!(
+      "git {} failed: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Gets the changed lines of the new version of each file from the output of
+/// `git diff --unified=0`.
+fn parse_diff_changed_lines(diff: &str) -> Vec<(String, Vec<Range<usize>>)> {
+  let mut files = Vec::<(String, Vec<Range<usize>>)>::new();
+  // whether in the header of a file, before its hunks
+  let mut in_header = false;
+  for line in diff.lines() {
+    if line.starts_with("diff --git ") {
+      in_header = true;
+    } else if let Some(path) = line.strip_prefix("+++ ").filter(|_| in_header) {
+      let path = unquote_path(path);
+      if let Some(path) = path.strip_prefix("b/") {
+        files.push((path.to_string(), Vec::new()));
+      }
+    } else if let Some(hunk) = line.strip_prefix("@@ ") {
+      in_header = false;
+      // @@ -<start>[,<count>] +<start>[,<count>] @@
+      let Some((_, new_range)) = hunk.split_once(" +") else {
+        continue;
+      };
+      let new_range = new_range.split(' ').next().unwrap_or_default();
+      let (start, count) = match new_range.split_once(',') {
+        Some((start, count)) => (start, count.parse().unwrap_or(0)),
+        None => (new_range, 1),
+      };
+      let Ok(start) = start.parse::<usize>() else {
+        continue;
+      };
+      // hunks that only delete lines don't change any line
+      if count == 0 {
+        continue;
+      }
+      if let Some((_, lines)) = files.last_mut() {
+        lines.push(start - 1..start - 1 + count);
+      }
+    }
+  }
+  files
+}
+
+/// Paths with unusual characters are quoted and escaped by git.
+fn unquote_path(path: &str) -> String {
+  let Some(path) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"'))
+  else {
+    return path.to_string();
+  };
+  let mut result = String::with_capacity(path.len());
+  let mut chars = path.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      result.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('t') => result.push('\t'),
+      Some('n') => result.push('\n'),
+      Some(c) => result.push(c),
+      None => {}
+    }
+  }
+  result
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_parse_diff_changed_lines() {
+    let diff = concat!(
+      "diff --git a/main.ts b/main.ts\n",
+      "index 1234567..89abcde 100644\n",
+      "--- a/main.ts\n",
+      "+++ b/main.ts\n",
+      "@@ -2 +2 @@ export function a() {\n",
+      "-  return 1;\n",
+      "+  return  2;\n",
+      "@@ -10,0 +11,3 @@ export function b() {\n",
+      "+const c=1;\n",
+      "+const d=2;\n",
+      "+const e=3;\n",
+      "@@ -20,2 +23,0 @@\n",
+      "-removed();\n",
+      "-removed();\n",
+      "diff --git a/new.ts b/new.ts\n",
+      "new file mode 100644\n",
+      "--- /dev/null\n",
+      "+++ \"b/dir/with \\\"quote\\\".ts\"\n",
+      "@@ -0,0 +1,2 @@\n",
+      "+a;\n",
+      "+b;\n",
+    );
+    assert_eq!(
+      parse_diff_changed_lines(diff),
+      vec![
+        ("main.ts".to_string(), vec![1..2, 10..13]),
+        ("dir/with \"quote\".ts".to_string(), vec![0..2]),
+      ]
+    );
+  }
+}
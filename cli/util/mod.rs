@@ -9,11 +9,13 @@ pub mod display;
 pub mod draw_thread;
 pub mod file_watcher;
 pub mod fs;
+pub mod git;
 pub mod gitignore;
 pub mod logger;
 pub mod path;
 pub mod progress_bar;
 pub mod result;
+pub mod sarif;
 pub mod sync;
 pub mod text_encoding;
 pub mod unix;
@@ -0,0 +1,176 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! A subset of the SARIF 2.1.0 format, which is read by code scanning tools.
+//! See https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use serde::Serialize;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifLog {
+  #[serde(rename = "$schema")]
+  pub schema: &'static str,
+  pub version: &'static str,
+  pub runs: Vec<SarifRun>,
+}
+
+impl SarifLog {
+  pub fn new(runs: Vec<SarifRun>) -> Self {
+    Self {
+      schema: SARIF_SCHEMA,
+      version: SARIF_VERSION,
+      runs,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+  pub tool: SarifTool,
+  pub results: Vec<SarifResult>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub invocations: Vec<SarifInvocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifTool {
+  pub driver: SarifToolComponent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifToolComponent {
+  pub name: String,
+  pub information_uri: String,
+  pub version: String,
+  pub rules: Vec<SarifReportingDescriptor>,
+}
+
+/// The metadata of a rule that results refer to by its id.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifReportingDescriptor {
+  pub id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub short_description: Option<SarifMessage>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub help_uri: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub properties: Option<SarifPropertyBag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifPropertyBag {
+  pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SarifLevel {
+  Error,
+  Warning,
+  Note,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+  pub rule_id: String,
+  pub level: SarifLevel,
+  pub message: SarifMessage,
+  pub locations: Vec<SarifLocation>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub related_locations: Vec<SarifLocation>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub fixes: Vec<SarifFix>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifMessage {
+  pub text: String,
+}
+
+impl SarifMessage {
+  pub fn new(text: impl Into<String>) -> Self {
+    Self { text: text.into() }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+  pub physical_location: SarifPhysicalLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub message: Option<SarifMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+  pub artifact_location: SarifArtifactLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifArtifactLocation {
+  pub uri: String,
+}
+
+/// A range of a file, with 1-indexed lines and columns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+  pub start_line: usize,
+  pub start_column: usize,
+  pub end_line: usize,
+  pub end_column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifFix {
+  pub description: SarifMessage,
+  pub artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactChange {
+  pub artifact_location: SarifArtifactLocation,
+  pub replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifReplacement {
+  pub deleted_region: SarifRegion,
+  pub inserted_content: SarifArtifactContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SarifArtifactContent {
+  pub text: String,
+}
+
+/// Whether the tool ran to completion, along with the errors it hit, such
+/// as files that failed to parse.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifInvocation {
+  pub execution_successful: bool,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tool_execution_notifications: Vec<SarifNotification>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifNotification {
+  pub level: SarifLevel,
+  pub message: SarifMessage,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub locations: Vec<SarifLocation>,
+}
@@ -26,6 +26,7 @@ use deno_core::futures::StreamExt;
 use deno_core::op2;
 use deno_core::unsync::spawn;
 use deno_core::url::Url;
+use deno_core::v8;
 use deno_core::AsyncRefCell;
 use deno_core::AsyncResult;
 use deno_core::BufView;
@@ -39,6 +40,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::AuditLocation;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::Proxy;
 use deno_tls::RootCertStoreProvider;
@@ -305,6 +307,7 @@ impl FetchPermissions for deno_permissions::PermissionsContainer {
 #[serde]
 #[allow(clippy::too_many_arguments)]
 pub fn op_fetch<FP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] method: ByteString,
   #[string] url: String,
@@ -317,6 +320,7 @@ pub fn op_fetch<FP>(
 where
   FP: FetchPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let (client, allow_host) = if let Some(rid) = client_rid {
     let r = state.resource_table.get::<HttpClientResource>(rid)?;
     (r.client.clone(), r.allow_host)
@@ -841,6 +845,7 @@ fn default_true() -> bool {
 #[op2]
 #[smi]
 pub fn op_fetch_custom_client<FP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] args: CreateHttpClientArgs,
   #[cppgc] tls_keys: &TlsKeysHolder,
@@ -848,6 +853,7 @@ pub fn op_fetch_custom_client<FP>(
 where
   FP: FetchPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   if let Some(proxy) = args.proxy.clone() {
     let permissions = state.borrow_mut::<FP>();
     let url = Url::parse(&proxy.url)?;
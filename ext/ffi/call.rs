@@ -18,6 +18,7 @@ use deno_core::unsync::spawn_blocking;
 use deno_core::v8;
 use deno_core::OpState;
 use deno_core::ResourceId;
+use deno_permissions::AuditLocation;
 use libffi::middle::Arg;
 use serde::Serialize;
 use std::cell::RefCell;
@@ -287,6 +288,7 @@ where
 {
   check_unstable(&state.borrow(), "Deno.UnsafeFnPointer#call");
   {
+    let _location = AuditLocation::capture(scope).enter();
     let mut state = state.borrow_mut();
     let permissions = state.borrow_mut::<FP>();
     permissions.check_partial(None)?;
@@ -383,6 +385,7 @@ where
 {
   check_unstable(&state.borrow(), "Deno.UnsafeFnPointer#call");
   {
+    let _location = AuditLocation::capture(scope).enter();
     let mut state = state.borrow_mut();
     let permissions = state.borrow_mut::<FP>();
     permissions.check_partial(None)?;
@@ -14,6 +14,7 @@ use deno_core::OpState;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::V8CrossThreadTaskSpawner;
+use deno_permissions::AuditLocation;
 use libffi::middle::Cif;
 use serde::Deserialize;
 use std::borrow::Cow;
@@ -558,6 +559,7 @@ where
   FP: FfiPermissions + 'static,
 {
   check_unstable(state, "Deno.UnsafeCallback");
+  let _location = AuditLocation::capture(scope).enter();
   let permissions = state.borrow_mut::<FP>();
   permissions.check_partial(None)?;
 
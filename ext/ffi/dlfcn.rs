@@ -12,6 +12,7 @@ use deno_core::op2;
 use deno_core::v8;
 use deno_core::OpState;
 use deno_core::Resource;
+use deno_permissions::AuditLocation;
 use dlopen2::raw::Library;
 use serde::Deserialize;
 use serde_value::ValueDeserializer;
@@ -123,6 +124,7 @@ where
   let path = args.path;
 
   check_unstable(state, "Deno.dlopen");
+  let _location = AuditLocation::capture(scope).enter();
   let permissions = state.borrow_mut::<FP>();
   permissions.check_partial(Some(&PathBuf::from(&path)))?;
 
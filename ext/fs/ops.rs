@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::cell::RefCell;
+use std::future::Future;
 use std::io;
 use std::io::SeekFrom;
 use std::path::Path;
@@ -12,6 +13,7 @@ use deno_core::error::custom_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
 use deno_core::JsBuffer;
@@ -21,6 +23,7 @@ use deno_core::ToJsBuffer;
 use deno_io::fs::FileResource;
 use deno_io::fs::FsError;
 use deno_io::fs::FsStat;
+use deno_permissions::AuditLocation;
 use rand::rngs::ThreadRng;
 use rand::thread_rng;
 use rand::Rng;
@@ -45,9 +48,11 @@ fn sync_permission_check<'a, P: FsPermissions + 'static>(
 
 fn async_permission_check<P: FsPermissions + 'static>(
   state: Rc<RefCell<OpState>>,
+  location: AuditLocation,
   api_name: &'static str,
 ) -> impl AccessCheckFn {
   move |resolved, path, options| {
+    let _location = location.enter();
     let mut state = state.borrow_mut();
     let permissions = state.borrow_mut::<P>();
     permissions.check(resolved, options, path, api_name)
@@ -78,10 +83,14 @@ fn map_permission_error(
 
 #[op2]
 #[string]
-pub fn op_fs_cwd<P>(state: &mut OpState) -> Result<String, AnyError>
+pub fn op_fs_cwd<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let fs = state.borrow::<FileSystemRc>();
   let path = fs.cwd()?;
   state
@@ -91,14 +100,16 @@ where
   Ok(path_str)
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_chdir<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] directory: &str,
 ) -> Result<(), AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let d = PathBuf::from(&directory);
   state.borrow_mut::<P>().check_read(&d, "Deno.chdir()")?;
   state
@@ -121,6 +132,7 @@ where
 #[op2]
 #[smi]
 pub fn op_fs_open_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   #[serde] options: Option<OpenOptions>,
@@ -128,6 +140,7 @@ pub fn op_fs_open_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let options = options.unwrap_or_else(OpenOptions::read);
@@ -147,34 +160,39 @@ where
 
 #[op2(async)]
 #[smi]
-pub async fn op_fs_open_async<P>(
+pub fn op_fs_open_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[serde] options: Option<OpenOptions>,
-) -> Result<ResourceId, AnyError>
+) -> impl Future<Output = Result<ResourceId, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-
-  let options = options.unwrap_or_else(OpenOptions::read);
-  let mut access_check =
-    async_permission_check::<P>(state.clone(), "Deno.open()");
-  let fs = state.borrow().borrow::<FileSystemRc>().clone();
-  let file = fs
-    .open_async(path.clone(), options, Some(&mut access_check))
-    .await
-    .map_err(|error| map_permission_error("open", error, &path))?;
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let options = options.unwrap_or_else(OpenOptions::read);
+    let mut access_check =
+      async_permission_check::<P>(state.clone(), location, "Deno.open()");
+    let fs = state.borrow().borrow::<FileSystemRc>().clone();
+    let file = fs
+      .open_async(path.clone(), options, Some(&mut access_check))
+      .await
+      .map_err(|error| map_permission_error("open", error, &path))?;
 
-  let rid = state
-    .borrow_mut()
-    .resource_table
-    .add(FileResource::new(file, "fsFile".to_string()));
-  Ok(rid)
+    let rid = state
+      .borrow_mut()
+      .resource_table
+      .add(FileResource::new(file, "fsFile".to_string()));
+    Ok(rid)
+  }
 }
 
 #[op2]
 pub fn op_fs_mkdir_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   recursive: bool,
@@ -183,6 +201,7 @@ pub fn op_fs_mkdir_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let mode = mode.unwrap_or(0o777) & 0o777;
@@ -199,34 +218,40 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_mkdir_async<P>(
+pub fn op_fs_mkdir_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   recursive: bool,
   mode: Option<u32>,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
 
-  let mode = mode.unwrap_or(0o777) & 0o777;
+    let mode = mode.unwrap_or(0o777) & 0o777;
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state.borrow_mut::<P>().check_write(&path, "Deno.mkdir()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state.borrow_mut::<P>().check_write(&path, "Deno.mkdir()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.mkdir_async(path.clone(), recursive, mode)
-    .await
-    .context_path("mkdir", &path)?;
+    fs.mkdir_async(path.clone(), recursive, mode)
+      .await
+      .context_path("mkdir", &path)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_chmod_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   mode: u32,
@@ -234,6 +259,7 @@ pub fn op_fs_chmod_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
   state
     .borrow_mut::<P>()
@@ -244,28 +270,34 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_chmod_async<P>(
+pub fn op_fs_chmod_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   mode: u32,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-  let fs = {
-    let mut state = state.borrow_mut();
-    state.borrow_mut::<P>().check_write(&path, "Deno.chmod()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
-  fs.chmod_async(path.clone(), mode)
-    .await
-    .context_path("chmod", &path)?;
-  Ok(())
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state.borrow_mut::<P>().check_write(&path, "Deno.chmod()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
+    fs.chmod_async(path.clone(), mode)
+      .await
+      .context_path("chmod", &path)?;
+    Ok(())
+  }
 }
 
 #[op2]
 pub fn op_fs_chown_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   uid: Option<u32>,
@@ -274,6 +306,7 @@ pub fn op_fs_chown_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
   state
     .borrow_mut::<P>()
@@ -285,29 +318,35 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_chown_async<P>(
+pub fn op_fs_chown_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   uid: Option<u32>,
   gid: Option<u32>,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-  let fs = {
-    let mut state = state.borrow_mut();
-    state.borrow_mut::<P>().check_write(&path, "Deno.chown()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
-  fs.chown_async(path.clone(), uid, gid)
-    .await
-    .context_path("chown", &path)?;
-  Ok(())
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state.borrow_mut::<P>().check_write(&path, "Deno.chown()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
+    fs.chown_async(path.clone(), uid, gid)
+      .await
+      .context_path("chown", &path)?;
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_remove_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: &str,
   recursive: bool,
@@ -315,6 +354,7 @@ pub fn op_fs_remove_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   state
@@ -329,40 +369,46 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_remove_async<P>(
+pub fn op_fs_remove_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   recursive: bool,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    if recursive {
-      state
-        .borrow_mut::<P>()
-        .check_write(&path, "Deno.remove()")?;
-    } else {
-      state
-        .borrow_mut::<P>()
-        .check_write_partial(&path, "Deno.remove()")?;
-    }
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      if recursive {
+        state
+          .borrow_mut::<P>()
+          .check_write(&path, "Deno.remove()")?;
+      } else {
+        state
+          .borrow_mut::<P>()
+          .check_write_partial(&path, "Deno.remove()")?;
+      }
 
-    state.borrow::<FileSystemRc>().clone()
-  };
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.remove_async(path.clone(), recursive)
-    .await
-    .context_path("remove", &path)?;
+    fs.remove_async(path.clone(), recursive)
+      .await
+      .context_path("remove", &path)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_copy_file_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] from: &str,
   #[string] to: &str,
@@ -370,6 +416,7 @@ pub fn op_fs_copy_file_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let from = PathBuf::from(from);
   let to = PathBuf::from(to);
 
@@ -385,34 +432,40 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_copy_file_async<P>(
+pub fn op_fs_copy_file_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] from: String,
   #[string] to: String,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let from = PathBuf::from(from);
-  let to = PathBuf::from(to);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&from, "Deno.copyFile()")?;
-    permissions.check_write(&to, "Deno.copyFile()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let from = PathBuf::from(from);
+    let to = PathBuf::from(to);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&from, "Deno.copyFile()")?;
+      permissions.check_write(&to, "Deno.copyFile()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.copy_file_async(from.clone(), to.clone())
-    .await
-    .context_two_path("copy", &from, &to)?;
+    fs.copy_file_async(from.clone(), to.clone())
+      .await
+      .context_two_path("copy", &from, &to)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_stat_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   #[buffer] stat_out_buf: &mut [u32],
@@ -420,6 +473,7 @@ pub fn op_fs_stat_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
   state
     .borrow_mut::<P>()
@@ -433,29 +487,35 @@ where
 
 #[op2(async)]
 #[serde]
-pub async fn op_fs_stat_async<P>(
+pub fn op_fs_stat_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<SerializableStat, AnyError>
+) -> impl Future<Output = Result<SerializableStat, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&path, "Deno.stat()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
-  let stat = fs
-    .stat_async(path.clone())
-    .await
-    .context_path("stat", &path)?;
-  Ok(SerializableStat::from(stat))
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&path, "Deno.stat()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
+    let stat = fs
+      .stat_async(path.clone())
+      .await
+      .context_path("stat", &path)?;
+    Ok(SerializableStat::from(stat))
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_lstat_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   #[buffer] stat_out_buf: &mut [u32],
@@ -463,6 +523,7 @@ pub fn op_fs_lstat_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
   state
     .borrow_mut::<P>()
@@ -476,36 +537,43 @@ where
 
 #[op2(async)]
 #[serde]
-pub async fn op_fs_lstat_async<P>(
+pub fn op_fs_lstat_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<SerializableStat, AnyError>
+) -> impl Future<Output = Result<SerializableStat, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&path, "Deno.lstat()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
-  let stat = fs
-    .lstat_async(path.clone())
-    .await
-    .context_path("lstat", &path)?;
-  Ok(SerializableStat::from(stat))
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&path, "Deno.lstat()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
+    let stat = fs
+      .lstat_async(path.clone())
+      .await
+      .context_path("lstat", &path)?;
+    Ok(SerializableStat::from(stat))
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_fs_realpath_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let fs = state.borrow::<FileSystemRc>().clone();
@@ -524,43 +592,50 @@ where
 
 #[op2(async)]
 #[string]
-pub async fn op_fs_realpath_async<P>(
+pub fn op_fs_realpath_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<String, AnyError>
+) -> impl Future<Output = Result<String, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-
-  let fs;
-  {
-    let mut state = state.borrow_mut();
-    fs = state.borrow::<FileSystemRc>().clone();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&path, "Deno.realPath()")?;
-    if path.is_relative() {
-      permissions.check_read_blind(&fs.cwd()?, "CWD", "Deno.realPath()")?;
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let fs;
+    {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      fs = state.borrow::<FileSystemRc>().clone();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&path, "Deno.realPath()")?;
+      if path.is_relative() {
+        permissions.check_read_blind(&fs.cwd()?, "CWD", "Deno.realPath()")?;
+      }
     }
-  }
-  let resolved_path = fs
-    .realpath_async(path.clone())
-    .await
-    .context_path("realpath", &path)?;
+    let resolved_path = fs
+      .realpath_async(path.clone())
+      .await
+      .context_path("realpath", &path)?;
 
-  let path_string = path_into_string(resolved_path.into_os_string())?;
-  Ok(path_string)
+    let path_string = path_into_string(resolved_path.into_os_string())?;
+    Ok(path_string)
+  }
 }
 
 #[op2]
 #[serde]
 pub fn op_fs_read_dir_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<Vec<FsDirEntry>, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   state
@@ -575,33 +650,39 @@ where
 
 #[op2(async)]
 #[serde]
-pub async fn op_fs_read_dir_async<P>(
+pub fn op_fs_read_dir_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<Vec<FsDirEntry>, AnyError>
+) -> impl Future<Output = Result<Vec<FsDirEntry>, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_read(&path, "Deno.readDir()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state
+        .borrow_mut::<P>()
+        .check_read(&path, "Deno.readDir()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  let entries = fs
-    .read_dir_async(path.clone())
-    .await
-    .context_path("readdir", &path)?;
+    let entries = fs
+      .read_dir_async(path.clone())
+      .await
+      .context_path("readdir", &path)?;
 
-  Ok(entries)
+    Ok(entries)
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_rename_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] oldpath: String,
   #[string] newpath: String,
@@ -609,6 +690,7 @@ pub fn op_fs_rename_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let oldpath = PathBuf::from(oldpath);
   let newpath = PathBuf::from(newpath);
 
@@ -625,35 +707,41 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_rename_async<P>(
+pub fn op_fs_rename_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] oldpath: String,
   #[string] newpath: String,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let oldpath = PathBuf::from(oldpath);
-  let newpath = PathBuf::from(newpath);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&oldpath, "Deno.rename()")?;
-    permissions.check_write(&oldpath, "Deno.rename()")?;
-    permissions.check_write(&newpath, "Deno.rename()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let oldpath = PathBuf::from(oldpath);
+    let newpath = PathBuf::from(newpath);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&oldpath, "Deno.rename()")?;
+      permissions.check_write(&oldpath, "Deno.rename()")?;
+      permissions.check_write(&newpath, "Deno.rename()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.rename_async(oldpath.clone(), newpath.clone())
-    .await
-    .context_two_path("rename", &oldpath, &newpath)?;
+    fs.rename_async(oldpath.clone(), newpath.clone())
+      .await
+      .context_two_path("rename", &oldpath, &newpath)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_link_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] oldpath: &str,
   #[string] newpath: &str,
@@ -661,6 +749,7 @@ pub fn op_fs_link_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let oldpath = PathBuf::from(oldpath);
   let newpath = PathBuf::from(newpath);
 
@@ -678,36 +767,42 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_link_async<P>(
+pub fn op_fs_link_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] oldpath: String,
   #[string] newpath: String,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let oldpath = PathBuf::from(&oldpath);
-  let newpath = PathBuf::from(&newpath);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_read(&oldpath, "Deno.link()")?;
-    permissions.check_write(&oldpath, "Deno.link()")?;
-    permissions.check_read(&newpath, "Deno.link()")?;
-    permissions.check_write(&newpath, "Deno.link()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let oldpath = PathBuf::from(&oldpath);
+    let newpath = PathBuf::from(&newpath);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_read(&oldpath, "Deno.link()")?;
+      permissions.check_write(&oldpath, "Deno.link()")?;
+      permissions.check_read(&newpath, "Deno.link()")?;
+      permissions.check_write(&newpath, "Deno.link()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.link_async(oldpath.clone(), newpath.clone())
-    .await
-    .context_two_path("link", &oldpath, &newpath)?;
+    fs.link_async(oldpath.clone(), newpath.clone())
+      .await
+      .context_two_path("link", &oldpath, &newpath)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
 #[op2]
 pub fn op_fs_symlink_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] oldpath: &str,
   #[string] newpath: &str,
@@ -716,6 +811,7 @@ pub fn op_fs_symlink_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let oldpath = PathBuf::from(oldpath);
   let newpath = PathBuf::from(newpath);
 
@@ -731,42 +827,49 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_symlink_async<P>(
+pub fn op_fs_symlink_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] oldpath: String,
   #[string] newpath: String,
   #[serde] file_type: Option<FsFileType>,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let oldpath = PathBuf::from(&oldpath);
-  let newpath = PathBuf::from(&newpath);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<P>();
-    permissions.check_write_all("Deno.symlink()")?;
-    permissions.check_read_all("Deno.symlink()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let oldpath = PathBuf::from(&oldpath);
+    let newpath = PathBuf::from(&newpath);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      let permissions = state.borrow_mut::<P>();
+      permissions.check_write_all("Deno.symlink()")?;
+      permissions.check_read_all("Deno.symlink()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.symlink_async(oldpath.clone(), newpath.clone(), file_type)
-    .await
-    .context_two_path("symlink", &oldpath, &newpath)?;
+    fs.symlink_async(oldpath.clone(), newpath.clone(), file_type)
+      .await
+      .context_two_path("symlink", &oldpath, &newpath)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_fs_read_link_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   state
@@ -782,33 +885,39 @@ where
 
 #[op2(async)]
 #[string]
-pub async fn op_fs_read_link_async<P>(
+pub fn op_fs_read_link_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<String, AnyError>
+) -> impl Future<Output = Result<String, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_read(&path, "Deno.readLink()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state
+        .borrow_mut::<P>()
+        .check_read(&path, "Deno.readLink()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  let target = fs
-    .read_link_async(path.clone())
-    .await
-    .context_path("readlink", &path)?;
-  let target_string = path_into_string(target.into_os_string())?;
-  Ok(target_string)
+    let target = fs
+      .read_link_async(path.clone())
+      .await
+      .context_path("readlink", &path)?;
+    let target_string = path_into_string(target.into_os_string())?;
+    Ok(target_string)
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_truncate_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: &str,
   #[number] len: u64,
@@ -816,6 +925,7 @@ pub fn op_fs_truncate_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   state
@@ -830,33 +940,39 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_truncate_async<P>(
+pub fn op_fs_truncate_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[number] len: u64,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_write(&path, "Deno.truncate()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state
+        .borrow_mut::<P>()
+        .check_write(&path, "Deno.truncate()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.truncate_async(path.clone(), len)
-    .await
-    .context_path("truncate", &path)?;
+    fs.truncate_async(path.clone(), len)
+      .await
+      .context_path("truncate", &path)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_fs_utime_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: &str,
   #[number] atime_secs: i64,
@@ -867,6 +983,7 @@ pub fn op_fs_utime_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   state.borrow_mut::<P>().check_write(&path, "Deno.utime()")?;
@@ -879,41 +996,47 @@ where
 }
 
 #[op2(async)]
-pub async fn op_fs_utime_async<P>(
+pub fn op_fs_utime_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[number] atime_secs: i64,
   #[smi] atime_nanos: u32,
   #[number] mtime_secs: i64,
   #[smi] mtime_nanos: u32,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-
-  let fs = {
-    let mut state = state.borrow_mut();
-    state.borrow_mut::<P>().check_write(&path, "Deno.utime()")?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state.borrow_mut::<P>().check_write(&path, "Deno.utime()")?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.utime_async(
-    path.clone(),
-    atime_secs,
-    atime_nanos,
-    mtime_secs,
-    mtime_nanos,
-  )
-  .await
-  .context_path("utime", &path)?;
+    fs.utime_async(
+      path.clone(),
+      atime_secs,
+      atime_nanos,
+      mtime_secs,
+      mtime_nanos,
+    )
+    .await
+    .context_path("utime", &path)?;
 
-  Ok(())
+    Ok(())
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_fs_make_temp_dir_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
@@ -922,6 +1045,7 @@ pub fn op_fs_make_temp_dir_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let (dir, fs) =
     make_temp_check_sync::<P>(state, dir, "Deno.makeTempDirSync()")?;
 
@@ -948,41 +1072,50 @@ where
 
 #[op2(async)]
 #[string]
-pub async fn op_fs_make_temp_dir_async<P>(
+pub fn op_fs_make_temp_dir_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
-) -> Result<String, AnyError>
+) -> impl Future<Output = Result<String, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let (dir, fs) = make_temp_check_async::<P>(state, dir, "Deno.makeTempDir()")?;
-
-  let mut rng = thread_rng();
+  let location = AuditLocation::capture(scope);
+  async move {
+    let (dir, fs) = {
+      let _location = location.enter();
+      make_temp_check_async::<P>(state, dir, "Deno.makeTempDir()")?
+    };
 
-  const MAX_TRIES: u32 = 10;
-  for _ in 0..MAX_TRIES {
-    let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
-    match fs.clone().mkdir_async(path.clone(), false, 0o700).await {
-      Ok(_) => return path_into_string(path.into_os_string()),
-      Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
-        continue;
+    let mut rng = thread_rng();
+
+    const MAX_TRIES: u32 = 10;
+    for _ in 0..MAX_TRIES {
+      let path =
+        tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
+      match fs.clone().mkdir_async(path.clone(), false, 0o700).await {
+        Ok(_) => return path_into_string(path.into_os_string()),
+        Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
+          continue;
+        }
+        Err(e) => return Err(e).context("tmpdir"),
       }
-      Err(e) => return Err(e).context("tmpdir"),
     }
-  }
 
-  Err(FsError::Io(io::Error::new(
-    io::ErrorKind::AlreadyExists,
-    "too many temp dirs exist",
-  )))
-  .context("tmpdir")
+    Err(FsError::Io(io::Error::new(
+      io::ErrorKind::AlreadyExists,
+      "too many temp dirs exist",
+    )))
+    .context("tmpdir")
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_fs_make_temp_file_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
@@ -991,6 +1124,7 @@ pub fn op_fs_make_temp_file_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let (dir, fs) =
     make_temp_check_sync::<P>(state, dir, "Deno.makeTempFileSync()")?;
 
@@ -1023,43 +1157,50 @@ where
 
 #[op2(async)]
 #[string]
-pub async fn op_fs_make_temp_file_async<P>(
+pub fn op_fs_make_temp_file_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
-) -> Result<String, AnyError>
+) -> impl Future<Output = Result<String, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let (dir, fs) =
-    make_temp_check_async::<P>(state, dir, "Deno.makeTempFile()")?;
-
-  let open_opts = OpenOptions {
-    write: true,
-    create_new: true,
-    mode: Some(0o600),
-    ..Default::default()
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let (dir, fs) = {
+      let _location = location.enter();
+      make_temp_check_async::<P>(state, dir, "Deno.makeTempFile()")?
+    };
 
-  let mut rng = thread_rng();
+    let open_opts = OpenOptions {
+      write: true,
+      create_new: true,
+      mode: Some(0o600),
+      ..Default::default()
+    };
 
-  const MAX_TRIES: u32 = 10;
-  for _ in 0..MAX_TRIES {
-    let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
-    match fs.clone().open_async(path.clone(), open_opts, None).await {
-      Ok(_) => return path_into_string(path.into_os_string()),
-      Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
-        continue;
+    let mut rng = thread_rng();
+
+    const MAX_TRIES: u32 = 10;
+    for _ in 0..MAX_TRIES {
+      let path =
+        tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
+      match fs.clone().open_async(path.clone(), open_opts, None).await {
+        Ok(_) => return path_into_string(path.into_os_string()),
+        Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
+          continue;
+        }
+        Err(e) => return Err(e).context("tmpfile"),
       }
-      Err(e) => return Err(e).context("tmpfile"),
     }
+    Err(FsError::Io(io::Error::new(
+      io::ErrorKind::AlreadyExists,
+      "too many temp files exist",
+    )))
+    .context("tmpfile")
   }
-  Err(FsError::Io(io::Error::new(
-    io::ErrorKind::AlreadyExists,
-    "too many temp files exist",
-  )))
-  .context("tmpfile")
 }
 
 fn make_temp_check_sync<P>(
@@ -1176,7 +1317,9 @@ fn tmp_name(
 }
 
 #[op2]
+#[allow(clippy::too_many_arguments)]
 pub fn op_fs_write_file_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   mode: Option<u32>,
@@ -1188,6 +1331,7 @@ pub fn op_fs_write_file_sync<P>(
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let options = OpenOptions::write(create, append, create_new, mode);
@@ -1203,7 +1347,8 @@ where
 
 #[op2(async)]
 #[allow(clippy::too_many_arguments)]
-pub async fn op_fs_write_file_async<P>(
+pub fn op_fs_write_file_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[smi] mode: Option<u32>,
@@ -1212,58 +1357,64 @@ pub async fn op_fs_write_file_async<P>(
   create_new: bool,
   #[buffer] data: JsBuffer,
   #[smi] cancel_rid: Option<ResourceId>,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
-
-  let options = OpenOptions::write(create, append, create_new, mode);
-
-  let mut access_check =
-    async_permission_check::<P>(state.clone(), "Deno.writeFile()");
-  let (fs, cancel_handle) = {
-    let state = state.borrow_mut();
-    let cancel_handle = cancel_rid
-      .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
-    (state.borrow::<FileSystemRc>().clone(), cancel_handle)
-  };
-
-  let fut = fs.write_file_async(
-    path.clone(),
-    options,
-    Some(&mut access_check),
-    data.to_vec(),
-  );
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let options = OpenOptions::write(create, append, create_new, mode);
+
+    let mut access_check =
+      async_permission_check::<P>(state.clone(), location, "Deno.writeFile()");
+    let (fs, cancel_handle) = {
+      let state = state.borrow_mut();
+      let cancel_handle = cancel_rid
+        .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
+      (state.borrow::<FileSystemRc>().clone(), cancel_handle)
+    };
 
-  if let Some(cancel_handle) = cancel_handle {
-    let res = fut.or_cancel(cancel_handle).await;
+    let fut = fs.write_file_async(
+      path.clone(),
+      options,
+      Some(&mut access_check),
+      data.to_vec(),
+    );
+
+    if let Some(cancel_handle) = cancel_handle {
+      let res = fut.or_cancel(cancel_handle).await;
+
+      if let Some(cancel_rid) = cancel_rid {
+        if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid)
+        {
+          res.close();
+        }
+      };
 
-    if let Some(cancel_rid) = cancel_rid {
-      if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
-        res.close();
-      }
-    };
+      res?.map_err(|error| map_permission_error("writefile", error, &path))?;
+    } else {
+      fut
+        .await
+        .map_err(|error| map_permission_error("writefile", error, &path))?;
+    }
 
-    res?.map_err(|error| map_permission_error("writefile", error, &path))?;
-  } else {
-    fut
-      .await
-      .map_err(|error| map_permission_error("writefile", error, &path))?;
+    Ok(())
   }
-
-  Ok(())
 }
 
 #[op2]
 #[serde]
 pub fn op_fs_read_file_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<ToJsBuffer, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let fs = state.borrow::<FileSystemRc>().clone();
@@ -1278,55 +1429,62 @@ where
 
 #[op2(async)]
 #[serde]
-pub async fn op_fs_read_file_async<P>(
+pub fn op_fs_read_file_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[smi] cancel_rid: Option<ResourceId>,
-) -> Result<ToJsBuffer, AnyError>
+) -> impl Future<Output = Result<ToJsBuffer, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let mut access_check =
+      async_permission_check::<P>(state.clone(), location, "Deno.readFile()");
+    let (fs, cancel_handle) = {
+      let state = state.borrow();
+      let cancel_handle = cancel_rid
+        .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
+      (state.borrow::<FileSystemRc>().clone(), cancel_handle)
+    };
 
-  let mut access_check =
-    async_permission_check::<P>(state.clone(), "Deno.readFile()");
-  let (fs, cancel_handle) = {
-    let state = state.borrow();
-    let cancel_handle = cancel_rid
-      .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
-    (state.borrow::<FileSystemRc>().clone(), cancel_handle)
-  };
+    let fut = fs.read_file_async(path.clone(), Some(&mut access_check));
 
-  let fut = fs.read_file_async(path.clone(), Some(&mut access_check));
+    let buf = if let Some(cancel_handle) = cancel_handle {
+      let res = fut.or_cancel(cancel_handle).await;
 
-  let buf = if let Some(cancel_handle) = cancel_handle {
-    let res = fut.or_cancel(cancel_handle).await;
+      if let Some(cancel_rid) = cancel_rid {
+        if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid)
+        {
+          res.close();
+        }
+      };
 
-    if let Some(cancel_rid) = cancel_rid {
-      if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
-        res.close();
-      }
+      res?.map_err(|error| map_permission_error("readfile", error, &path))?
+    } else {
+      fut
+        .await
+        .map_err(|error| map_permission_error("readfile", error, &path))?
     };
 
-    res?.map_err(|error| map_permission_error("readfile", error, &path))?
-  } else {
-    fut
-      .await
-      .map_err(|error| map_permission_error("readfile", error, &path))?
-  };
-
-  Ok(buf.into())
+    Ok(buf.into())
+  }
 }
 
 #[op2]
 #[string]
 pub fn op_fs_read_file_text_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
 
   let fs = state.borrow::<FileSystemRc>().clone();
@@ -1341,45 +1499,50 @@ where
 
 #[op2(async)]
 #[string]
-pub async fn op_fs_read_file_text_async<P>(
+pub fn op_fs_read_file_text_async<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[smi] cancel_rid: Option<ResourceId>,
-) -> Result<String, AnyError>
+) -> impl Future<Output = Result<String, AnyError>>
 where
   P: FsPermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+
+    let mut access_check =
+      async_permission_check::<P>(state.clone(), location, "Deno.readFile()");
+    let (fs, cancel_handle) = {
+      let state = state.borrow_mut();
+      let cancel_handle = cancel_rid
+        .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
+      (state.borrow::<FileSystemRc>().clone(), cancel_handle)
+    };
 
-  let mut access_check =
-    async_permission_check::<P>(state.clone(), "Deno.readFile()");
-  let (fs, cancel_handle) = {
-    let state = state.borrow_mut();
-    let cancel_handle = cancel_rid
-      .and_then(|rid| state.resource_table.get::<CancelHandle>(rid).ok());
-    (state.borrow::<FileSystemRc>().clone(), cancel_handle)
-  };
+    let fut =
+      fs.read_text_file_lossy_async(path.clone(), Some(&mut access_check));
 
-  let fut =
-    fs.read_text_file_lossy_async(path.clone(), Some(&mut access_check));
+    let str = if let Some(cancel_handle) = cancel_handle {
+      let res = fut.or_cancel(cancel_handle).await;
 
-  let str = if let Some(cancel_handle) = cancel_handle {
-    let res = fut.or_cancel(cancel_handle).await;
+      if let Some(cancel_rid) = cancel_rid {
+        if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid)
+        {
+          res.close();
+        }
+      };
 
-    if let Some(cancel_rid) = cancel_rid {
-      if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
-        res.close();
-      }
+      res?.map_err(|error| map_permission_error("readfile", error, &path))?
+    } else {
+      fut
+        .await
+        .map_err(|error| map_permission_error("readfile", error, &path))?
     };
 
-    res?.map_err(|error| map_permission_error("readfile", error, &path))?
-  } else {
-    fut
-      .await
-      .map_err(|error| map_permission_error("readfile", error, &path))?
-  };
-
-  Ok(str)
+    Ok(str)
+  }
 }
 
 fn to_seek_from(offset: i64, whence: i32) -> Result<SeekFrom, AnyError> {
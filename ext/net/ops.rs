@@ -11,6 +11,7 @@ use deno_core::error::custom_error;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::CancelFuture;
 
 use deno_core::AsyncRefCell;
@@ -22,6 +23,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::AuditLocation;
 use serde::Deserialize;
 use serde::Serialize;
 use socket2::Domain;
@@ -30,6 +32,7 @@ use socket2::Socket;
 use socket2::Type;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::future::Future;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 use std::net::SocketAddr;
@@ -130,36 +133,41 @@ pub async fn op_net_recv_udp(
 
 #[op2(async)]
 #[number]
-pub async fn op_net_send_udp<NP>(
+pub fn op_net_send_udp<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
   #[serde] addr: IpAddr,
   #[buffer] zero_copy: JsBuffer,
-) -> Result<usize, AnyError>
+) -> impl Future<Output = Result<usize, AnyError>>
 where
   NP: NetPermissions + 'static,
 {
-  {
-    let mut s = state.borrow_mut();
-    s.borrow_mut::<NP>().check_net(
-      &(&addr.hostname, Some(addr.port)),
-      "Deno.DatagramConn.send()",
-    )?;
-  }
-  let addr = resolve_addr(&addr.hostname, addr.port)
-    .await?
-    .next()
-    .ok_or_else(|| generic_error("No resolved address found"))?;
+  let location = AuditLocation::capture(scope);
+  async move {
+    {
+      let _location = location.enter();
+      let mut s = state.borrow_mut();
+      s.borrow_mut::<NP>().check_net(
+        &(&addr.hostname, Some(addr.port)),
+        "Deno.DatagramConn.send()",
+      )?;
+    }
+    let addr = resolve_addr(&addr.hostname, addr.port)
+      .await?
+      .next()
+      .ok_or_else(|| generic_error("No resolved address found"))?;
 
-  let resource = state
-    .borrow_mut()
-    .resource_table
-    .get::<UdpSocketResource>(rid)
-    .map_err(|_| bad_resource("Socket has been closed"))?;
-  let socket = RcRef::map(&resource, |r| &r.socket).borrow().await;
-  let nwritten = socket.send_to(&zero_copy, &addr).await?;
+    let resource = state
+      .borrow_mut()
+      .resource_table
+      .get::<UdpSocketResource>(rid)
+      .map_err(|_| bad_resource("Socket has been closed"))?;
+    let socket = RcRef::map(&resource, |r| &r.socket).borrow().await;
+    let nwritten = socket.send_to(&zero_copy, &addr).await?;
 
-  Ok(nwritten)
+    Ok(nwritten)
+  }
 }
 
 #[op2(async)]
@@ -291,25 +299,29 @@ pub async fn op_net_set_multi_ttl_udp(
 
 #[op2(async)]
 #[serde]
-pub async fn op_net_connect_tcp<NP>(
+pub fn op_net_connect_tcp<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[serde] addr: IpAddr,
-) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
+) -> impl Future<Output = Result<(ResourceId, IpAddr, IpAddr), AnyError>>
 where
   NP: NetPermissions + 'static,
 {
-  op_net_connect_tcp_inner::<NP>(state, addr).await
+  let location = AuditLocation::capture(scope);
+  op_net_connect_tcp_inner::<NP>(state, addr, location)
 }
 
 #[inline]
 pub async fn op_net_connect_tcp_inner<NP>(
   state: Rc<RefCell<OpState>>,
   addr: IpAddr,
+  location: AuditLocation,
 ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
 {
   {
+    let _location = location.enter();
     let mut state_ = state.borrow_mut();
     state_
       .borrow_mut::<NP>()
@@ -350,6 +362,7 @@ impl Resource for UdpSocketResource {
 #[op2]
 #[serde]
 pub fn op_net_listen_tcp<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] addr: IpAddr,
   reuse_port: bool,
@@ -357,6 +370,7 @@ pub fn op_net_listen_tcp<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   if reuse_port {
     super::check_unstable(state, "Deno.listen({ reusePort: true })");
   }
@@ -445,6 +459,7 @@ where
 #[op2]
 #[serde]
 pub fn op_net_listen_udp<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] addr: IpAddr,
   reuse_address: bool,
@@ -453,6 +468,7 @@ pub fn op_net_listen_udp<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   super::check_unstable(state, "Deno.listenDatagram");
   net_listen_udp::<NP>(state, addr, reuse_address, loopback)
 }
@@ -460,6 +476,7 @@ where
 #[op2]
 #[serde]
 pub fn op_node_unstable_net_listen_udp<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] addr: IpAddr,
   reuse_address: bool,
@@ -468,6 +485,7 @@ pub fn op_node_unstable_net_listen_udp<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   net_listen_udp::<NP>(state, addr, reuse_address, loopback)
 }
 
@@ -544,92 +562,100 @@ pub struct NameServer {
 
 #[op2(async)]
 #[serde]
-pub async fn op_dns_resolve<NP>(
+pub fn op_dns_resolve<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[serde] args: ResolveAddrArgs,
-) -> Result<Vec<DnsReturnRecord>, AnyError>
+) -> impl Future<Output = Result<Vec<DnsReturnRecord>, AnyError>>
 where
   NP: NetPermissions + 'static,
 {
-  let ResolveAddrArgs {
-    query,
-    record_type,
-    options,
-    cancel_rid,
-  } = args;
-
-  let (config, opts) = if let Some(name_server) =
-    options.as_ref().and_then(|o| o.name_server.as_ref())
-  {
-    let group = NameServerConfigGroup::from_ips_clear(
-      &[name_server.ip_addr.parse()?],
-      name_server.port,
-      true,
-    );
-    (
-      ResolverConfig::from_parts(None, vec![], group),
-      ResolverOpts::default(),
-    )
-  } else {
-    system_conf::read_system_conf()?
-  };
+  let location = AuditLocation::capture(scope);
+  async move {
+    let ResolveAddrArgs {
+      query,
+      record_type,
+      options,
+      cancel_rid,
+    } = args;
+
+    let (config, opts) = if let Some(name_server) =
+      options.as_ref().and_then(|o| o.name_server.as_ref())
+    {
+      let group = NameServerConfigGroup::from_ips_clear(
+        &[name_server.ip_addr.parse()?],
+        name_server.port,
+        true,
+      );
+      (
+        ResolverConfig::from_parts(None, vec![], group),
+        ResolverOpts::default(),
+      )
+    } else {
+      system_conf::read_system_conf()?
+    };
 
-  {
-    let mut s = state.borrow_mut();
-    let perm = s.borrow_mut::<NP>();
-
-    // Checks permission against the name servers which will be actually queried.
-    for ns in config.name_servers() {
-      let socker_addr = &ns.socket_addr;
-      let ip = socker_addr.ip().to_string();
-      let port = socker_addr.port();
-      perm.check_net(&(ip, Some(port)), "Deno.resolveDns()")?;
+    {
+      let _location = location.enter();
+      let mut s = state.borrow_mut();
+      let perm = s.borrow_mut::<NP>();
+
+      // Checks permission against the name servers which will be actually queried.
+      for ns in config.name_servers() {
+        let socker_addr = &ns.socket_addr;
+        let ip = socker_addr.ip().to_string();
+        let port = socker_addr.port();
+        perm.check_net(&(ip, Some(port)), "Deno.resolveDns()")?;
+      }
     }
-  }
 
-  let resolver = AsyncResolver::tokio(config, opts);
+    let resolver = AsyncResolver::tokio(config, opts);
 
-  let lookup_fut = resolver.lookup(query, record_type);
+    let lookup_fut = resolver.lookup(query, record_type);
 
-  let cancel_handle = cancel_rid.and_then(|rid| {
-    state
-      .borrow_mut()
-      .resource_table
-      .get::<CancelHandle>(rid)
-      .ok()
-  });
+    let cancel_handle = cancel_rid.and_then(|rid| {
+      state
+        .borrow_mut()
+        .resource_table
+        .get::<CancelHandle>(rid)
+        .ok()
+    });
 
-  let lookup = if let Some(cancel_handle) = cancel_handle {
-    let lookup_rv = lookup_fut.or_cancel(cancel_handle).await;
+    let lookup = if let Some(cancel_handle) = cancel_handle {
+      let lookup_rv = lookup_fut.or_cancel(cancel_handle).await;
 
-    if let Some(cancel_rid) = cancel_rid {
-      if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
-        res.close();
-      }
-    };
+      if let Some(cancel_rid) = cancel_rid {
+        if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid)
+        {
+          res.close();
+        }
+      };
 
-    lookup_rv?
-  } else {
-    lookup_fut.await
-  };
+      lookup_rv?
+    } else {
+      lookup_fut.await
+    };
 
-  lookup
-    .map_err(|e| {
-      let message = format!("{e}");
-      match e.kind() {
-        ResolveErrorKind::NoRecordsFound { .. } => {
-          custom_error("NotFound", message)
-        }
-        ResolveErrorKind::Message("No connections available") => {
-          custom_error("NotConnected", message)
+    lookup
+      .map_err(|e| {
+        let message = format!("{e}");
+        match e.kind() {
+          ResolveErrorKind::NoRecordsFound { .. } => {
+            custom_error("NotFound", message)
+          }
+          ResolveErrorKind::Message("No connections available") => {
+            custom_error("NotConnected", message)
+          }
+          ResolveErrorKind::Timeout => custom_error("TimedOut", message),
+          _ => generic_error(message),
         }
-        ResolveErrorKind::Timeout => custom_error("TimedOut", message),
-        _ => generic_error(message),
-      }
-    })?
-    .iter()
-    .filter_map(|rdata| rdata_to_return_record(record_type)(rdata).transpose())
-    .collect::<Result<Vec<DnsReturnRecord>, AnyError>>()
+      })?
+      .iter()
+      .filter_map(|rdata| {
+        rdata_to_return_record(record_type)(rdata).transpose()
+      })
+      .collect::<Result<Vec<DnsReturnRecord>, AnyError>>()
+  }
 }
 
 #[op2(fast)]
@@ -1066,9 +1092,12 @@ mod tests {
       port: server_addr[1].parse().unwrap(),
     };
 
-    let mut connect_fut =
-      op_net_connect_tcp_inner::<TestPermission>(conn_state, ip_addr)
-        .boxed_local();
+    let mut connect_fut = op_net_connect_tcp_inner::<TestPermission>(
+      conn_state,
+      ip_addr,
+      AuditLocation::default(),
+    )
+    .boxed_local();
     let mut rid = None;
 
     tokio::select! {
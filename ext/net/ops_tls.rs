@@ -27,6 +27,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::AuditLocation;
 use deno_tls::create_client_config;
 use deno_tls::load_certs;
 use deno_tls::load_private_keys;
@@ -49,6 +50,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::convert::From;
 use std::fs::File;
+use std::future::Future;
 use std::io::BufReader;
 use std::io::ErrorKind;
 use std::io::Read;
@@ -217,6 +219,7 @@ pub fn op_tls_key_static(
 #[op2]
 #[cppgc]
 pub fn op_tls_key_static_from_file<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] api: String,
   #[string] cert_file: String,
@@ -225,6 +228,7 @@ pub fn op_tls_key_static_from_file<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<NP>();
     permissions.check_read(Path::new(&cert_file), &api)?;
@@ -285,12 +289,14 @@ pub fn op_tls_cert_resolver_resolve_error(
 #[op2]
 #[serde]
 pub fn op_tls_start<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[serde] args: StartTlsArgs,
 ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let rid = args.rid;
   let hostname = match &*args.hostname {
     "" => "localhost".to_string(),
@@ -369,90 +375,96 @@ where
 
 #[op2(async)]
 #[serde]
-pub async fn op_net_connect_tls<NP>(
+pub fn op_net_connect_tls<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[serde] addr: IpAddr,
   #[serde] args: ConnectTlsArgs,
   #[cppgc] key_pair: &TlsKeysHolder,
-) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
+) -> impl Future<Output = Result<(ResourceId, IpAddr, IpAddr), AnyError>>
 where
   NP: NetPermissions + 'static,
 {
-  let cert_file = args.cert_file.as_deref();
-  let unsafely_ignore_certificate_errors = state
-    .borrow()
-    .try_borrow::<UnsafelyIgnoreCertificateErrors>()
-    .and_then(|it| it.0.clone());
-
-  {
-    let mut s = state.borrow_mut();
-    let permissions = s.borrow_mut::<NP>();
-    permissions
-      .check_net(&(&addr.hostname, Some(addr.port)), "Deno.connectTls()")?;
-    if let Some(path) = cert_file {
-      permissions.check_read(Path::new(path), "Deno.connectTls()")?;
+  let location = AuditLocation::capture(scope);
+  let key_pair = key_pair.take();
+  async move {
+    let cert_file = args.cert_file.as_deref();
+    let unsafely_ignore_certificate_errors = state
+      .borrow()
+      .try_borrow::<UnsafelyIgnoreCertificateErrors>()
+      .and_then(|it| it.0.clone());
+
+    {
+      let _location = location.enter();
+      let mut s = state.borrow_mut();
+      let permissions = s.borrow_mut::<NP>();
+      permissions
+        .check_net(&(&addr.hostname, Some(addr.port)), "Deno.connectTls()")?;
+      if let Some(path) = cert_file {
+        permissions.check_read(Path::new(path), "Deno.connectTls()")?;
+      }
     }
-  }
-
-  let mut ca_certs = args
-    .ca_certs
-    .into_iter()
-    .map(|s| s.into_bytes())
-    .collect::<Vec<_>>();
 
-  if let Some(path) = cert_file {
-    let mut buf = Vec::new();
-    File::open(path)?.read_to_end(&mut buf)?;
-    ca_certs.push(buf);
-  };
+    let mut ca_certs = args
+      .ca_certs
+      .into_iter()
+      .map(|s| s.into_bytes())
+      .collect::<Vec<_>>();
 
-  let root_cert_store = state
-    .borrow()
-    .borrow::<DefaultTlsOptions>()
-    .root_cert_store()?;
-  let hostname_dns = if let Some(server_name) = args.server_name {
-    ServerName::try_from(server_name)
-  } else {
-    ServerName::try_from(addr.hostname.clone())
-  }
-  .map_err(|_| invalid_hostname(&addr.hostname))?;
-  let connect_addr = resolve_addr(&addr.hostname, addr.port)
-    .await?
-    .next()
-    .ok_or_else(|| generic_error("No resolved address found"))?;
-  let tcp_stream = TcpStream::connect(connect_addr).await?;
-  let local_addr = tcp_stream.local_addr()?;
-  let remote_addr = tcp_stream.peer_addr()?;
-
-  let mut tls_config = create_client_config(
-    root_cert_store,
-    ca_certs,
-    unsafely_ignore_certificate_errors,
-    key_pair.take(),
-    SocketUse::GeneralSsl,
-  )?;
+    if let Some(path) = cert_file {
+      let mut buf = Vec::new();
+      File::open(path)?.read_to_end(&mut buf)?;
+      ca_certs.push(buf);
+    };
 
-  if let Some(alpn_protocols) = args.alpn_protocols {
-    tls_config.alpn_protocols =
-      alpn_protocols.into_iter().map(|s| s.into_bytes()).collect();
-  }
+    let root_cert_store = state
+      .borrow()
+      .borrow::<DefaultTlsOptions>()
+      .root_cert_store()?;
+    let hostname_dns = if let Some(server_name) = args.server_name {
+      ServerName::try_from(server_name)
+    } else {
+      ServerName::try_from(addr.hostname.clone())
+    }
+    .map_err(|_| invalid_hostname(&addr.hostname))?;
+    let connect_addr = resolve_addr(&addr.hostname, addr.port)
+      .await?
+      .next()
+      .ok_or_else(|| generic_error("No resolved address found"))?;
+    let tcp_stream = TcpStream::connect(connect_addr).await?;
+    let local_addr = tcp_stream.local_addr()?;
+    let remote_addr = tcp_stream.peer_addr()?;
+
+    let mut tls_config = create_client_config(
+      root_cert_store,
+      ca_certs,
+      unsafely_ignore_certificate_errors,
+      key_pair,
+      SocketUse::GeneralSsl,
+    )?;
+
+    if let Some(alpn_protocols) = args.alpn_protocols {
+      tls_config.alpn_protocols =
+        alpn_protocols.into_iter().map(|s| s.into_bytes()).collect();
+    }
 
-  let tls_config = Arc::new(tls_config);
+    let tls_config = Arc::new(tls_config);
 
-  let tls_stream = TlsStream::new_client_side(
-    tcp_stream,
-    ClientConnection::new(tls_config, hostname_dns)?,
-    TLS_BUFFER_SIZE,
-  );
+    let tls_stream = TlsStream::new_client_side(
+      tcp_stream,
+      ClientConnection::new(tls_config, hostname_dns)?,
+      TLS_BUFFER_SIZE,
+    );
 
-  let rid = {
-    let mut state_ = state.borrow_mut();
-    state_
-      .resource_table
-      .add(TlsStreamResource::new(tls_stream.into_split()))
-  };
+    let rid = {
+      let mut state_ = state.borrow_mut();
+      state_
+        .resource_table
+        .add(TlsStreamResource::new(tls_stream.into_split()))
+    };
 
-  Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
+    Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
+  }
 }
 
 fn load_certs_from_file(
@@ -480,6 +492,7 @@ pub struct ListenTlsArgs {
 #[op2]
 #[serde]
 pub fn op_net_listen_tls<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] addr: IpAddr,
   #[serde] args: ListenTlsArgs,
@@ -488,6 +501,7 @@ pub fn op_net_listen_tls<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   if args.reuse_port {
     super::check_unstable(state, "Deno.listenTls({ reusePort: true })");
   }
@@ -7,6 +7,7 @@ use deno_core::error::bad_resource;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::AsyncRefCell;
 use deno_core::CancelHandle;
 use deno_core::CancelTryFuture;
@@ -15,10 +16,12 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_permissions::AuditLocation;
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::future::Future;
 use std::path::Path;
 use std::rc::Rc;
 use tokio::net::UnixDatagram;
@@ -92,33 +95,41 @@ pub async fn op_net_accept_unix(
 
 #[op2(async)]
 #[serde]
-pub async fn op_net_connect_unix<NP>(
+pub fn op_net_connect_unix<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
-) -> Result<(ResourceId, Option<String>, Option<String>), AnyError>
+) -> impl Future<
+  Output = Result<(ResourceId, Option<String>, Option<String>), AnyError>,
+>
 where
   NP: NetPermissions + 'static,
 {
-  let address_path = Path::new(&path);
-  {
+  let location = AuditLocation::capture(scope);
+  async move {
+    let address_path = Path::new(&path);
+    {
+      let _location = location.enter();
+      let mut state_ = state.borrow_mut();
+      state_
+        .borrow_mut::<NP>()
+        .check_read(address_path, "Deno.connect()")?;
+      state_
+        .borrow_mut::<NP>()
+        .check_write(address_path, "Deno.connect()")?;
+    }
+    let unix_stream = UnixStream::connect(Path::new(&path)).await?;
+    let local_addr = unix_stream.local_addr()?;
+    let remote_addr = unix_stream.peer_addr()?;
+    let local_addr_path =
+      local_addr.as_pathname().map(pathstring).transpose()?;
+    let remote_addr_path =
+      remote_addr.as_pathname().map(pathstring).transpose()?;
     let mut state_ = state.borrow_mut();
-    state_
-      .borrow_mut::<NP>()
-      .check_read(address_path, "Deno.connect()")?;
-    state_
-      .borrow_mut::<NP>()
-      .check_write(address_path, "Deno.connect()")?;
+    let resource = UnixStreamResource::new(unix_stream.into_split());
+    let rid = state_.resource_table.add(resource);
+    Ok((rid, local_addr_path, remote_addr_path))
   }
-  let unix_stream = UnixStream::connect(Path::new(&path)).await?;
-  let local_addr = unix_stream.local_addr()?;
-  let remote_addr = unix_stream.peer_addr()?;
-  let local_addr_path = local_addr.as_pathname().map(pathstring).transpose()?;
-  let remote_addr_path =
-    remote_addr.as_pathname().map(pathstring).transpose()?;
-  let mut state_ = state.borrow_mut();
-  let resource = UnixStreamResource::new(unix_stream.into_split());
-  let rid = state_.resource_table.add(resource);
-  Ok((rid, local_addr_path, remote_addr_path))
 }
 
 #[op2(async)]
@@ -145,38 +156,44 @@ pub async fn op_net_recv_unixpacket(
 
 #[op2(async)]
 #[number]
-pub async fn op_net_send_unixpacket<NP>(
+pub fn op_net_send_unixpacket<NP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[smi] rid: ResourceId,
   #[string] path: String,
   #[buffer] zero_copy: JsBuffer,
-) -> Result<usize, AnyError>
+) -> impl Future<Output = Result<usize, AnyError>>
 where
   NP: NetPermissions + 'static,
 {
-  let address_path = Path::new(&path);
-  {
-    let mut s = state.borrow_mut();
-    s.borrow_mut::<NP>()
-      .check_write(address_path, "Deno.DatagramConn.send()")?;
-  }
+  let location = AuditLocation::capture(scope);
+  async move {
+    let address_path = Path::new(&path);
+    {
+      let _location = location.enter();
+      let mut s = state.borrow_mut();
+      s.borrow_mut::<NP>()
+        .check_write(address_path, "Deno.DatagramConn.send()")?;
+    }
 
-  let resource = state
-    .borrow()
-    .resource_table
-    .get::<UnixDatagramResource>(rid)
-    .map_err(|_| custom_error("NotConnected", "Socket has been closed"))?;
-  let socket = RcRef::map(&resource, |r| &r.socket)
-    .try_borrow_mut()
-    .ok_or_else(|| custom_error("Busy", "Socket already in use"))?;
-  let nwritten = socket.send_to(&zero_copy, address_path).await?;
+    let resource = state
+      .borrow()
+      .resource_table
+      .get::<UnixDatagramResource>(rid)
+      .map_err(|_| custom_error("NotConnected", "Socket has been closed"))?;
+    let socket = RcRef::map(&resource, |r| &r.socket)
+      .try_borrow_mut()
+      .ok_or_else(|| custom_error("Busy", "Socket already in use"))?;
+    let nwritten = socket.send_to(&zero_copy, address_path).await?;
 
-  Ok(nwritten)
+    Ok(nwritten)
+  }
 }
 
 #[op2]
 #[serde]
 pub fn op_net_listen_unix<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
   #[string] api_name: String,
@@ -184,6 +201,7 @@ pub fn op_net_listen_unix<NP>(
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let address_path = Path::new(&path);
   let permissions = state.borrow_mut::<NP>();
   let api_call_expr = format!("{}()", api_name);
@@ -222,12 +240,14 @@ where
 #[op2]
 #[serde]
 pub fn op_net_listen_unixpacket<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<(ResourceId, Option<String>), AnyError>
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   super::check_unstable(state, "Deno.listenDatagram");
   net_listen_unixpacket::<NP>(state, path)
 }
@@ -235,12 +255,14 @@ where
 #[op2]
 #[serde]
 pub fn op_node_unstable_net_listen_unixpacket<NP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<(ResourceId, Option<String>), AnyError>
 where
   NP: NetPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   net_listen_unixpacket::<NP>(state, path)
 }
 
@@ -1,26 +1,31 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::cell::RefCell;
+use std::future::Future;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::OpState;
 use deno_fs::FileSystemRc;
+use deno_permissions::AuditLocation;
 use serde::Serialize;
 
 use crate::NodePermissions;
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_fs_exists_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: String,
 ) -> Result<bool, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = PathBuf::from(path);
   state
     .borrow_mut::<P>()
@@ -29,8 +34,9 @@ where
   Ok(fs.lstat_sync(&path).is_ok())
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_cp_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: &str,
   #[string] new_path: &str,
@@ -38,6 +44,7 @@ pub fn op_node_cp_sync<P>(
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = Path::new(path);
   let new_path = Path::new(new_path);
 
@@ -54,30 +61,35 @@ where
 }
 
 #[op2(async)]
-pub async fn op_node_cp<P>(
+pub fn op_node_cp<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[string] new_path: String,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: NodePermissions + 'static,
 {
-  let path = PathBuf::from(path);
-  let new_path = PathBuf::from(new_path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
+    let new_path = PathBuf::from(new_path);
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_read_with_api_name(&path, Some("node:fs.cpSync"))?;
-    state
-      .borrow_mut::<P>()
-      .check_write_with_api_name(&new_path, Some("node:fs.cpSync"))?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state
+        .borrow_mut::<P>()
+        .check_read_with_api_name(&path, Some("node:fs.cpSync"))?;
+      state
+        .borrow_mut::<P>()
+        .check_write_with_api_name(&new_path, Some("node:fs.cpSync"))?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.cp_async(path, new_path).await?;
-  Ok(())
+    fs.cp_async(path, new_path).await?;
+    Ok(())
+  }
 }
 
 #[derive(Debug, Serialize)]
@@ -95,6 +107,7 @@ pub struct StatFs {
 #[op2]
 #[serde]
 pub fn op_node_statfs<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   bigint: bool,
@@ -102,6 +115,7 @@ pub fn op_node_statfs<P>(
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let mut state = state.borrow_mut();
     state
@@ -223,8 +237,9 @@ where
   }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_lutimes_sync<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] path: &str,
   #[number] atime_secs: i64,
@@ -235,6 +250,7 @@ pub fn op_node_lutimes_sync<P>(
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let path = Path::new(path);
 
   state
@@ -247,29 +263,34 @@ where
 }
 
 #[op2(async)]
-pub async fn op_node_lutimes<P>(
+pub fn op_node_lutimes<P>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] path: String,
   #[number] atime_secs: i64,
   #[smi] atime_nanos: u32,
   #[number] mtime_secs: i64,
   #[smi] mtime_nanos: u32,
-) -> Result<(), AnyError>
+) -> impl Future<Output = Result<(), AnyError>>
 where
   P: NodePermissions + 'static,
 {
-  let path = PathBuf::from(path);
+  let location = AuditLocation::capture(scope);
+  async move {
+    let path = PathBuf::from(path);
 
-  let fs = {
-    let mut state = state.borrow_mut();
-    state
-      .borrow_mut::<P>()
-      .check_write_with_api_name(&path, Some("node:fs.lutimesSync"))?;
-    state.borrow::<FileSystemRc>().clone()
-  };
+    let fs = {
+      let _location = location.enter();
+      let mut state = state.borrow_mut();
+      state
+        .borrow_mut::<P>()
+        .check_write_with_api_name(&path, Some("node:fs.lutimesSync"))?;
+      state.borrow::<FileSystemRc>().clone()
+    };
 
-  fs.lutime_async(path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
-    .await?;
+    fs.lutime_async(path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+      .await?;
 
-  Ok(())
+    Ok(())
+  }
 }
@@ -4,6 +4,7 @@ use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::url::Url;
+use deno_core::v8;
 use deno_core::ByteString;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
@@ -15,6 +16,7 @@ use deno_fetch::FetchRequestResource;
 use deno_fetch::FetchReturn;
 use deno_fetch::HttpClientResource;
 use deno_fetch::ResourceToBodyAdapter;
+use deno_permissions::AuditLocation;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
@@ -25,6 +27,7 @@ use reqwest::Method;
 #[op2]
 #[serde]
 pub fn op_node_http_request<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] method: ByteString,
   #[string] url: String,
@@ -35,6 +38,7 @@ pub fn op_node_http_request<P>(
 where
   P: crate::NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   let client = if let Some(rid) = client_rid {
     let r = state.resource_table.get::<HttpClientResource>(rid)?;
     r.client.clone()
@@ -4,19 +4,23 @@ use crate::NodePermissions;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::OpState;
+use deno_permissions::AuditLocation;
 
 mod cpus;
 mod priority;
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_os_get_priority<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   pid: u32,
 ) -> Result<i32, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("getPriority", "node:os.getPriority()")?;
@@ -25,8 +29,9 @@ where
   priority::get_priority(pid)
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_os_set_priority<P>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   pid: u32,
   priority: i32,
@@ -34,6 +39,7 @@ pub fn op_node_os_set_priority<P>(
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("setPriority", "node:os.setPriority()")?;
@@ -44,10 +50,14 @@ where
 
 #[op2]
 #[string]
-pub fn op_node_os_username<P>(state: &mut OpState) -> Result<String, AnyError>
+pub fn op_node_os_username<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("userInfo", "node:os.userInfo()")?;
@@ -56,11 +66,15 @@ where
   Ok(deno_whoami::username())
 }
 
-#[op2(fast)]
-pub fn op_geteuid<P>(state: &mut OpState) -> Result<u32, AnyError>
+#[op2]
+pub fn op_geteuid<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<u32, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("geteuid", "node:os.geteuid()")?;
@@ -77,10 +91,14 @@ where
 
 #[op2]
 #[serde]
-pub fn op_cpus<P>(state: &mut OpState) -> Result<Vec<cpus::CpuInfo>, AnyError>
+pub fn op_cpus<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Vec<cpus::CpuInfo>, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("cpus", "node:os.cpus()")?;
@@ -91,10 +109,14 @@ where
 
 #[op2]
 #[string]
-pub fn op_homedir<P>(state: &mut OpState) -> Result<Option<String>, AnyError>
+pub fn op_homedir<P>(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Option<String>, AnyError>
 where
   P: NodePermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   {
     let permissions = state.borrow_mut::<P>();
     permissions.check_sys("homedir", "node:os.homedir()")?;
@@ -2,7 +2,9 @@
 
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::OpState;
+use deno_permissions::AuditLocation;
 use deno_permissions::PermissionsContainer;
 
 #[cfg(unix)]
@@ -46,12 +48,14 @@ fn kill(pid: i32, _sig: i32) -> i32 {
   }
 }
 
-#[op2(fast)]
+#[op2]
 pub fn op_node_process_kill(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[smi] pid: i32,
   #[smi] sig: i32,
 ) -> Result<i32, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_run_all("process.kill")?;
@@ -9,6 +9,7 @@ use deno_core::futures::TryFutureExt;
 use deno_core::op2;
 use deno_core::unsync::spawn;
 use deno_core::url;
+use deno_core::v8;
 use deno_core::AsyncMutFuture;
 use deno_core::AsyncRefCell;
 use deno_core::ByteString;
@@ -21,6 +22,7 @@ use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ToJsBuffer;
 use deno_net::raw::NetworkStream;
+use deno_permissions::AuditLocation;
 use deno_tls::create_client_config;
 use deno_tls::rustls::ClientConfig;
 use deno_tls::rustls::ClientConnection;
@@ -133,6 +135,7 @@ impl Resource for WsCancelResource {
 #[op2]
 #[smi]
 pub fn op_ws_check_permission_and_cancel_handle<WP>(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] api_name: String,
   #[string] url: String,
@@ -141,6 +144,7 @@ pub fn op_ws_check_permission_and_cancel_handle<WP>(
 where
   WP: WebSocketPermissions + 'static,
 {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<WP>()
     .check_net_url(&url::Url::parse(&url)?, &api_name)?;
@@ -394,72 +398,77 @@ fn populate_common_request_headers(
 
 #[op2(async)]
 #[serde]
-pub async fn op_ws_create<WP>(
+pub fn op_ws_create<WP>(
+  scope: &mut v8::HandleScope,
   state: Rc<RefCell<OpState>>,
   #[string] api_name: String,
   #[string] url: String,
   #[string] protocols: String,
   #[smi] cancel_handle: Option<ResourceId>,
   #[serde] headers: Option<Vec<(ByteString, ByteString)>>,
-) -> Result<CreateResponse, AnyError>
+) -> impl Future<Output = Result<CreateResponse, AnyError>>
 where
   WP: WebSocketPermissions + 'static,
 {
-  {
-    let mut s = state.borrow_mut();
-    s.borrow_mut::<WP>()
-      .check_net_url(&url::Url::parse(&url)?, &api_name)
-      .expect(
-        "Permission check should have been done in op_ws_check_permission",
-      );
-  }
+  let location = AuditLocation::capture(scope);
+  async move {
+    {
+      let _location = location.enter();
+      let mut s = state.borrow_mut();
+      s.borrow_mut::<WP>()
+        .check_net_url(&url::Url::parse(&url)?, &api_name)
+        .expect(
+          "Permission check should have been done in op_ws_check_permission",
+        );
+    }
 
-  let cancel_resource = if let Some(cancel_rid) = cancel_handle {
-    let r = state
-      .borrow_mut()
-      .resource_table
-      .get::<WsCancelResource>(cancel_rid)?;
-    Some(r.0.clone())
-  } else {
-    None
-  };
+    let cancel_resource = if let Some(cancel_rid) = cancel_handle {
+      let r = state
+        .borrow_mut()
+        .resource_table
+        .get::<WsCancelResource>(cancel_rid)?;
+      Some(r.0.clone())
+    } else {
+      None
+    };
 
-  let uri: Uri = url.parse()?;
-
-  let handshake = handshake_websocket(&state, &uri, &protocols, headers)
-    .map_err(|err| {
-      AnyError::from(DomExceptionNetworkError::new(&format!(
-        "failed to connect to WebSocket: {err}"
-      )))
-    });
-  let (stream, response) = match cancel_resource {
-    Some(rc) => handshake.try_or_cancel(rc).await,
-    None => handshake.await,
-  }?;
-
-  if let Some(cancel_rid) = cancel_handle {
-    if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
-      res.close();
+    let uri: Uri = url.parse()?;
+
+    let handshake = handshake_websocket(&state, &uri, &protocols, headers)
+      .map_err(|err| {
+        AnyError::from(DomExceptionNetworkError::new(&format!(
+          "failed to connect to WebSocket: {err}"
+        )))
+      });
+    let (stream, response) = match cancel_resource {
+      Some(rc) => handshake.try_or_cancel(rc).await,
+      None => handshake.await,
+    }?;
+
+    if let Some(cancel_rid) = cancel_handle {
+      if let Ok(res) = state.borrow_mut().resource_table.take_any(cancel_rid) {
+        res.close();
+      }
     }
-  }
 
-  let mut state = state.borrow_mut();
-  let rid = state.resource_table.add(ServerWebSocket::new(stream));
+    let mut state = state.borrow_mut();
+    let rid = state.resource_table.add(ServerWebSocket::new(stream));
 
-  let protocol = match response.get("Sec-WebSocket-Protocol") {
-    Some(header) => header.to_str().unwrap(),
-    None => "",
-  };
-  let extensions = response
-    .get_all("Sec-WebSocket-Extensions")
-    .iter()
-    .map(|header| header.to_str().unwrap())
-    .collect::<String>();
-  Ok(CreateResponse {
-    rid,
-    protocol: protocol.to_string(),
-    extensions,
-  })
+    let protocol = match response.get("Sec-WebSocket-Protocol") {
+      Some(header) => header.to_str().unwrap(),
+      None => "",
+    };
+    let extensions = response
+      .get_all("Sec-WebSocket-Extensions")
+      .iter()
+      .map(|header| header.to_str().unwrap())
+      .collect::<String>();
+    Ok(CreateResponse {
+      rid,
+      protocol: protocol.to_string(),
+      extensions,
+    })
+  }
 }
 
 #[repr(u16)]
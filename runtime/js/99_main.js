@@ -594,6 +594,7 @@ const NOT_IMPORTED_OPS = [
   "op_register_test_step",
   "op_register_test",
   "op_test_get_origin",
+  "op_test_snapshot_assert",
   "op_pledge_test_permissions",
 
   // TODO(bartlomieju): used in various integration tests - figure out a way
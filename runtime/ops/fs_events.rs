@@ -11,7 +11,9 @@ use deno_core::Resource;
 use deno_core::ResourceId;
 
 use deno_core::op2;
+use deno_core::v8;
 
+use deno_permissions::AuditLocation;
 use deno_permissions::PermissionsContainer;
 use notify::event::Event as NotifyEvent;
 use notify::Error as NotifyError;
@@ -95,9 +97,11 @@ pub struct OpenArgs {
 #[op2]
 #[smi]
 fn op_fs_events_open(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] args: OpenArgs,
 ) -> Result<ResourceId, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let (sender, receiver) = mpsc::channel::<Result<FsEvent, AnyError>>(16);
   let sender = Mutex::new(sender);
   let mut watcher: RecommendedWatcher = Watcher::new(
@@ -9,6 +9,7 @@ use deno_core::url::Url;
 use deno_core::v8;
 use deno_core::OpState;
 use deno_node::NODE_ENV_VAR_ALLOWLIST;
+use deno_permissions::AuditLocation;
 use deno_permissions::PermissionsContainer;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -75,7 +76,11 @@ deno_core::extension!(
 
 #[op2]
 #[string]
-fn op_exec_path(state: &mut OpState) -> Result<String, AnyError> {
+fn op_exec_path(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let current_exe = env::current_exe().unwrap();
   state
     .borrow_mut::<PermissionsContainer>()
@@ -88,12 +93,14 @@ fn op_exec_path(state: &mut OpState) -> Result<String, AnyError> {
   into_string(path.into_os_string())
 }
 
-#[op2(fast)]
+#[op2]
 fn op_set_env(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] key: &str,
   #[string] value: &str,
 ) -> Result<(), AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state.borrow_mut::<PermissionsContainer>().check_env(key)?;
   if key.is_empty() {
     return Err(type_error("Key is an empty string."));
@@ -114,7 +121,11 @@ fn op_set_env(
 
 #[op2]
 #[serde]
-fn op_env(state: &mut OpState) -> Result<HashMap<String, String>, AnyError> {
+fn op_env(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<HashMap<String, String>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state.borrow_mut::<PermissionsContainer>().check_env_all()?;
   Ok(env::vars().collect())
 }
@@ -122,9 +133,11 @@ fn op_env(state: &mut OpState) -> Result<HashMap<String, String>, AnyError> {
 #[op2]
 #[string]
 fn op_get_env(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] key: String,
 ) -> Result<Option<String>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let skip_permission_check = NODE_ENV_VAR_ALLOWLIST.contains(&key);
 
   if !skip_permission_check {
@@ -148,11 +161,13 @@ fn op_get_env(
   Ok(r)
 }
 
-#[op2(fast)]
+#[op2]
 fn op_delete_env(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[string] key: String,
 ) -> Result<(), AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state.borrow_mut::<PermissionsContainer>().check_env(&key)?;
   if key.is_empty() || key.contains(&['=', '\0'] as &[char]) {
     return Err(type_error("Key contains invalid characters."));
@@ -180,7 +195,11 @@ fn op_exit(state: &mut OpState) {
 
 #[op2]
 #[serde]
-fn op_loadavg(state: &mut OpState) -> Result<(f64, f64, f64), AnyError> {
+fn op_loadavg(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<(f64, f64, f64), AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("loadavg", "Deno.loadavg()")?;
@@ -189,7 +208,11 @@ fn op_loadavg(state: &mut OpState) -> Result<(f64, f64, f64), AnyError> {
 
 #[op2]
 #[string]
-fn op_hostname(state: &mut OpState) -> Result<String, AnyError> {
+fn op_hostname(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("hostname", "Deno.hostname()")?;
@@ -198,7 +221,11 @@ fn op_hostname(state: &mut OpState) -> Result<String, AnyError> {
 
 #[op2]
 #[string]
-fn op_os_release(state: &mut OpState) -> Result<String, AnyError> {
+fn op_os_release(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("osRelease", "Deno.osRelease()")?;
@@ -208,8 +235,10 @@ fn op_os_release(state: &mut OpState) -> Result<String, AnyError> {
 #[op2]
 #[serde]
 fn op_network_interfaces(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
 ) -> Result<Vec<NetworkInterface>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("networkInterfaces", "Deno.networkInterfaces()")?;
@@ -260,8 +289,10 @@ impl From<netif::Interface> for NetworkInterface {
 #[op2]
 #[serde]
 fn op_system_memory_info(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
 ) -> Result<Option<sys_info::MemInfo>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("systemMemoryInfo", "Deno.systemMemoryInfo()")?;
@@ -271,7 +302,11 @@ fn op_system_memory_info(
 #[cfg(not(windows))]
 #[op2]
 #[smi]
-fn op_gid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
+fn op_gid(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Option<u32>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("gid", "Deno.gid()")?;
@@ -285,7 +320,11 @@ fn op_gid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
 #[cfg(windows)]
 #[op2]
 #[smi]
-fn op_gid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
+fn op_gid(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Option<u32>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("gid", "Deno.gid()")?;
@@ -295,7 +334,11 @@ fn op_gid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
 #[cfg(not(windows))]
 #[op2]
 #[smi]
-fn op_uid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
+fn op_uid(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Option<u32>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("uid", "Deno.uid()")?;
@@ -309,7 +352,11 @@ fn op_uid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
 #[cfg(windows)]
 #[op2]
 #[smi]
-fn op_uid(state: &mut OpState) -> Result<Option<u32>, AnyError> {
+fn op_uid(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<Option<u32>, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("uid", "Deno.uid()")?;
@@ -494,8 +541,12 @@ fn os_uptime(state: &mut OpState) -> Result<u64, AnyError> {
   Ok(sys_info::os_uptime())
 }
 
-#[op2(fast)]
+#[op2]
 #[number]
-fn op_os_uptime(state: &mut OpState) -> Result<u64, AnyError> {
+fn op_os_uptime(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<u64, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   os_uptime(state)
 }
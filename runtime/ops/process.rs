@@ -6,6 +6,7 @@ use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::serde_json;
+use deno_core::v8;
 use deno_core::AsyncMutFuture;
 use deno_core::AsyncRefCell;
 use deno_core::OpState;
@@ -17,6 +18,7 @@ use deno_io::fs::FileResource;
 use deno_io::ChildStderrResource;
 use deno_io::ChildStdinResource;
 use deno_io::ChildStdoutResource;
+use deno_permissions::AuditLocation;
 use deno_permissions::PermissionsContainer;
 use serde::Deserialize;
 use serde::Serialize;
@@ -590,10 +592,12 @@ fn spawn_child(
 #[op2]
 #[serde]
 fn op_spawn_child(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] args: SpawnArgs,
   #[string] api_name: String,
 ) -> Result<Child, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let (command, pipe_rid) = create_command(state, args, &api_name)?;
   spawn_child(state, command, pipe_rid)
 }
@@ -619,9 +623,11 @@ async fn op_spawn_wait(
 #[op2]
 #[serde]
 fn op_spawn_sync(
+  scope: &mut v8::HandleScope,
   state: &mut OpState,
   #[serde] args: SpawnArgs,
 ) -> Result<SpawnOutput, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let stdout = matches!(args.stdio.stdout, StdioOrRid::Stdio(Stdio::Piped));
   let stderr = matches!(args.stdio.stderr, StdioOrRid::Stdio(Stdio::Piped));
   let (mut command, _) =
@@ -710,9 +716,11 @@ mod deprecated {
   #[op2]
   #[serde]
   pub fn op_run(
+    scope: &mut v8::HandleScope,
     state: &mut OpState,
     #[serde] run_args: RunArgs,
   ) -> Result<RunInfo, AnyError> {
+    let _location = AuditLocation::capture(scope).enter();
     let args = run_args.cmd;
     state
       .borrow_mut::<PermissionsContainer>()
@@ -917,13 +925,15 @@ mod deprecated {
     }
   }
 
-  #[op2(fast)]
+  #[op2]
   pub fn op_kill(
+    scope: &mut v8::HandleScope,
     state: &mut OpState,
     #[smi] pid: i32,
     #[string] signal: String,
     #[string] api_name: String,
   ) -> Result<(), AnyError> {
+    let _location = AuditLocation::capture(scope).enter();
     state
       .borrow_mut::<PermissionsContainer>()
       .check_run_all(&api_name)?;
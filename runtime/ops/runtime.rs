@@ -2,8 +2,10 @@
 
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
+use deno_permissions::AuditLocation;
 use deno_permissions::PermissionsContainer;
 
 deno_core::extension!(
@@ -17,7 +19,11 @@ deno_core::extension!(
 
 #[op2]
 #[string]
-fn op_main_module(state: &mut OpState) -> Result<String, AnyError> {
+fn op_main_module(
+  scope: &mut v8::HandleScope,
+  state: &mut OpState,
+) -> Result<String, AnyError> {
+  let _location = AuditLocation::capture(scope).enter();
   let main_url = state.borrow::<ModuleSpecifier>();
   let main_path = main_url.to_string();
   if main_url.scheme() == "file" {
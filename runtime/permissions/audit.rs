@@ -0,0 +1,215 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Audit log of the permission checks, written as JSON lines. Each check of a
+//! `UnaryPermission` or `UnitPermission` is recorded, including the ones of
+//! permissions that are fully granted.
+
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::v8;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static AUDIT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static AUDIT_LOG: Lazy<Mutex<Option<Box<dyn Write + Send>>>> =
+  Lazy::new(|| Mutex::new(None));
+
+thread_local! {
+  // the location of the op that is running on the thread
+  static AUDIT_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionAuditResult {
+  Granted,
+  Denied,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionAuditEntry<'a> {
+  /// The kind of permission, like "read" or "net".
+  pub kind: &'a str,
+  /// What was accessed, or `None` when checking the whole permission.
+  pub descriptor: Option<String>,
+  pub api_name: Option<&'a str>,
+  pub result: PermissionAuditResult,
+  /// Whether the user was prompted for the permission.
+  pub prompted: bool,
+  pub stack: Option<String>,
+}
+
+/// Starts writing the permission checks to the log.
+pub fn set_audit_log(writer: Box<dyn Write + Send>) {
+  *AUDIT_LOG.lock() = Some(writer);
+  AUDIT_ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[inline(always)]
+pub fn is_audit_enabled() -> bool {
+  AUDIT_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The location in the JS code that called an op, like
+/// `file:///main.ts:1:12`, which the permission checks of the op are audited
+/// with. It's captured by the op, where a scope is available, so checks made
+/// outside of ops, like when loading modules, have no location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLocation(Option<String>);
+
+impl AuditLocation {
+  /// Captures the location of the JS code calling the op, when the checks
+  /// are audited.
+  pub fn capture(scope: &mut v8::HandleScope) -> Self {
+    if !is_audit_enabled() {
+      return Self(None);
+    }
+    Self(current_js_location(scope))
+  }
+
+  /// Audits the checks made on the current thread with this location until
+  /// the guard is dropped. The guard must not be held across an `await`,
+  /// where other ops run.
+  pub fn enter(&self) -> AuditLocationGuard {
+    let previous = AUDIT_LOCATION.with(|cell| cell.replace(self.0.clone()));
+    AuditLocationGuard { previous }
+  }
+}
+
+/// Restores the previous location when dropped.
+pub struct AuditLocationGuard {
+  previous: Option<String>,
+}
+
+impl Drop for AuditLocationGuard {
+  fn drop(&mut self) {
+    let previous = self.previous.take();
+    AUDIT_LOCATION.with(|cell| *cell.borrow_mut() = previous);
+  }
+}
+
+/// The first frame of the stack that isn't in the code of the runtime.
+fn current_js_location(scope: &mut v8::HandleScope) -> Option<String> {
+  let stack = v8::StackTrace::current_stack_trace(scope, 16)?;
+  (0..stack.get_frame_count()).find_map(|i| {
+    let frame = stack.get_frame(scope, i)?;
+    let script_name = frame
+      .get_script_name_or_source_url(scope)?
+      .to_rust_string_lossy(scope);
+    if script_name.starts_with("ext:") {
+      return None;
+    }
+    Some(format!(
+      "{}:{}:{}",
+      script_name,
+      frame.get_line_number(),
+      frame.get_column()
+    ))
+  })
+}
+
+#[inline]
+pub(crate) fn audit_permission_check(
+  kind: &str,
+  descriptor: impl FnOnce() -> Option<String>,
+  api_name: Option<&str>,
+  granted: bool,
+  prompted: bool,
+) {
+  if !is_audit_enabled() {
+    return;
+  }
+  let stack = AUDIT_LOCATION.with(|cell| cell.borrow().clone());
+  let entry = PermissionAuditEntry {
+    kind,
+    descriptor: descriptor(),
+    api_name,
+    result: if granted {
+      PermissionAuditResult::Granted
+    } else {
+      PermissionAuditResult::Denied
+    },
+    prompted,
+    stack,
+  };
+  if let Some(writer) = AUDIT_LOG.lock().as_mut() {
+    // failing to write the log shouldn't fail the check
+    let _ = write_entry(writer, &entry);
+  }
+}
+
+fn write_entry(
+  writer: &mut dyn Write,
+  entry: &PermissionAuditEntry,
+) -> std::io::Result<()> {
+  let mut line = serde_json::to_vec(entry)?;
+  line.push(b'\n');
+  // a single write, so the lines of the workers don't interleave
+  writer.write_all(&line)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn audit_entry_line() {
+    let mut output = Vec::new();
+    write_entry(
+      &mut output,
+      &PermissionAuditEntry {
+        kind: "read",
+        descriptor: Some("/data/config.json".to_string()),
+        api_name: Some("Deno.readTextFile()"),
+        result: PermissionAuditResult::Granted,
+        prompted: false,
+        stack: Some("file:///main.ts:1:12".to_string()),
+      },
+    )
+    .unwrap();
+    write_entry(
+      &mut output,
+      &PermissionAuditEntry {
+        kind: "hrtime",
+        descriptor: None,
+        api_name: None,
+        result: PermissionAuditResult::Denied,
+        prompted: true,
+        stack: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      concat!(
+        r#"{"kind":"read","descriptor":"/data/config.json","apiName":"Deno.readTextFile()","result":"granted","prompted":false,"stack":"file:///main.ts:1:12"}"#,
+        "\n",
+        r#"{"kind":"hrtime","descriptor":null,"apiName":null,"result":"denied","prompted":true,"stack":null}"#,
+        "\n",
+      )
+    );
+  }
+
+  #[test]
+  fn audit_location_restored() {
+    let current = || AUDIT_LOCATION.with(|cell| cell.borrow().clone());
+    let outer = AuditLocation(Some("file:///a.ts:1:1".to_string()));
+    let inner = AuditLocation(Some("file:///b.ts:2:2".to_string()));
+    {
+      let _outer = outer.enter();
+      {
+        let _inner = inner.enter();
+        assert_eq!(current().as_deref(), Some("file:///b.ts:2:2"));
+      }
+      assert_eq!(current().as_deref(), Some("file:///a.ts:1:1"));
+    }
+    // checks outside of ops have no location
+    assert_eq!(current(), None);
+  }
+}
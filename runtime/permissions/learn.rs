@@ -0,0 +1,83 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Records the permissions that a program uses, so that the narrowest flags
+//! that allow them can be suggested.
+
+use deno_core::parking_lot::Mutex;
+use once_cell::sync::Lazy;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::Descriptor;
+use crate::EnvDescriptor;
+use crate::FfiDescriptor;
+use crate::NetDescriptor;
+use crate::ReadDescriptor;
+use crate::RunDescriptor;
+use crate::SysDescriptor;
+use crate::WriteDescriptor;
+
+/// The permissions used by a program.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LearnedPermissions {
+  pub read: HashSet<ReadDescriptor>,
+  pub write: HashSet<WriteDescriptor>,
+  pub net: HashSet<NetDescriptor>,
+  pub env: HashSet<EnvDescriptor>,
+  pub run: HashSet<RunDescriptor>,
+  pub sys: HashSet<SysDescriptor>,
+  pub ffi: HashSet<FfiDescriptor>,
+  /// The kinds of permissions that were used as a whole, like "env" for
+  /// `Deno.env.toObject()`, and the unit permissions like "hrtime".
+  pub all: BTreeSet<&'static str>,
+}
+
+static LEARN_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static LEARNED: Lazy<Mutex<LearnedPermissions>> =
+  Lazy::new(|| Mutex::new(LearnedPermissions::default()));
+
+/// Starts recording the permissions that are granted.
+pub fn start_learning_permissions() {
+  LEARN_ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[inline(always)]
+pub fn is_learning_permissions() -> bool {
+  LEARN_ENABLED.load(Ordering::Relaxed)
+}
+
+/// The permissions recorded so far.
+pub fn learned_permissions() -> LearnedPermissions {
+  LEARNED.lock().clone()
+}
+
+/// The permissions recorded so far, unless another thread is recording one
+/// right now.
+pub fn try_learned_permissions() -> Option<LearnedPermissions> {
+  LEARNED.try_lock().map(|learned| learned.clone())
+}
+
+#[inline]
+pub(crate) fn learn_permission_use<T: Descriptor>(desc: Option<&T>) {
+  if !is_learning_permissions() {
+    return;
+  }
+  let mut learned = LEARNED.lock();
+  match desc {
+    Some(desc) => desc.learn_use(&mut learned),
+    None => {
+      learned.all.insert(T::flag_name());
+    }
+  }
+}
+
+#[inline]
+pub(crate) fn learn_unit_permission_use(name: &'static str) {
+  if !is_learning_permissions() {
+    return;
+  }
+  LEARNED.lock().all.insert(name);
+}
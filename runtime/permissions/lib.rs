@@ -25,6 +25,7 @@ use std::ffi::OsStr;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::path::Component;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -32,19 +33,38 @@ use std::string::ToString;
 use std::sync::Arc;
 use which::which;
 
+pub mod audit;
+pub mod learn;
 pub mod prompter;
+use audit::audit_permission_check;
+use learn::learn_permission_use;
+use learn::learn_unit_permission_use;
+use learn::LearnedPermissions;
 use prompter::permission_prompt;
 use prompter::PromptResponse;
 use prompter::PERMISSION_EMOJI;
 
+pub use audit::is_audit_enabled;
+pub use audit::set_audit_log;
+pub use audit::AuditLocation;
+pub use learn::is_learning_permissions;
+pub use learn::start_learning_permissions;
 pub use prompter::set_prompt_callbacks;
+pub use prompter::set_prompter;
+pub use prompter::PermissionPrompter;
 pub use prompter::PromptCallback;
+pub use prompter::PromptSocketAddress;
+pub use prompter::SocketPrompter;
 
 /// Fast exit from permission check routines if this permission
-/// is in the "fully-granted" state.
+/// is in the "fully-granted" state, unless the checks are audited or the
+/// used permissions are learned.
 macro_rules! skip_check_if_is_permission_fully_granted {
   ($this:ident) => {
-    if $this.is_allow_all() {
+    if $this.is_allow_all()
+      && !audit::is_audit_enabled()
+      && !learn::is_learning_permissions()
+    {
       return Ok(());
     }
   };
@@ -62,6 +82,90 @@ fn resolve_from_cwd(path: &Path) -> Result<PathBuf, AnyError> {
   }
 }
 
+/// The path of a read, write or ffi descriptor. Whether a path of the
+/// permission lists is a glob is decided once when parsing them, while the
+/// paths that are checked are always literal.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PermissionPath {
+  Literal(PathBuf),
+  /// A path with glob characters, like `./data/**/*.json`.
+  Glob(glob::Pattern),
+}
+
+impl PermissionPath {
+  /// Parses a path of a permission list. A path whose glob characters belong
+  /// to a file or directory that exists, like `./data[1]`, is literal.
+  fn parse(path: PathBuf) -> Result<Self, glob::PatternError> {
+    let mut prefix = PathBuf::new();
+    for component in path.components() {
+      prefix.push(component);
+      if is_glob_component(&component) {
+        #[allow(clippy::disallowed_methods)]
+        if prefix.exists() {
+          break;
+        }
+        return glob::Pattern::new(&path.to_string_lossy()).map(Self::Glob);
+      }
+    }
+    Ok(Self::Literal(path))
+  }
+
+  /// Resolves a path that is checked, which is never a glob.
+  fn requested(path: &Path) -> Result<Self, AnyError> {
+    resolve_from_cwd(path).map(Self::Literal)
+  }
+
+  /// The path, or the pattern of a glob.
+  pub fn as_path(&self) -> &Path {
+    match self {
+      Self::Literal(path) => path,
+      Self::Glob(pattern) => Path::new(pattern.as_str()),
+    }
+  }
+
+  /// Whether the path covers another one. A literal path covers itself and
+  /// everything in it, and a glob covers the paths that it matches and
+  /// everything in them.
+  fn stronger_than(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::Literal(path), Self::Literal(other)) => other.starts_with(path),
+      (Self::Literal(path), Self::Glob(other)) => {
+        glob_base(Path::new(other.as_str())).starts_with(path)
+      }
+      (Self::Glob(pattern), Self::Literal(other)) => {
+        let options = glob::MatchOptions {
+          case_sensitive: true,
+          // `*` stays in a directory and `**` crosses them
+          require_literal_separator: true,
+          require_literal_leading_dot: false,
+        };
+        other
+          .ancestors()
+          .any(|path| pattern.matches_path_with(path, options))
+      }
+      // whether two globs overlap isn't known, so only a glob covers itself
+      (Self::Glob(pattern), Self::Glob(other)) => pattern == other,
+    }
+  }
+}
+
+// the prefixes of verbatim paths on Windows, like `\\?\C:`, aren't globs
+fn is_glob_component(component: &Component) -> bool {
+  match component {
+    Component::Normal(name) => name.to_string_lossy().contains(['*', '?', '[']),
+    _ => false,
+  }
+}
+
+/// The directory of a glob before its first glob character, which contains
+/// all the paths that it matches.
+fn glob_base(path: &Path) -> PathBuf {
+  path
+    .components()
+    .take_while(|c| !is_glob_component(c))
+    .collect()
+}
+
 static DEBUG_LOG_ENABLED: Lazy<bool> =
   Lazy::new(|| log::log_enabled!(log::Level::Debug));
 
@@ -243,6 +347,10 @@ impl UnitPermission {
   pub fn check(&mut self) -> Result<(), AnyError> {
     let (result, prompted, _is_allow_all) =
       self.state.check(self.name, None, None, self.prompt);
+    audit_permission_check(self.name, || None, None, result.is_ok(), prompted);
+    if result.is_ok() {
+      learn_unit_permission_use(self.name);
+    }
     if prompted {
       if result.is_ok() {
         self.state = PermissionState::Granted;
@@ -328,6 +436,8 @@ pub trait Descriptor: Eq + Clone + Hash {
   fn aliases(&self) -> Vec<Self> {
     vec![]
   }
+  /// Records the use of this descriptor when learning the used permissions.
+  fn learn_use(&self, _learned: &mut LearnedPermissions) {}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -396,6 +506,16 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
         },
         self.prompt,
       );
+    audit_permission_check(
+      T::flag_name(),
+      || desc.map(|d| d.name().into_owned()),
+      api_name,
+      result.is_ok(),
+      prompted,
+    );
+    if result.is_ok() {
+      learn_permission_use(desc);
+    }
     if prompted {
       if result.is_ok() {
         if is_allow_all {
@@ -627,7 +747,7 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct ReadDescriptor(pub PathBuf);
+pub struct ReadDescriptor(pub PermissionPath);
 
 impl Descriptor for ReadDescriptor {
   type Arg = PathBuf;
@@ -649,17 +769,21 @@ impl Descriptor for ReadDescriptor {
     "read"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.read.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
-    Cow::from(self.0.display().to_string())
+    Cow::from(self.0.as_path().display().to_string())
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    other.0.starts_with(&self.0)
+    self.0.stronger_than(&other.0)
   }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct WriteDescriptor(pub PathBuf);
+pub struct WriteDescriptor(pub PermissionPath);
 
 impl Descriptor for WriteDescriptor {
   type Arg = PathBuf;
@@ -681,21 +805,98 @@ impl Descriptor for WriteDescriptor {
     "write"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.write.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
-    Cow::from(self.0.display().to_string())
+    Cow::from(self.0.as_path().display().to_string())
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    other.0.starts_with(&self.0)
+    self.0.stronger_than(&other.0)
   }
 }
 
+/// The host of a net permission.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct NetDescriptor(pub FQDN, pub Option<u16>);
+pub enum NetHost {
+  Fqdn(FQDN),
+  /// `*.example.com`, which matches all the subdomains of the domain but not
+  /// the domain itself.
+  Wildcard(FQDN),
+}
+
+impl NetHost {
+  fn stronger_than(&self, other: &Self) -> bool {
+    match (self, other) {
+      (NetHost::Fqdn(host), NetHost::Fqdn(other)) => host == other,
+      (NetHost::Wildcard(domain), NetHost::Fqdn(other)) => {
+        is_subdomain(other, domain)
+      }
+      (NetHost::Wildcard(domain), NetHost::Wildcard(other)) => {
+        domain == other || is_subdomain(other, domain)
+      }
+      (NetHost::Fqdn(_), NetHost::Wildcard(_)) => false,
+    }
+  }
+}
+
+fn is_subdomain(host: &FQDN, domain: &FQDN) -> bool {
+  let host = host.to_string();
+  let domain = domain.to_string();
+  host.len() > domain.len()
+    && host.is_char_boundary(host.len() - domain.len())
+    && host[host.len() - domain.len()..].eq_ignore_ascii_case(&domain)
+    && host[..host.len() - domain.len()].ends_with('.')
+}
+
+impl fmt::Display for NetHost {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NetHost::Fqdn(host) => write!(f, "{}", host),
+      NetHost::Wildcard(domain) => write!(f, "*.{}", domain),
+    }
+  }
+}
+
+/// An inclusive range of ports, like `8000-8100`. A single port is a range
+/// with the same start and end.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct NetPortRange(pub u16, pub u16);
+
+impl NetPortRange {
+  fn contains(&self, other: &Self) -> bool {
+    self.0 <= other.0 && other.1 <= self.1
+  }
+}
+
+impl fmt::Display for NetPortRange {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.0 == self.1 {
+      write!(f, "{}", self.0)
+    } else {
+      write!(f, "{}-{}", self.0, self.1)
+    }
+  }
+}
+
+fn parse_port_range(s: &str) -> Option<NetPortRange> {
+  let (start, end) = s.split_once('-')?;
+  let start = start.parse().ok()?;
+  let end = end.parse().ok()?;
+  (start <= end).then_some(NetPortRange(start, end))
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct NetDescriptor(pub NetHost, pub Option<NetPortRange>);
 
 impl NetDescriptor {
   fn new<T: AsRef<str>>(host: &&(T, Option<u16>)) -> Self {
-    NetDescriptor(fqdn!(host.0.as_ref()), host.1)
+    NetDescriptor(
+      NetHost::Fqdn(fqdn!(host.0.as_ref())),
+      host.1.map(|port| NetPortRange(port, port)),
+    )
   }
 }
 
@@ -719,12 +920,21 @@ impl Descriptor for NetDescriptor {
     "net"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.net.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
     Cow::from(format!("{}", self))
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    self.0 == other.0 && (self.1.is_none() || self.1 == other.1)
+    self.0.stronger_than(&other.0)
+      && match (self.1, other.1) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(ports), Some(other)) => ports.contains(&other),
+      }
   }
 }
 
@@ -732,25 +942,41 @@ impl FromStr for NetDescriptor {
   type Err = AnyError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
+    // a port range isn't a valid port of a URL, so it's parsed separately
+    let (s, port_range) = match s
+      .rsplit_once(':')
+      .and_then(|(host, ports)| Some((host, parse_port_range(ports)?)))
+    {
+      Some((host, port_range)) => (host, Some(port_range)),
+      None => (s, None),
+    };
     // Set the scheme to `unknown` to parse the URL, as we really don't know
     // what the scheme is. We only using Url::parse to parse the host and port
     // and don't care about the scheme.
     let url = url::Url::parse(&format!("unknown://{s}"))?;
-    let hostname = url
-      .host_str()
-      .ok_or(url::ParseError::EmptyHost)?
-      .to_string();
+    let hostname = url.host_str().ok_or(url::ParseError::EmptyHost)?;
+    let host = match hostname.strip_prefix("*.") {
+      Some(domain) if !domain.contains('*') => NetHost::Wildcard(fqdn!(domain)),
+      _ if hostname.contains('*') => {
+        return Err(uri_error(format!(
+          "A wildcard is only allowed as the first label of a host: {s}"
+        )))
+      }
+      _ => NetHost::Fqdn(fqdn!(hostname)),
+    };
+    let port_range =
+      port_range.or(url.port().map(|port| NetPortRange(port, port)));
 
-    Ok(NetDescriptor(fqdn!(&hostname), url.port()))
+    Ok(NetDescriptor(host, port_range))
   }
 }
 
 impl fmt::Display for NetDescriptor {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    f.write_str(&match self.1 {
-      None => self.0.to_string(),
-      Some(port) => format!("{}:{}", self.0, port),
-    })
+    match self.1 {
+      None => write!(f, "{}", self.0),
+      Some(ports) => write!(f, "{}:{}", self.0, ports),
+    }
   }
 }
 
@@ -783,6 +1009,10 @@ impl Descriptor for EnvDescriptor {
     "env"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.env.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
     Cow::from(self.0.as_ref())
   }
@@ -824,6 +1054,10 @@ impl Descriptor for RunDescriptor {
     "run"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.run.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
     Cow::from(self.to_string())
   }
@@ -907,6 +1141,10 @@ impl Descriptor for SysDescriptor {
     "sys"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.sys.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
     Cow::from(self.0.to_string())
   }
@@ -921,7 +1159,7 @@ pub fn parse_sys_kind(kind: &str) -> Result<&str, AnyError> {
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct FfiDescriptor(pub PathBuf);
+pub struct FfiDescriptor(pub PermissionPath);
 
 impl Descriptor for FfiDescriptor {
   type Arg = PathBuf;
@@ -943,12 +1181,16 @@ impl Descriptor for FfiDescriptor {
     "ffi"
   }
 
+  fn learn_use(&self, learned: &mut LearnedPermissions) {
+    learned.ffi.insert(self.clone());
+  }
+
   fn name(&self) -> Cow<str> {
-    Cow::from(self.0.display().to_string())
+    Cow::from(self.0.as_path().display().to_string())
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    other.0.starts_with(&self.0)
+    self.0.stronger_than(&other.0)
   }
 }
 
@@ -956,7 +1198,7 @@ impl UnaryPermission<ReadDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
     self.query_desc(
       path
-        .map(|p| ReadDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| ReadDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       AllowPartial::TreatAsPartialGranted,
     )
@@ -965,7 +1207,7 @@ impl UnaryPermission<ReadDescriptor> {
   pub fn request(&mut self, path: Option<&Path>) -> PermissionState {
     self.request_desc(
       path
-        .map(|p| ReadDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| ReadDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       || Some(path?.display().to_string()),
     )
@@ -974,7 +1216,7 @@ impl UnaryPermission<ReadDescriptor> {
   pub fn revoke(&mut self, path: Option<&Path>) -> PermissionState {
     self.revoke_desc(
       path
-        .map(|p| ReadDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| ReadDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
     )
   }
@@ -986,7 +1228,7 @@ impl UnaryPermission<ReadDescriptor> {
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
     self.check_desc(
-      Some(&ReadDescriptor(resolve_from_cwd(path)?)),
+      Some(&ReadDescriptor(PermissionPath::requested(path)?)),
       true,
       api_name,
       || Some(format!("\"{}\"", path.display())),
@@ -1000,7 +1242,7 @@ impl UnaryPermission<ReadDescriptor> {
     api_name: Option<&str>,
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
-    let desc = ReadDescriptor(resolve_from_cwd(path)?);
+    let desc = ReadDescriptor(PermissionPath::requested(path)?);
     self.check_desc(Some(&desc), false, api_name, || {
       Some(format!("\"{}\"", path.display()))
     })
@@ -1015,7 +1257,7 @@ impl UnaryPermission<ReadDescriptor> {
     api_name: &str,
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
-    let desc = ReadDescriptor(resolve_from_cwd(path)?);
+    let desc = ReadDescriptor(PermissionPath::requested(path)?);
     self.check_desc(Some(&desc), false, Some(api_name), || {
       Some(format!("<{display}>"))
     })
@@ -1031,7 +1273,7 @@ impl UnaryPermission<WriteDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
     self.query_desc(
       path
-        .map(|p| WriteDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| WriteDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       AllowPartial::TreatAsPartialGranted,
     )
@@ -1040,7 +1282,7 @@ impl UnaryPermission<WriteDescriptor> {
   pub fn request(&mut self, path: Option<&Path>) -> PermissionState {
     self.request_desc(
       path
-        .map(|p| WriteDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| WriteDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       || Some(path?.display().to_string()),
     )
@@ -1049,7 +1291,7 @@ impl UnaryPermission<WriteDescriptor> {
   pub fn revoke(&mut self, path: Option<&Path>) -> PermissionState {
     self.revoke_desc(
       path
-        .map(|p| WriteDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| WriteDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
     )
   }
@@ -1061,7 +1303,7 @@ impl UnaryPermission<WriteDescriptor> {
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
     self.check_desc(
-      Some(&WriteDescriptor(resolve_from_cwd(path)?)),
+      Some(&WriteDescriptor(PermissionPath::requested(path)?)),
       true,
       api_name,
       || Some(format!("\"{}\"", path.display())),
@@ -1076,7 +1318,7 @@ impl UnaryPermission<WriteDescriptor> {
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
     self.check_desc(
-      Some(&WriteDescriptor(resolve_from_cwd(path)?)),
+      Some(&WriteDescriptor(PermissionPath::requested(path)?)),
       false,
       api_name,
       || Some(format!("\"{}\"", path.display())),
@@ -1092,7 +1334,7 @@ impl UnaryPermission<WriteDescriptor> {
     api_name: &str,
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
-    let desc = WriteDescriptor(resolve_from_cwd(path)?);
+    let desc = WriteDescriptor(PermissionPath::requested(path)?);
     self.check_desc(Some(&desc), false, Some(api_name), || {
       Some(format!("<{display}>"))
     })
@@ -1277,7 +1519,7 @@ impl UnaryPermission<FfiDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
     self.query_desc(
       path
-        .map(|p| FfiDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| FfiDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       AllowPartial::TreatAsPartialGranted,
     )
@@ -1286,7 +1528,7 @@ impl UnaryPermission<FfiDescriptor> {
   pub fn request(&mut self, path: Option<&Path>) -> PermissionState {
     self.request_desc(
       path
-        .map(|p| FfiDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| FfiDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
       || Some(path?.display().to_string()),
     )
@@ -1295,7 +1537,7 @@ impl UnaryPermission<FfiDescriptor> {
   pub fn revoke(&mut self, path: Option<&Path>) -> PermissionState {
     self.revoke_desc(
       path
-        .map(|p| FfiDescriptor(resolve_from_cwd(p).unwrap()))
+        .map(|p| FfiDescriptor(PermissionPath::requested(p).unwrap()))
         .as_ref(),
     )
   }
@@ -1307,7 +1549,7 @@ impl UnaryPermission<FfiDescriptor> {
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
     self.check_desc(
-      Some(&FfiDescriptor(resolve_from_cwd(path)?)),
+      Some(&FfiDescriptor(PermissionPath::requested(path)?)),
       true,
       api_name,
       || Some(format!("\"{}\"", path.display())),
@@ -1317,7 +1559,7 @@ impl UnaryPermission<FfiDescriptor> {
   pub fn check_partial(&mut self, path: Option<&Path>) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
     let desc = match path {
-      Some(path) => Some(FfiDescriptor(resolve_from_cwd(path)?)),
+      Some(path) => Some(FfiDescriptor(PermissionPath::requested(path)?)),
       None => None,
     };
     self.check_desc(desc.as_ref(), false, None, || {
@@ -1854,7 +2096,7 @@ fn parse_env_list(
 
 fn parse_path_list<T: Descriptor + Hash>(
   list: &Option<Vec<PathBuf>>,
-  f: fn(PathBuf) -> T,
+  f: fn(PermissionPath) -> T,
 ) -> Result<HashSet<T>, AnyError> {
   if let Some(v) = list {
     v.iter()
@@ -1862,7 +2104,14 @@ fn parse_path_list<T: Descriptor + Hash>(
         if raw_path.as_os_str().is_empty() {
           Err(AnyError::msg("Empty path is not allowed"))
         } else {
-          resolve_from_cwd(Path::new(&raw_path)).map(f)
+          let path = PermissionPath::parse(resolve_from_cwd(raw_path)?)
+            .map_err(|_| {
+              AnyError::msg(format!(
+                "Invalid glob pattern: {}",
+                raw_path.display()
+              ))
+            })?;
+          Ok(f(path))
         }
       })
       .collect()
@@ -3048,6 +3297,198 @@ mod tests {
     assert!(perms.net.check(&("2.2.2.2.", None), None).is_err());
   }
 
+  #[test]
+  fn test_check_glob_paths() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions {
+      read: Permissions::new_unary(
+        &Some(vec![PathBuf::from("/data/**/*.json")]),
+        &Some(vec![PathBuf::from("/data/**/secret*")]),
+        false,
+      )
+      .unwrap(),
+      ..Permissions::none_without_prompt()
+    };
+
+    let read_tests = [
+      ("/data/a.json", true),
+      ("/data/nested/b.json", true),
+      ("/data/nested/deep/c.json", true),
+      ("/data/a.txt", false),
+      ("/data", false),
+      ("/other/a.json", false),
+      ("/data/secret.json", false),
+      ("/data/nested/secrets.json", false),
+    ];
+    for (path, is_ok) in read_tests {
+      assert_eq!(
+        is_ok,
+        perms.read.check(Path::new(path), None).is_ok(),
+        "{}",
+        path
+      );
+    }
+
+    assert_eq!(
+      perms.read.query(Some(Path::new("/data/nested/b.json"))),
+      PermissionState::Granted
+    );
+    assert_eq!(
+      perms.read.query(Some(Path::new("/data/secret.json"))),
+      PermissionState::Denied
+    );
+    assert_eq!(
+      perms.read.query(Some(Path::new("/data/a.txt"))),
+      PermissionState::Prompt
+    );
+
+    // the paths that might contain denied ones are partially granted
+    let mut perms = Permissions {
+      read: Permissions::new_unary(
+        &Some(vec![]),
+        &Some(vec![PathBuf::from("/data/**/*.key")]),
+        false,
+      )
+      .unwrap(),
+      ..Permissions::none_without_prompt()
+    };
+    assert_eq!(
+      perms.read.query(Some(Path::new("/data"))),
+      PermissionState::GrantedPartial
+    );
+    assert!(perms.read.check(Path::new("/data/a.json"), None).is_ok());
+    assert!(perms.read.check(Path::new("/data/a/b.key"), None).is_err());
+
+    assert!(Permissions::new_unary::<ReadDescriptor>(
+      &Some(vec![PathBuf::from("/data/[a")]),
+      &None,
+      false
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_check_literal_bracket_paths() {
+    set_prompter(Box::new(TestPrompter));
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data_dir = temp_dir.path().join("data[1]");
+    #[allow(clippy::disallowed_methods)]
+    std::fs::create_dir(&data_dir).unwrap();
+    let mut perms = Permissions {
+      read: Permissions::new_unary(&Some(vec![data_dir.clone()]), &None, false)
+        .unwrap(),
+      ..Permissions::none_without_prompt()
+    };
+
+    // the existing directory is matched literally, including new files in it
+    assert!(perms.read.check(&data_dir, None).is_ok());
+    assert!(perms.read.check(&data_dir.join("a.json"), None).is_ok());
+    assert!(perms
+      .read
+      .check(&temp_dir.path().join("data1"), None)
+      .is_err());
+    assert_eq!(
+      perms.read.query(Some(data_dir.join("a.json").as_path())),
+      PermissionState::Granted
+    );
+  }
+
+  #[test]
+  fn test_check_glob_deny_paths() {
+    set_prompter(Box::new(TestPrompter));
+    let temp_dir = tempfile::tempdir().unwrap();
+    let proj = temp_dir.path().to_path_buf();
+    let mut perms = Permissions {
+      write: Permissions::new_unary(
+        &Some(vec![proj.clone()]),
+        &Some(vec![proj.join("**").join("*.key")]),
+        false,
+      )
+      .unwrap(),
+      ..Permissions::none_without_prompt()
+    };
+
+    // a checked path with glob characters isn't a glob
+    assert!(perms.write.check(&proj.join("[a].key"), None).is_err());
+    assert!(perms.write.check(&proj.join("a.txt"), None).is_ok());
+
+    // creating a file named like the glob doesn't make it literal
+    #[cfg(unix)]
+    {
+      #[allow(clippy::disallowed_methods)]
+      std::fs::create_dir(proj.join("**")).unwrap();
+      assert!(perms.write.check(&proj.join("a.key"), None).is_err());
+      assert!(perms
+        .write
+        .check(&proj.join("**").join("a.key"), None)
+        .is_err());
+    }
+  }
+
+  #[test]
+  fn test_net_wildcards_and_port_ranges() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions {
+      net: Permissions::new_unary(
+        &Some(svec![
+          "*.internal.example.com",
+          "localhost:8000-8100",
+          "*.api.example.com:443"
+        ]),
+        &Some(svec!["*.secret.internal.example.com", "localhost:8080"]),
+        false,
+      )
+      .unwrap(),
+      ..Permissions::none_without_prompt()
+    };
+
+    let net_tests = [
+      ("db.internal.example.com", 5432, true),
+      ("a.b.internal.example.com", 80, true),
+      ("internal.example.com", 80, false),
+      ("db.internal.example.org", 80, false),
+      ("vault.secret.internal.example.com", 443, false),
+      ("localhost", 8000, true),
+      ("localhost", 8100, true),
+      ("localhost", 8080, false),
+      ("localhost", 7999, false),
+      ("localhost", 8101, false),
+      ("v1.api.example.com", 443, true),
+      ("v1.api.example.com", 80, false),
+    ];
+    for (host, port, is_ok) in net_tests {
+      assert_eq!(
+        is_ok,
+        perms.net.check(&(host, Some(port)), None).is_ok(),
+        "{}:{}",
+        host,
+        port,
+      );
+    }
+
+    assert_eq!(
+      perms.net.query(Some(&("db.internal.example.com", None))),
+      PermissionState::Granted
+    );
+    assert_eq!(
+      perms.net.query(Some(&("localhost", Some(8050)))),
+      PermissionState::Granted
+    );
+    assert_eq!(
+      perms.net.query(Some(&("localhost", Some(8080)))),
+      PermissionState::Denied
+    );
+    assert_eq!(
+      perms.net.query(Some(&("localhost", None))),
+      PermissionState::Prompt
+    );
+
+    let desc = NetDescriptor::from_str("*.example.com:8000-8100").unwrap();
+    assert_eq!(desc.to_string(), "*.example.com:8000-8100");
+    assert!(NetDescriptor::from_str("api.*.example.com").is_err());
+    assert!(NetDescriptor::from_str("localhost:8100-8000").is_err());
+  }
+
   #[test]
   fn test_deserialize_child_permissions_arg() {
     set_prompter(Box::new(TestPrompter));
@@ -1,15 +1,26 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
 use deno_terminal::colors;
 use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
 use std::fmt::Write;
 use std::io::BufRead;
+use std::io::BufReader;
 use std::io::IsTerminal;
+use std::io::Read;
 use std::io::StderrLock;
 use std::io::StdinLock;
 use std::io::Write as IoWrite;
+use std::net::SocketAddr;
+use std::net::TcpStream;
+use std::net::ToSocketAddrs;
+use std::time::Duration;
+use std::time::Instant;
 
 /// Helper function to make control characters visible so users can see the underlying filename.
 fn escape_control_characters(s: &str) -> std::borrow::Cow<str> {
@@ -70,6 +81,12 @@ pub fn permission_prompt(
   r
 }
 
+/// Replaces the prompter used for the permission prompts, which asks on the
+/// terminal by default.
+pub fn set_prompter(prompter: Box<dyn PermissionPrompter>) {
+  *PERMISSION_PROMPTER.lock() = prompter;
+}
+
 pub fn set_prompt_callbacks(
   before_callback: PromptCallback,
   after_callback: PromptCallback,
@@ -96,7 +113,6 @@ fn clear_stdin(
   _stdin_lock: &mut StdinLock,
   _stderr_lock: &mut StderrLock,
 ) -> Result<(), AnyError> {
-  use deno_core::anyhow::bail;
   use std::mem::MaybeUninit;
 
   const STDIN_FD: i32 = 0;
@@ -151,7 +167,6 @@ fn clear_stdin(
   stdin_lock: &mut StdinLock,
   stderr_lock: &mut StderrLock,
 ) -> Result<(), AnyError> {
-  use deno_core::anyhow::bail;
   use winapi::shared::minwindef::TRUE;
   use winapi::shared::minwindef::UINT;
   use winapi::shared::minwindef::WORD;
@@ -420,6 +435,242 @@ impl PermissionPrompter for TtyPrompter {
   }
 }
 
+/// The address of the socket that a `SocketPrompter` sends the prompts to,
+/// either `<host>:<port>` of a loopback address or `unix:<path>` on Unix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptSocketAddress {
+  Tcp(SocketAddr),
+  #[cfg(unix)]
+  Unix(std::path::PathBuf),
+}
+
+impl std::str::FromStr for PromptSocketAddress {
+  type Err = AnyError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Some(path) = s.strip_prefix("unix:") {
+      #[cfg(unix)]
+      return Ok(Self::Unix(path.into()));
+      #[cfg(not(unix))]
+      {
+        let _ = path;
+        bail!("Unix sockets are not supported on this platform: {}", s);
+      }
+    }
+    if s.is_empty() {
+      bail!("Missing permission prompt socket address");
+    }
+    let addresses = s.to_socket_addrs()?.collect::<Vec<_>>();
+    // the prompts can't be answered from another machine
+    match addresses.first() {
+      Some(address) if addresses.iter().all(|a| a.ip().is_loopback()) => {
+        Ok(Self::Tcp(*address))
+      }
+      Some(_) => bail!(
+        "The permission prompt socket must be on a loopback address: {}",
+        s
+      ),
+      None => bail!("Invalid permission prompt socket address: {}", s),
+    }
+  }
+}
+
+enum PromptSocketStream {
+  Tcp(TcpStream),
+  #[cfg(unix)]
+  Unix(std::os::unix::net::UnixStream),
+}
+
+impl PromptSocketStream {
+  fn connect(
+    address: &PromptSocketAddress,
+    timeout: Duration,
+  ) -> std::io::Result<Self> {
+    match address {
+      PromptSocketAddress::Tcp(address) => {
+        TcpStream::connect_timeout(address, timeout).map(Self::Tcp)
+      }
+      #[cfg(unix)]
+      PromptSocketAddress::Unix(path) => {
+        std::os::unix::net::UnixStream::connect(path).map(Self::Unix)
+      }
+    }
+  }
+
+  fn set_read_timeout(&self, timeout: Duration) -> std::io::Result<()> {
+    match self {
+      Self::Tcp(stream) => stream.set_read_timeout(Some(timeout)),
+      #[cfg(unix)]
+      Self::Unix(stream) => stream.set_read_timeout(Some(timeout)),
+    }
+  }
+
+  fn set_write_timeout(&self, timeout: Duration) -> std::io::Result<()> {
+    match self {
+      Self::Tcp(stream) => stream.set_write_timeout(Some(timeout)),
+      #[cfg(unix)]
+      Self::Unix(stream) => stream.set_write_timeout(Some(timeout)),
+    }
+  }
+}
+
+impl Read for PromptSocketStream {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    match self {
+      Self::Tcp(stream) => stream.read(buf),
+      #[cfg(unix)]
+      Self::Unix(stream) => stream.read(buf),
+    }
+  }
+}
+
+impl IoWrite for PromptSocketStream {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    match self {
+      Self::Tcp(stream) => stream.write(buf),
+      #[cfg(unix)]
+      Self::Unix(stream) => stream.write(buf),
+    }
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    match self {
+      Self::Tcp(stream) => stream.flush(),
+      #[cfg(unix)]
+      Self::Unix(stream) => stream.flush(),
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SocketPromptRequest<'a> {
+  id: u64,
+  message: &'a str,
+  name: &'a str,
+  api_name: Option<&'a str>,
+  is_unary: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum SocketPromptAnswer {
+  Allow,
+  Deny,
+  AllowAll,
+}
+
+#[derive(Deserialize)]
+struct SocketPromptResponse {
+  id: u64,
+  response: SocketPromptAnswer,
+}
+
+/// Sends the prompts as JSON lines to a local socket, so that an IDE or a
+/// supervisor can answer them when there's no terminal:
+///
+/// ```text
+/// -> {"id":1,"message":"read access to \"/data\"","name":"read","apiName":"Deno.readFile()","isUnary":true}
+/// <- {"id":1,"response":"allow"}
+/// ```
+///
+/// The response is "allow", "deny" or "allowAll". A prompt is denied when it
+/// isn't answered before the timeout or the socket fails.
+pub struct SocketPrompter {
+  address: PromptSocketAddress,
+  timeout: Duration,
+  connection: Option<BufReader<PromptSocketStream>>,
+  next_id: u64,
+}
+
+impl SocketPrompter {
+  pub fn new(address: PromptSocketAddress, timeout: Duration) -> Self {
+    Self {
+      address,
+      timeout,
+      connection: None,
+      next_id: 1,
+    }
+  }
+
+  fn request(
+    &mut self,
+    request: &SocketPromptRequest,
+  ) -> Result<SocketPromptAnswer, AnyError> {
+    // connecting, sending and answering the prompt share the timeout
+    let deadline = Instant::now() + self.timeout;
+    let remaining = || {
+      let remaining = deadline.saturating_duration_since(Instant::now());
+      if remaining.is_zero() {
+        bail!("Timed out");
+      }
+      Ok(remaining)
+    };
+    let connection = match &mut self.connection {
+      Some(connection) => connection,
+      None => {
+        self
+          .connection
+          .insert(BufReader::new(PromptSocketStream::connect(
+            &self.address,
+            remaining()?,
+          )?))
+      }
+    };
+    let mut line = serde_json::to_vec(request)?;
+    line.push(b'\n');
+    connection.get_ref().set_write_timeout(remaining()?)?;
+    connection.get_mut().write_all(&line)?;
+    loop {
+      connection.get_ref().set_read_timeout(remaining()?)?;
+      let mut line = String::new();
+      if connection.read_line(&mut line)? == 0 {
+        bail!("Connection closed");
+      }
+      let response: SocketPromptResponse = serde_json::from_str(&line)?;
+      // skip the late responses of prompts that timed out
+      if response.id == request.id {
+        return Ok(response.response);
+      }
+    }
+  }
+}
+
+impl PermissionPrompter for SocketPrompter {
+  fn prompt(
+    &mut self,
+    message: &str,
+    name: &str,
+    api_name: Option<&str>,
+    is_unary: bool,
+  ) -> PromptResponse {
+    if message.len() > MAX_PERMISSION_PROMPT_LENGTH {
+      return PromptResponse::Deny;
+    }
+    let id = self.next_id;
+    self.next_id += 1;
+    let request = SocketPromptRequest {
+      id,
+      message,
+      name,
+      api_name,
+      is_unary,
+    };
+    match self.request(&request) {
+      Ok(SocketPromptAnswer::Allow) => PromptResponse::Allow,
+      Ok(SocketPromptAnswer::AllowAll) if is_unary => PromptResponse::AllowAll,
+      Ok(SocketPromptAnswer::AllowAll) => PromptResponse::Allow,
+      Ok(SocketPromptAnswer::Deny) => PromptResponse::Deny,
+      Err(err) => {
+        log::debug!("Permission prompt over socket failed: {:#}", err);
+        // the stream might be in the middle of a line, so reconnect next time
+        self.connection = None;
+        PromptResponse::Deny
+      }
+    }
+  }
+}
+
 #[cfg(test)]
 pub mod tests {
   use super::*;
@@ -458,7 +709,79 @@ pub mod tests {
     }
   }
 
-  pub fn set_prompter(prompter: Box<dyn PermissionPrompter>) {
-    *PERMISSION_PROMPTER.lock() = prompter;
+  #[test]
+  fn socket_prompter() {
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = PromptSocketAddress::Tcp(listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+      let (stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+      let mut writer = stream;
+      let mut requests = Vec::new();
+      for answer in ["allowAll", "deny"] {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+        writeln!(
+          writer,
+          r#"{{"id":{},"response":"{}"}}"#,
+          request["id"], answer
+        )
+        .unwrap();
+        requests.push(request);
+      }
+      // don't answer the last one, keeping the connection open until the
+      // prompt times out
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      (requests, writer)
+    });
+
+    let mut prompter = SocketPrompter::new(address, Duration::from_millis(500));
+    assert_eq!(
+      prompter.prompt(
+        "read access to \"/data\"",
+        "read",
+        Some("Deno.readFile()"),
+        true
+      ),
+      PromptResponse::AllowAll
+    );
+    assert_eq!(
+      prompter.prompt("env access", "env", None, true),
+      PromptResponse::Deny
+    );
+    assert_eq!(
+      prompter.prompt("net access", "net", None, true),
+      PromptResponse::Deny
+    );
+    let (requests, _stream) = server.join().unwrap();
+    assert_eq!(
+      requests[0],
+      serde_json::json!({
+        "id": 1,
+        "message": "read access to \"/data\"",
+        "name": "read",
+        "apiName": "Deno.readFile()",
+        "isUnary": true,
+      })
+    );
+  }
+
+  #[test]
+  fn prompt_socket_address() {
+    use std::str::FromStr;
+
+    assert_eq!(
+      PromptSocketAddress::from_str("127.0.0.1:9000").unwrap(),
+      PromptSocketAddress::Tcp("127.0.0.1:9000".parse().unwrap())
+    );
+    assert!(PromptSocketAddress::from_str("[::1]:9000").is_ok());
+    let err = PromptSocketAddress::from_str("0.0.0.0:9000").unwrap_err();
+    assert!(err.to_string().contains("loopback"));
+    assert!(PromptSocketAddress::from_str("192.168.1.2:9000").is_err());
+    assert!(PromptSocketAddress::from_str("").is_err());
   }
 }
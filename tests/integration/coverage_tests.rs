@@ -4,6 +4,7 @@ use deno_core::serde_json;
 use test_util as util;
 use test_util::TempDir;
 use util::assert_contains;
+use util::assert_not_contains;
 use util::assert_starts_with;
 use util::env_vars_for_npm_tests;
 use util::PathRef;
@@ -588,16 +589,16 @@ fn test_summary_reporter() {
 
     output.assert_exit_code(0);
     output.assert_matches_text(
-      "----------------------------------
-File         | Branch % | Line % |
-----------------------------------
- bar.ts      |      0.0 |   57.1 |
- baz/quux.ts |      0.0 |   28.6 |
- baz/qux.ts  |    100.0 |  100.0 |
- foo.ts      |     50.0 |   76.9 |
-----------------------------------
- All files   |     40.0 |   61.0 |
-----------------------------------
+      "-----------------------------------------------
+File         | Branch % | Function % | Line % |
+-----------------------------------------------
+ bar.ts      |      0.0 |      100.0 |   57.1 |
+ baz/quux.ts |      0.0 |      100.0 |   28.6 |
+ baz/qux.ts  |    100.0 |      100.0 |  100.0 |
+ foo.ts      |     50.0 |      100.0 |   76.9 |
+-----------------------------------------------
+ All files   |     40.0 |      100.0 |   61.0 |
+-----------------------------------------------
 ",
     );
   }
@@ -615,19 +616,280 @@ File         | Branch % | Line % |
 
     output.assert_exit_code(0);
     output.assert_matches_text(
-      "---------------------------------
-File        | Branch % | Line % |
----------------------------------
- baz/qux.ts |    100.0 |  100.0 |
- foo.ts     |     50.0 |   76.9 |
----------------------------------
- All files  |     66.7 |   85.0 |
----------------------------------
+      "----------------------------------------------
+File        | Branch % | Function % | Line % |
+----------------------------------------------
+ baz/qux.ts |    100.0 |      100.0 |  100.0 |
+ foo.ts     |     50.0 |      100.0 |   76.9 |
+----------------------------------------------
+ All files  |     66.7 |      100.0 |   85.0 |
+----------------------------------------------
 ",
     );
   }
 }
 
+#[test]
+fn test_uncovered_functions() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/uncovered_functions".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--html".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec!["coverage".to_string(), format!("{}/", tempdir)])
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.combined_output(),
+    "| Branch % | Function % | Line % |"
+  );
+  assert_contains!(output.combined_output(), "|       50.0 |");
+
+  let math_ts_html = tempdir
+    .join("html")
+    .join("lib")
+    .join("math.ts.html")
+    .read_to_string();
+  assert_contains!(math_ts_html, "<span class='quiet'>Functions</span>");
+  assert_contains!(math_ts_html, "<h2>Uncovered functions</h2>");
+  assert_contains!(
+    math_ts_html,
+    "<li><a href='#L5'>subtract</a> <span class='quiet'>line 5</span></li>"
+  );
+  assert_not_contains!(math_ts_html, "<a href='#L1'>add</a>");
+
+  let index_html = tempdir.join("html").join("index.html").read_to_string();
+  assert_contains!(index_html, "<th class='pct'>Functions</th>");
+  assert_contains!(
+    index_html,
+    "<li><a href='lib/math.ts.html#L5'>subtract</a> <span class='quiet'>lib/math.ts:5</span></li>"
+  );
+}
+
+#[test]
+fn test_cobertura_reporter() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--cobertura".to_string(),
+      format!("--output={}", tempdir.join("cobertura.xml")),
+      format!("{}/", tempdir),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let xml = tempdir.join("cobertura.xml").read_to_string();
+  assert_starts_with!(xml, "<?xml version=\"1.0\" ?>");
+  assert_contains!(xml, "branch-rate=\"0.4000\" timestamp=");
+  assert_contains!(xml, "<package name=\".\"");
+  assert_contains!(xml, "<package name=\"baz\"");
+  assert_contains!(
+    xml,
+    "<class name=\"bar.ts\" filename=\"bar.ts\" line-rate=\"0.5714\" branch-rate=\"0.0000\""
+  );
+  assert_contains!(
+    xml,
+    "<class name=\"qux.ts\" filename=\"baz/qux.ts\" line-rate=\"1.0000\" branch-rate=\"1.0000\""
+  );
+  assert_contains!(xml, "<method name=\"bar\"");
+  assert_contains!(xml, "branch=\"true\" condition-coverage=\"");
+}
+
+#[test]
+fn test_json_summary_reporter() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--json-summary".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  let summary: serde_json::Value =
+    serde_json::from_str(output.stdout()).unwrap();
+  let summary = summary.as_object().unwrap();
+  // the total and one entry per file
+  assert_eq!(summary.len(), 5);
+  assert_eq!(summary["total"]["branches"]["pct"], 40.0);
+  assert_eq!(summary["total"]["functions"]["pct"], 100.0);
+  let line_pct = summary["total"]["lines"]["pct"].as_f64().unwrap();
+  assert!((line_pct - 61.0).abs() < 0.1, "{line_pct}");
+
+  let (_, qux) = summary
+    .iter()
+    .find(|(path, _)| path.ends_with("qux.ts"))
+    .unwrap();
+  assert_eq!(qux["lines"]["pct"], 100.0);
+  assert_eq!(qux["lines"]["covered"], qux["lines"]["total"]);
+  assert_eq!(qux["branches"]["pct"], 100.0);
+}
+
+#[test]
+fn test_coverage_thresholds() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  // all files together cover 61% of the lines and 40% of the branches
+  {
+    let output = context
+      .new_command()
+      .args_vec(vec![
+        "coverage".to_string(),
+        "--threshold-lines=60".to_string(),
+        "--threshold-branches=40".to_string(),
+        format!("{}/", tempdir),
+      ])
+      .run();
+
+    output.assert_exit_code(0);
+    output.skip_output_check();
+  }
+
+  {
+    let output = context
+      .new_command()
+      .args_vec(vec![
+        "coverage".to_string(),
+        "--threshold-lines=60".to_string(),
+        "--threshold-branches=50".to_string(),
+        format!("{}/", tempdir),
+      ])
+      .run();
+
+    output.assert_exit_code(1);
+    assert_contains!(
+      output.combined_output(),
+      "error: Coverage thresholds not met:\n  All files: branches 40.0% is below 50%\n"
+    );
+  }
+
+  {
+    let output = context
+      .new_command()
+      .args_vec(vec![
+        "coverage".to_string(),
+        "--threshold-lines=50".to_string(),
+        "--threshold-per-file".to_string(),
+        format!("{}/", tempdir),
+      ])
+      .run();
+
+    output.assert_exit_code(1);
+    assert_contains!(
+      output.combined_output(),
+      "error: Coverage thresholds not met:\n  baz/quux.ts: lines 28.6% is below 50%\n"
+    );
+  }
+
+  // thresholds from the config file
+  {
+    context.temp_dir().path().join("deno.json").write_json(
+      &serde_json::json!({
+        "coverage": {
+          "thresholds": { "lines": 70, "perFile": true }
+        }
+      }),
+    );
+    let output = context
+      .new_command()
+      .current_dir(context.temp_dir().path())
+      .args_vec(vec!["coverage".to_string(), format!("{}/", tempdir)])
+      .run();
+
+    output.assert_exit_code(1);
+    assert_contains!(
+      output.combined_output(),
+      concat!(
+        "error: Coverage thresholds not met:\n",
+        "  All files: lines 61.0% is below 70%\n",
+        "  bar.ts: lines 57.1% is below 70%\n",
+        "  baz/quux.ts: lines 28.6% is below 70%\n",
+      )
+    );
+  }
+}
+
 #[test]
 fn test_collect_summary_with_no_matches() {
   let context: TestContext = TestContext::default();
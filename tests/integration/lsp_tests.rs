@@ -9761,6 +9761,55 @@ fn lsp_format_no_changes() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_range_format() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "console.log('a')\n\nconsole.log('b')\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/rangeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "range": {
+        "start": { "line": 2, "character": 0 },
+        "end": { "line": 2, "character": 16 }
+      },
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  // only the selected line is formatted
+  assert_eq!(
+    res,
+    json!([{
+      "range": {
+        "start": { "line": 2, "character": 12 },
+        "end": { "line": 2, "character": 13 }
+      },
+      "newText": "\""
+    }, {
+      "range": {
+        "start": { "line": 2, "character": 14 },
+        "end": { "line": 2, "character": 16 }
+      },
+      "newText": "\");"
+    }])
+  );
+  client.shutdown();
+}
+
 #[test]
 fn lsp_format_error() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -11145,6 +11194,60 @@ fn lsp_lint_with_config() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_lint_type_aware_rules() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    json!({
+      "lint": {
+        "rules": {
+          "include": ["no-floating-promises"],
+          "tags": [],
+        },
+      },
+    })
+    .to_string(),
+  );
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  let diagnostics = client.did_open(json!({
+    "textDocument": {
+      "uri": temp_dir.uri().join("file.ts").unwrap(),
+      "languageId": "typescript",
+      "version": 1,
+      "text": "async function save() {}\nsave();\nawait save();\n",
+    },
+  }));
+  let diagnostics = diagnostics
+    .all()
+    .into_iter()
+    .filter(|d| d.source.as_deref() == Some("deno-lint"))
+    .collect::<Vec<_>>();
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(
+    diagnostics[0].code,
+    Some(lsp::NumberOrString::String(
+      "no-floating-promises".to_string()
+    ))
+  );
+  assert_eq!(
+    diagnostics[0].range,
+    lsp::Range {
+      start: lsp::Position {
+        line: 1,
+        character: 0,
+      },
+      end: lsp::Position {
+        line: 1,
+        character: 6,
+      },
+    }
+  );
+  client.shutdown();
+}
+
 #[test]
 fn lsp_lint_exclude_with_config() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use deno_core::serde_json::json;
 use deno_core::url::Url;
 use test_util as util;
@@ -355,6 +356,85 @@ fn junit_path() {
     .assert_matches_text("<?xml [WILDCARD]");
 }
 
+#[test]
+fn json_path() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "test.js",
+    r#"Deno.test("passes", async (t) => {
+  console.log("hello");
+  await t.step("step", () => {});
+});
+Deno.test("fails", () => {
+  throw new Error("boom");
+});
+"#,
+  );
+  let output = context
+    .new_command()
+    .args("test --json-path=sub_dir/output.jsonl test.js")
+    .run();
+  output.assert_exit_code(1);
+  // the pretty reporter still writes to stdout
+  assert_contains!(output.combined_output(), "passes ... ok");
+
+  let events = temp_dir
+    .path()
+    .join("sub_dir/output.jsonl")
+    .read_to_string()
+    .lines()
+    .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+    .collect::<Vec<_>>();
+  let types = events
+    .iter()
+    .map(|event| event["type"].as_str().unwrap())
+    .filter(|ty| !matches!(*ty, "register" | "stepRegister" | "stepWait"))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    types,
+    vec![
+      "plan",
+      "wait",
+      "output",
+      "stepResult",
+      "result",
+      "wait",
+      "result",
+      "summary"
+    ]
+  );
+
+  let plan = &events.iter().find(|e| e["type"] == "plan").unwrap();
+  assert_eq!(plan["origin"], "./test.js");
+  assert_eq!(plan["total"], 2);
+
+  let results = events
+    .iter()
+    .filter(|e| e["type"] == "result")
+    .collect::<Vec<_>>();
+  assert_eq!(results[0]["name"], "passes");
+  assert_eq!(results[0]["status"], "ok");
+  assert_eq!(results[0]["location"]["file"], "./test.js");
+  assert_eq!(results[0]["location"]["line"], 1);
+  assert!(results[0]["duration"].is_u64());
+  assert_eq!(results[1]["name"], "fails");
+  assert_eq!(results[1]["status"], "failed");
+  assert_eq!(results[1]["failure"]["kind"], "jsError");
+  assert_eq!(results[1]["failure"]["message"], "Uncaught Error: boom");
+  assert_eq!(
+    results[1]["failure"]["error"]["exceptionMessage"],
+    "Uncaught Error: boom"
+  );
+
+  let summary = events.last().unwrap();
+  assert_eq!(summary["type"], "summary");
+  assert_eq!(summary["passed"], 1);
+  assert_eq!(summary["failed"], 1);
+  assert_eq!(summary["passedSteps"], 1);
+  assert_eq!(summary["ok"], false);
+}
+
 itest!(clear_timeout {
   args: "test test/clear_timeout.ts",
   exit_code: 0,
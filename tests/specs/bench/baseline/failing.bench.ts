@@ -0,0 +1,3 @@
+Deno.bench("fails", () => {
+  throw new Error("fail");
+});
@@ -0,0 +1,9 @@
+const iterations = Deno.env.get("SLOW") ? 100_000 : 1_000;
+
+Deno.bench("sum", () => {
+  let sum = 0;
+  for (let i = 0; i < iterations; i++) {
+    sum += i;
+  }
+  return sum;
+});
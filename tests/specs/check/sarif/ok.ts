@@ -0,0 +1,2 @@
+const value: number = 1;
+console.log(value);
@@ -0,0 +1 @@
+const x=1;
@@ -0,0 +1,9 @@
+const color = "red";
+const id = 1;
+
+export const styles = css`:host{display:block;color:${color}}`;
+
+export const query = sql`select * from users where id = ${id}`;
+
+// other tags are left as they are
+export const text = dedent`  a  `;
@@ -0,0 +1 @@
+select id, name from users where id = 1;
@@ -0,0 +1,2 @@
+export let a: any;
+export let b: any;
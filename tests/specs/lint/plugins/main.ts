@@ -0,0 +1,5 @@
+import chunk from "npm:lodash/chunk";
+
+export const cnt = chunk([1, 2, 3], 2).length;
+// deno-lint-ignore team/no-abbreviations
+export const fmt = "json";
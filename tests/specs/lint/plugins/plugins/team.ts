@@ -0,0 +1,59 @@
+interface Node {
+  type: string;
+  range: [number, number];
+  text: string;
+  children: Node[];
+}
+
+interface Context {
+  report(report: {
+    node: Node;
+    message: string;
+    hint?: string;
+    fix?: { node: Node; text: string; description: string };
+  }): void;
+}
+
+const ABBREVIATIONS = new Map([
+  ["cnt", "count"],
+  ["fmt", "format"],
+]);
+
+export default {
+  name: "team",
+  rules: {
+    "banned-imports": {
+      create(context: Context) {
+        return {
+          ImportDecl(node: Node) {
+            const source = node.children.find((child) => child.type === "Str");
+            if (source?.text.includes("lodash")) {
+              context.report({
+                node,
+                message: "Importing lodash is not allowed",
+                hint: "Use the standard library instead",
+              });
+            }
+          },
+        };
+      },
+    },
+    "no-abbreviations": {
+      docs: "https://example.com/lint/no-abbreviations",
+      create(context: Context) {
+        return {
+          Ident(node: Node) {
+            const name = ABBREVIATIONS.get(node.text);
+            if (name !== undefined) {
+              context.report({
+                node,
+                message: "Identifiers should not be abbreviated",
+                fix: { node, text: name, description: `Rename to ${name}` },
+              });
+            }
+          },
+        };
+      },
+    },
+  },
+};
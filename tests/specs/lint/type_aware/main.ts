@@ -0,0 +1,21 @@
+async function save(value: string): Promise<void> {
+  await Promise.resolve(value);
+}
+
+export function run(values: string[]) {
+  save("a");
+  if (save("b")) {
+    console.log("saved");
+  }
+  values.forEach(async (value) => {
+    await save(value);
+  });
+  void save("c");
+  save("d").catch(() => {});
+}
+
+export async function wait() {
+  await 1;
+  // deno-lint-ignore no-floating-promises
+  save("e");
+}
@@ -0,0 +1,6 @@
+console.log(Deno.readTextFileSync("data.txt").trim());
+try {
+  Deno.env.get("HOME");
+} catch (err) {
+  console.log(err.name);
+}
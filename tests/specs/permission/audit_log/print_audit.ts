@@ -0,0 +1,12 @@
+const lines = Deno.readTextFileSync("audit.jsonl").trim().split("\n");
+for (const line of lines) {
+  const entry = JSON.parse(line);
+  console.log(
+    entry.kind,
+    entry.result,
+    entry.prompted,
+    entry.apiName,
+    entry.descriptor?.replace(/^.*[\\/]/, ""),
+    entry.stack?.replace(/^.*\//, ""),
+  );
+}
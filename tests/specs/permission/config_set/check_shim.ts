@@ -0,0 +1,5 @@
+const name = Deno.build.os === "windows" ? "main.cmd" : "main";
+const shim = Deno.readTextFileSync(`./bins/bin/${name}`);
+console.log(shim.includes("--permission-set"));
+console.log(/--allow-read=\S*data/.test(shim));
+console.log(shim.includes("--allow-env=HOME"));
@@ -0,0 +1,2 @@
+console.log(Deno.readTextFileSync("./data/hello.txt").trim());
+console.log(Deno.env.get("HOME") !== undefined);
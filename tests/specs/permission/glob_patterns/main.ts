@@ -0,0 +1,32 @@
+for (
+  const path of [
+    "./data/a.json",
+    "./data/nested/b.json",
+    "./data/nested/secret.json",
+    "./data/notes.txt",
+  ]
+) {
+  const { state } = await Deno.permissions.query({ name: "read", path });
+  console.log(path, state);
+}
+
+for (
+  const host of [
+    "api.internal.example.com",
+    "internal.example.com",
+    "localhost:8050",
+    "localhost:9000",
+  ]
+) {
+  const { state } = await Deno.permissions.query({ name: "net", host });
+  console.log(host, state);
+}
+
+console.log(Deno.readTextFileSync("./data/nested/b.json").trim());
+for (const path of ["./data/notes.txt", "./data/nested/secret.json"]) {
+  try {
+    Deno.readTextFileSync(path);
+  } catch (err) {
+    console.log(path, err.name);
+  }
+}
@@ -0,0 +1,4 @@
+const a = Deno.readTextFileSync("data/a.txt").trim();
+const b = Deno.readTextFileSync("data/b.txt").trim();
+console.log(a, b, Deno.env.get("LEARN_PERMISSIONS_TEST") ?? "unset");
+Deno.exit(0);
@@ -0,0 +1,3 @@
+Deno.mkdirSync("dist", { recursive: true });
+Deno.writeTextFileSync("dist/out.txt", Deno.readTextFileSync("src/in.txt"));
+console.log("built");
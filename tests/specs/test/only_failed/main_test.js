@@ -0,0 +1,9 @@
+Deno.test("passes", () => {});
+
+Deno.test("fails when asked to", () => {
+  if (Deno.env.get("FAIL")) {
+    throw new Error("failed");
+  }
+});
+
+Deno.test("also passes", () => {});
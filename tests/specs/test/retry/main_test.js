@@ -0,0 +1,21 @@
+let flakyAttempts = 0;
+Deno.test("flaky", () => {
+  flakyAttempts++;
+  if (flakyAttempts < 2) {
+    throw new Error(`attempt ${flakyAttempts} failed`);
+  }
+});
+
+let stepAttempts = 0;
+Deno.test({
+  name: "flaky steps",
+  retry: 2,
+  async fn(t) {
+    stepAttempts++;
+    await t.step("inner", () => {
+      if (stepAttempts < 3) {
+        throw new Error(`attempt ${stepAttempts} failed`);
+      }
+    });
+  },
+});
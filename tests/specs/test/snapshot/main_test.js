@@ -0,0 +1,10 @@
+Deno.test("object", (t) => {
+  t.assertSnapshot({ b: 2, a: [1, "two"] });
+});
+
+Deno.test("steps", async (t) => {
+  await t.step("inner", (t) => {
+    t.assertSnapshot(Deno.env.get("GREETING") ?? "hello");
+    t.assertSnapshot("world");
+  });
+});
@@ -0,0 +1,7 @@
+Deno.test({
+  name: "no timeout",
+  timeout: 0,
+  fn: () => new Promise((resolve) => setTimeout(resolve, 200)),
+});
+
+Deno.test("hangs", () => new Promise((resolve) => setTimeout(resolve, 100_000)));
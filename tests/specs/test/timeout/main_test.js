@@ -0,0 +1,9 @@
+Deno.test("fast", () => {});
+
+Deno.test({
+  name: "hangs",
+  timeout: 100,
+  fn: () => new Promise((resolve) => setTimeout(resolve, 100_000)),
+});
+
+Deno.test("after", () => {});
@@ -0,0 +1,11 @@
+Deno.test({
+  name: "busy loop",
+  timeout: 100,
+  fn: () => {
+    while (true) {
+      // never yields to the event loop
+    }
+  },
+});
+
+Deno.test("after", () => {});
@@ -0,0 +1,5 @@
+import { add } from "./lib/math.ts";
+
+Deno.test("add", () => {
+  add(1, 2);
+});